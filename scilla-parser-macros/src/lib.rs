@@ -0,0 +1,259 @@
+//! Procedural macros embedding the interface of a Scilla contract at compile time.
+
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use scilla_parser::{parser::parse_type, printer::print_type, Contract, Type};
+use syn::{
+    braced, parenthesized,
+    parse::{Parse, ParseStream},
+    parse_macro_input, Ident, LitStr, Token,
+};
+
+/// Expand to the typed bindings of a contract, see `Contract::rust_bindings`.
+///
+/// The path is relative to the manifest directory of the crate using the macro. Besides Scilla
+/// sources, it can point to the S-expression generated by `scilla-fmt` (`.sexp`) or to a contract
+/// info JSON (`.json`), so that no external tool is needed at build time.
+///
+/// ```ignore
+/// mod zrc2 {
+///     scilla_parser_macros::scilla_contract!("contracts/ZRC2.scilla");
+/// }
+///
+/// let call = zrc2::FungibleToken::new(address).transfer(recipient, 1000);
+/// ```
+///
+/// The transitions the crate relies on can be declared after the path, with their parameters in
+/// Scilla syntax. The build fails with an error pointing at the declaration if the contract no
+/// longer has such a transition:
+///
+/// ```ignore
+/// mod zrc2 {
+///     scilla_parser_macros::scilla_contract!("contracts/ZRC2.scilla" {
+///         transition Transfer(to: ByStr20, amount: Uint128);
+///     });
+/// }
+/// ```
+///
+/// The build also fails if the contract cannot be read, and calls that no longer match the
+/// interface of the contract fail to type-check.
+#[proc_macro]
+pub fn scilla_contract(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as Input);
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    expand(&input, Path::new(&manifest_dir))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The arguments of `scilla_contract!`: a path and the declared transitions.
+struct Input {
+    path: LitStr,
+    transitions: Vec<DeclaredTransition>,
+}
+
+/// `transition Name(p1: T1, p2: T2);`
+struct DeclaredTransition {
+    name: Ident,
+    params: Vec<(Ident, Type)>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut transitions = vec![];
+        if !input.is_empty() {
+            let content;
+            braced!(content in input);
+            while !content.is_empty() {
+                transitions.push(content.parse()?);
+            }
+        }
+        Ok(Self { path, transitions })
+    }
+}
+
+impl Parse for DeclaredTransition {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let keyword: Ident = input.parse()?;
+        if keyword != "transition" {
+            return Err(syn::Error::new(keyword.span(), "Expected `transition`"));
+        }
+        let name = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let mut params = vec![];
+        while !content.is_empty() {
+            let param: Ident = content.parse()?;
+            content.parse::<Token![:]>()?;
+            params.push((param, parse_declared_type(&content)?));
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        input.parse::<Token![;]>()?;
+        Ok(Self { name, params })
+    }
+}
+
+/// Parse the tokens of a type up to the next comma separating parameters. Commas between the
+/// fields of an address type, inside `with ... end`, belong to the type.
+fn parse_declared_type(input: ParseStream) -> syn::Result<Type> {
+    let span = input.span();
+    let mut tokens = TokenStream::new();
+    let mut depth = 0usize;
+    while !input.is_empty() && (depth > 0 || !input.peek(Token![,])) {
+        let token: TokenTree = input.parse()?;
+        if let TokenTree::Ident(ident) = &token {
+            if ident == "with" {
+                depth += 1;
+            } else if ident == "end" {
+                depth = depth.saturating_sub(1);
+            }
+        }
+        tokens.extend([token]);
+    }
+    parse_type(&tokens.to_string()).map_err(|e| syn::Error::new(span, e))
+}
+
+/// Check that the contract has the declared transitions, with the same parameters.
+fn check_transitions(contract: &Contract, declared: &[DeclaredTransition]) -> syn::Result<()> {
+    for transition in declared {
+        let name = transition.name.to_string();
+        let Some(actual) = contract.transitions.iter().find(|t| t.name == name) else {
+            let message = format!("`{}` has no transition `{name}`", contract.name);
+            return Err(syn::Error::new(transition.name.span(), message));
+        };
+        let matches = actual.params.len() == transition.params.len()
+            && actual
+                .params
+                .iter()
+                .zip(&transition.params)
+                .all(|(a, (n, ty))| *n == a.name && a.r#type == *ty);
+        if !matches {
+            let params: Vec<String> = actual
+                .params
+                .iter()
+                .map(|p| format!("{}: {}", p.name, print_type(&p.r#type)))
+                .collect();
+            let message = format!(
+                "The transition `{name}` of `{}` is `{name}({})`",
+                contract.name,
+                params.join(", ")
+            );
+            return Err(syn::Error::new(transition.name.span(), message));
+        }
+    }
+    Ok(())
+}
+
+fn read_contract(path: &Path) -> Result<Contract, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+    let contract = match path.extension().and_then(|e| e.to_str()) {
+        Some("sexp") => content.parse(),
+        Some("json") => Contract::from_contract_info_json(&content),
+        _ => Contract::from_source(&content),
+    };
+    contract.map_err(|e| format!("Cannot parse {}: {e}", path.display()))
+}
+
+fn expand(input: &Input, manifest_dir: &Path) -> Result<TokenStream, syn::Error> {
+    let path = &input.path;
+    let full_path: PathBuf = manifest_dir.join(path.value());
+    let contract = read_contract(&full_path).map_err(|e| syn::Error::new(path.span(), e))?;
+    check_transitions(&contract, &input.transitions)?;
+    let bindings = contract.rust_bindings();
+
+    // Rebuild when the contract changes.
+    let full_path = full_path.display().to_string();
+    Ok(quote! {
+        const _: &[u8] = include_bytes!(#full_path);
+        #bindings
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::Span;
+
+    fn manifest_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    fn input(path: &str) -> Input {
+        Input {
+            path: LitStr::new(path, Span::call_site()),
+            transitions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_expand_formats() {
+        for path in [
+            "../tests/contracts/HelloWorld.scilla",
+            "../tests/contract_info/HelloWorld.json",
+        ] {
+            let tokens = expand(&input(path), &manifest_dir()).unwrap();
+            assert!(tokens.to_string().contains("pub fn set_hello"), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_expand_errors() {
+        let error =
+            expand(&input("../tests/contracts/Missing.scilla"), &manifest_dir()).unwrap_err();
+        assert!(error.to_string().starts_with("Cannot read"));
+
+        let error = expand(&input("Cargo.toml"), &manifest_dir()).unwrap_err();
+        assert!(error.to_string().starts_with("Cannot parse"));
+        assert!(error.to_string().contains("Syntax error at line 1"));
+    }
+
+    #[test]
+    fn test_declared_transitions() {
+        let expand = |tokens: &str| {
+            let input = syn::parse_str::<Input>(tokens).unwrap();
+            expand(&input, &manifest_dir())
+        };
+        let path = r#""../tests/contracts/ZRC2.scilla""#;
+        expand(&format!(
+            "{path} {{
+                transition Transfer(to: ByStr20, amount: Uint128);
+                transition Mint(recipient: ByStr20, amount: Uint128);
+            }}"
+        ))
+        .unwrap();
+
+        let error = expand(&format!(
+            "{path} {{ transition Transfer(to: ByStr20, amount: Uint32); }}"
+        ))
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The transition `Transfer` of `FungibleToken` is `Transfer(to: ByStr20, amount: Uint128)`"
+        );
+
+        let error = expand(&format!("{path} {{ transition Transfers(); }}")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "`FungibleToken` has no transition `Transfers`"
+        );
+    }
+
+    #[test]
+    fn test_declared_address_types() {
+        let input = syn::parse_str::<Input>(
+            r#""x.scilla" {
+                transition Read(token: ByStr20 with contract field a: Uint128, field b: Bool end, n: Uint32);
+            }"#,
+        )
+        .unwrap();
+        let params = &input.transitions[0].params;
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[1].1, Type::Uint32);
+    }
+}
@@ -0,0 +1,39 @@
+use pretty_assertions::assert_eq;
+
+use scilla_parser::bindings::json;
+use scilla_parser_macros::scilla_contract;
+
+#[allow(dead_code)]
+mod zrc2 {
+    super::scilla_contract!("../tests/contracts/ZRC2.scilla" {
+        transition IncreaseAllowance(spender: ByStr20, amount: Uint128);
+    });
+}
+
+#[allow(dead_code)]
+mod hello_world {
+    super::scilla_contract!("../tests/contract_info/HelloWorld.json");
+}
+
+#[test]
+fn test_bindings_from_scilla_source() {
+    let token = zrc2::FungibleToken::new([0x1a; 20]);
+    let call = token.increase_allowance([0xbb; 20], 50);
+    assert_eq!(call.tag, "IncreaseAllowance");
+    assert_eq!(call.params[1].value, json!("50"));
+
+    let state = zrc2::FungibleTokenState(json!({"total_supply": "1000"}));
+    assert_eq!(state.total_supply().unwrap(), 1000);
+}
+
+#[test]
+fn test_bindings_from_contract_info() {
+    let contract = hello_world::HelloWorld::new([0x1a; 20]);
+    assert_eq!(
+        contract.set_hello("Hi".to_string()).to_json(),
+        json!({
+            "_tag": "setHello",
+            "params": [{"vname": "msg", "type": "String", "value": "Hi"}]
+        })
+    );
+}
@@ -0,0 +1,344 @@
+//! Detection of the access control of transitions: which callers a transition accepts, inferred
+//! from the checks it performs on `_sender` and `_origin` before changing the state.
+
+use std::fmt::Display;
+
+use crate::{
+    ast::{Component, ContractDef, ExprKind, Pattern, Stmt, StmtKind},
+    Contract, Error,
+};
+
+/// The implicit parameter identifying the caller of a transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Caller {
+    /// `_sender`, the account or contract calling the transition.
+    Sender,
+    /// `_origin`, the account signing the transaction.
+    Origin,
+}
+
+impl Display for Caller {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Caller::Sender => write!(f, "_sender"),
+            Caller::Origin => write!(f, "_origin"),
+        }
+    }
+}
+
+/// A check on the caller of a transition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Guard {
+    /// The caller is the address stored in a field or immutable parameter, e.g.
+    /// `builtin eq _sender contract_owner`.
+    Is { caller: Caller, owner: String },
+    /// The caller is a key of a map, e.g. `exists admins[_sender]`.
+    MemberOf { caller: Caller, map: String },
+}
+
+impl Display for Guard {
+    /// `_sender == contract_owner` or `_sender in admins`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Guard::Is { caller, owner } => write!(f, "{caller} == {owner}"),
+            Guard::MemberOf { caller, map } => write!(f, "{caller} in {map}"),
+        }
+    }
+}
+
+/// The inferred authorization requirement of a transition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Authorization {
+    pub transition: String,
+    /// The checks holding whenever the transition changes a field, or when it returns if it does
+    /// not change any.
+    pub guards: Vec<Guard>,
+    /// Whether the transition writes, updates or deletes from a field, directly or through the
+    /// procedures it calls.
+    pub mutates_state: bool,
+}
+
+impl Authorization {
+    /// Whether only some callers can use the transition.
+    pub fn is_restricted(&self) -> bool {
+        !self.guards.is_empty()
+    }
+
+    /// Whether anyone can change the state through the transition.
+    pub fn is_unguarded(&self) -> bool {
+        self.mutates_state && self.guards.is_empty()
+    }
+}
+
+impl Display for Authorization {
+    /// `Pause: _sender == contract_owner`, `Transfer: anyone` or `Reset: anyone (mutates state)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_restricted() {
+            let guards: Vec<String> = self.guards.iter().map(ToString::to_string).collect();
+            write!(f, "{}: {}", self.transition, guards.join(" and "))
+        } else if self.mutates_state {
+            write!(f, "{}: anyone (mutates state)", self.transition)
+        } else {
+            write!(f, "{}: anyone", self.transition)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AuthorizationList(pub Vec<Authorization>);
+
+impl std::ops::Deref for AuthorizationList {
+    type Target = Vec<Authorization>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AuthorizationList {
+    /// The authorization requirement of the transition `name`.
+    pub fn of(&self, name: &str) -> Option<&Authorization> {
+        self.iter().find(|a| a.transition == name)
+    }
+
+    /// Transitions only some callers can use, e.g. owner-only transitions.
+    pub fn restricted(&self) -> impl Iterator<Item = &Authorization> {
+        self.iter().filter(|a| a.is_restricted())
+    }
+
+    /// Transitions changing the state without checking their caller.
+    pub fn unguarded(&self) -> impl Iterator<Item = &Authorization> {
+        self.iter().filter(|a| a.is_unguarded())
+    }
+}
+
+/// What the analysis knows about a variable.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Caller(Caller),
+    /// The value of a field or immutable parameter.
+    Owner(String),
+    /// A `Bool` that is `True` when the guard holds.
+    Check(Guard),
+    Unknown,
+}
+
+/// The guards holding after some statements, or `None` if they always throw.
+type Held = Option<Vec<Guard>>;
+
+struct Analysis<'a> {
+    contract: &'a ContractDef,
+    /// The procedures being analyzed, to stop on malformed recursive calls.
+    stack: Vec<&'a str>,
+    /// The guards holding at each change of a field.
+    sites: Vec<Vec<Guard>>,
+}
+
+fn intersect(a: &[Guard], b: &[Guard]) -> Vec<Guard> {
+    a.iter().filter(|g| b.contains(g)).cloned().collect()
+}
+
+impl<'a> Analysis<'a> {
+    fn lookup(env: &[(&str, Value)], name: &str) -> Value {
+        env.iter()
+            .rev()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v.clone())
+            .unwrap_or(Value::Unknown)
+    }
+
+    fn check(caller: &Value, owner: &Value) -> Value {
+        match (caller, owner) {
+            (Value::Caller(caller), Value::Owner(owner))
+            | (Value::Owner(owner), Value::Caller(caller)) => Value::Check(Guard::Is {
+                caller: *caller,
+                owner: owner.clone(),
+            }),
+            _ => Value::Unknown,
+        }
+    }
+
+    fn call(&mut self, component: &'a Component, args: Vec<Value>, held: Vec<Guard>) -> Held {
+        if self.stack.contains(&component.name.as_str()) {
+            return Some(held);
+        }
+        self.stack.push(&component.name);
+        let mut env = self.initial_env();
+        env.extend(
+            component
+                .params
+                .iter()
+                .map(|p| p.name.as_str())
+                .zip(args.into_iter().chain(std::iter::repeat(Value::Unknown))),
+        );
+        let held = self.stmts(&component.body, &mut env, held);
+        self.stack.pop();
+        held
+    }
+
+    fn initial_env(&self) -> Vec<(&'a str, Value)> {
+        let mut env = vec![
+            ("_sender", Value::Caller(Caller::Sender)),
+            ("_origin", Value::Caller(Caller::Origin)),
+        ];
+        env.extend(
+            self.contract
+                .params
+                .iter()
+                .map(|p| (p.name.as_str(), Value::Owner(p.name.clone()))),
+        );
+        env
+    }
+
+    fn stmts(
+        &mut self,
+        stmts: &'a [Stmt],
+        env: &mut Vec<(&'a str, Value)>,
+        held: Vec<Guard>,
+    ) -> Held {
+        let mut held = held;
+        for stmt in stmts {
+            match &stmt.kind {
+                StmtKind::Load { lhs, field } => env.push((lhs, Value::Owner(field.clone()))),
+                StmtKind::Bind { lhs, expr } => {
+                    let value = match &expr.kind {
+                        ExprKind::Var(name) => Self::lookup(env, name),
+                        ExprKind::Builtin { name, args } if name == "eq" && args.len() == 2 => {
+                            Self::check(&Self::lookup(env, &args[0]), &Self::lookup(env, &args[1]))
+                        }
+                        _ => Value::Unknown,
+                    };
+                    env.push((lhs, value));
+                }
+                StmtKind::MapGet {
+                    lhs,
+                    map,
+                    keys,
+                    exists,
+                } => {
+                    // Reading an entry with the caller as key is not a check, e.g. reading
+                    // `balances[_sender]`.
+                    let caller =
+                        keys.iter()
+                            .filter(|_| *exists)
+                            .find_map(|k| match Self::lookup(env, k) {
+                                Value::Caller(caller) => Some(caller),
+                                _ => None,
+                            });
+                    let value = match caller {
+                        Some(caller) => Value::Check(Guard::MemberOf {
+                            caller,
+                            map: map.clone(),
+                        }),
+                        None => Value::Unknown,
+                    };
+                    env.push((lhs, value));
+                }
+                StmtKind::RemoteLoad { lhs, .. }
+                | StmtKind::RemoteMapGet { lhs, .. }
+                | StmtKind::TypeCast { lhs, .. }
+                | StmtKind::ReadFromBC { lhs, .. } => env.push((lhs, Value::Unknown)),
+                StmtKind::Store { .. }
+                | StmtKind::MapUpdate { .. }
+                | StmtKind::MapDelete { .. } => self.sites.push(held.clone()),
+                StmtKind::Accept | StmtKind::Send(_) | StmtKind::Event(_) => {}
+                StmtKind::Throw(_) => return None,
+                StmtKind::Match { scrutinee, arms } => {
+                    let scrutinee = Self::lookup(env, scrutinee);
+                    let check = match &scrutinee {
+                        Value::Check(guard) => Some(guard),
+                        _ => None,
+                    };
+                    let mut after: Held = None;
+                    for (pattern, arm) in arms {
+                        let mut arm_held = held.clone();
+                        if let (Some(guard), Pattern::Constructor(ctr, _)) = (check, pattern) {
+                            if ctr == "True" {
+                                arm_held.push(guard.clone());
+                            }
+                        }
+                        let mut arm_env = env.clone();
+                        // The address in `Some a` is still the one of an optional field.
+                        let wrapped = match (&scrutinee, pattern) {
+                            (Value::Owner(_), Pattern::Constructor(ctr, args))
+                                if ctr == "Some" && args.len() == 1 =>
+                            {
+                                scrutinee.clone()
+                            }
+                            _ => Value::Unknown,
+                        };
+                        arm_env.extend(pattern.binders().into_iter().map(|b| (b, wrapped.clone())));
+                        if let Some(arm_held) = self.stmts(arm, &mut arm_env, arm_held) {
+                            after = Some(match after {
+                                Some(after) => intersect(&after, &arm_held),
+                                None => arm_held,
+                            });
+                        }
+                    }
+                    held = after?;
+                }
+                StmtKind::CallProc { name, args } => {
+                    if let Some(proc) = self.contract.component(name) {
+                        let args = args.iter().map(|a| Self::lookup(env, a)).collect();
+                        held = self.call(proc, args, held)?;
+                    }
+                }
+                StmtKind::Iterate { proc, .. } => {
+                    // The list may be empty, so the procedure only adds sites.
+                    if let Some(proc) = self.contract.component(proc) {
+                        self.call(proc, vec![], held.clone());
+                    }
+                }
+            }
+        }
+        Some(held)
+    }
+}
+
+impl Contract {
+    /// Infer which callers each transition accepts from the checks of `_sender` and `_origin`
+    /// against fields, immutable parameters and map keys that make it throw, including the ones
+    /// in the procedures it calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    /// let authorization = contract.authorization().unwrap();
+    /// assert_eq!(
+    ///     authorization.of("Mint").unwrap().to_string(),
+    ///     "Mint: _sender == contract_owner"
+    /// );
+    /// assert_eq!(authorization.of("Transfer").unwrap().to_string(), "Transfer: anyone (mutates state)");
+    /// ```
+    pub fn authorization(&self) -> Result<AuthorizationList, Error> {
+        let module = self.syntax_tree()?;
+        let effects = self.effects()?;
+        let list = module
+            .contract
+            .transitions()
+            .map(|transition| {
+                let mut analysis = Analysis {
+                    contract: &module.contract,
+                    stack: vec![],
+                    sites: vec![],
+                };
+                let held = analysis.call(transition, vec![], vec![]);
+                let guards = match analysis.sites.split_first() {
+                    Some((first, rest)) => rest.iter().fold(first.clone(), |g, s| intersect(&g, s)),
+                    None => held.unwrap_or_default(),
+                };
+                Authorization {
+                    transition: transition.name.clone(),
+                    guards,
+                    mutates_state: effects
+                        .of(&transition.name)
+                        .is_some_and(|e| !e.is_read_only()),
+                }
+            })
+            .collect();
+        Ok(AuthorizationList(list))
+    }
+}
@@ -0,0 +1,407 @@
+//! Syntax tree of a Scilla module as produced by the native [parser](crate::parser).
+
+use crate::{Docs, Type};
+
+/// Location of a node in the source. `start` and `end` are byte offsets, `line` and `column` are
+/// 1-based and point to the first character of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+    /// Returns a span covering both `self` and `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            start: self.start,
+            end: other.end.max(self.end),
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+/// A whole `.scilla` file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module {
+    pub scilla_version: u32,
+    pub imports: Vec<Import>,
+    pub library: Option<Library>,
+    pub contract: ContractDef,
+}
+
+/// An imported library, optionally renamed with `as`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Import {
+    pub name: String,
+    pub alias: Option<String>,
+    pub span: Span,
+}
+
+/// The `library` section of a module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Library {
+    pub name: String,
+    pub entries: Vec<LibEntry>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LibEntry {
+    /// `let name : ty = expr`
+    Let {
+        name: String,
+        ty: Option<Type>,
+        expr: Expr,
+        docs: Docs,
+        span: Span,
+    },
+    /// `type name = | Ctr1 | Ctr2 of T1 T2`
+    Type {
+        name: String,
+        constructors: Vec<CtrDef>,
+        docs: Docs,
+        span: Span,
+    },
+}
+
+impl LibEntry {
+    pub fn name(&self) -> &str {
+        match self {
+            LibEntry::Let { name, .. } | LibEntry::Type { name, .. } => name,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            LibEntry::Let { span, .. } | LibEntry::Type { span, .. } => *span,
+        }
+    }
+
+    pub fn docs(&self) -> &Docs {
+        match self {
+            LibEntry::Let { docs, .. } | LibEntry::Type { docs, .. } => docs,
+        }
+    }
+}
+
+/// A constructor of a user-defined ADT.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CtrDef {
+    pub name: String,
+    pub args: Vec<Type>,
+}
+
+/// The `contract` section of a module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractDef {
+    pub name: String,
+    pub params: Vec<Param>,
+    /// The optional `with ... =>` constraint on the contract parameters.
+    pub constraint: Option<Expr>,
+    pub fields: Vec<FieldDef>,
+    pub components: Vec<Component>,
+    /// The comments right before `contract`.
+    pub docs: Docs,
+    pub span: Span,
+}
+
+impl ContractDef {
+    pub fn transitions(&self) -> impl Iterator<Item = &Component> {
+        self.components
+            .iter()
+            .filter(|c| c.kind == ComponentKind::Transition)
+    }
+
+    pub fn procedures(&self) -> impl Iterator<Item = &Component> {
+        self.components
+            .iter()
+            .filter(|c| c.kind == ComponentKind::Procedure)
+    }
+
+    pub fn component(&self, name: &str) -> Option<&Component> {
+        self.components.iter().find(|c| c.name == name)
+    }
+
+    /// The given component followed by all procedures it calls, directly or indirectly.
+    pub fn reachable_from<'a>(&'a self, component: &'a Component) -> Vec<&'a Component> {
+        let mut reached = vec![component];
+        let mut index = 0;
+        while index < reached.len() {
+            let mut called = vec![];
+            for stmt in &reached[index].body {
+                stmt.walk(&mut |stmt| match &stmt.kind {
+                    StmtKind::CallProc { name, .. } | StmtKind::Iterate { proc: name, .. } => {
+                        called.push(name.as_str())
+                    }
+                    _ => {}
+                });
+            }
+            for name in called {
+                if let Some(proc) = self.procedures().find(|p| p.name == name) {
+                    if !reached.iter().any(|c| c.name == proc.name) {
+                        reached.push(proc);
+                    }
+                }
+            }
+            index += 1;
+        }
+        reached
+    }
+}
+
+/// A typed parameter of a contract, transition or procedure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub ty: Type,
+    pub span: Span,
+}
+
+/// A mutable field together with its initializer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDef {
+    pub name: String,
+    pub ty: Type,
+    pub init: Expr,
+    pub docs: Docs,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentKind {
+    Transition,
+    Procedure,
+}
+
+/// A transition or a procedure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    pub kind: ComponentKind,
+    pub name: String,
+    pub params: Vec<Param>,
+    pub body: Vec<Stmt>,
+    pub docs: Docs,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    /// An integer literal such as `Uint128 42`. The type is one of the integer types.
+    Int(Type, String),
+    String(String),
+    /// A byte string literal such as `0x1234`.
+    ByStr(String),
+    /// A block number literal such as `BNum 100`.
+    BNum(String),
+    /// An empty map, `Emp key_type value_type`.
+    EmptyMap(Type, Type),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    pub kind: ExprKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprKind {
+    Literal(Literal),
+    Var(String),
+    /// `{ _tag : "Foo"; _recipient : to; amount : amount }`, used for messages and events.
+    Message(Vec<MessageEntry>),
+    /// `let name : ty = value in body`
+    Let {
+        name: String,
+        ty: Option<Type>,
+        value: Box<Expr>,
+        body: Box<Expr>,
+    },
+    /// `fun (param : ty) => body`
+    Fun {
+        param: String,
+        ty: Type,
+        body: Box<Expr>,
+    },
+    /// `func arg1 arg2`
+    App {
+        func: String,
+        args: Vec<String>,
+    },
+    /// `Constructor {T1 T2} arg1 arg2`
+    Constructor {
+        name: String,
+        type_args: Vec<Type>,
+        args: Vec<String>,
+    },
+    /// `builtin name arg1 arg2`
+    Builtin {
+        name: String,
+        args: Vec<String>,
+    },
+    /// `match scrutinee with | pattern => expr ... end`
+    Match {
+        scrutinee: String,
+        arms: Vec<(Pattern, Expr)>,
+    },
+    /// `tfun 'A => body`
+    TFun {
+        tvar: String,
+        body: Box<Expr>,
+    },
+    /// `@func T1 T2`
+    TApp {
+        func: String,
+        type_args: Vec<Type>,
+    },
+}
+
+/// One `name : value` entry of a message literal. The value is a literal or a variable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageEntry {
+    pub name: String,
+    pub value: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Wildcard,
+    Binder(String),
+    Constructor(String, Vec<Pattern>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stmt {
+    pub kind: StmtKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StmtKind {
+    /// `lhs <- field`
+    Load {
+        lhs: String,
+        field: String,
+    },
+    /// `field := rhs`
+    Store {
+        field: String,
+        rhs: String,
+    },
+    /// `lhs = expr`
+    Bind {
+        lhs: String,
+        expr: Expr,
+    },
+    /// `map[k1][k2] := value`
+    MapUpdate {
+        map: String,
+        keys: Vec<String>,
+        value: String,
+    },
+    /// `delete map[k1][k2]`
+    MapDelete {
+        map: String,
+        keys: Vec<String>,
+    },
+    /// `lhs <- map[k1][k2]` or `lhs <- exists map[k1][k2]`
+    MapGet {
+        lhs: String,
+        map: String,
+        keys: Vec<String>,
+        exists: bool,
+    },
+    /// `lhs <- & address.field`
+    RemoteLoad {
+        lhs: String,
+        address: String,
+        field: String,
+    },
+    /// `lhs <- & address.map[k1][k2]` or `lhs <- & exists address.map[k1][k2]`
+    RemoteMapGet {
+        lhs: String,
+        address: String,
+        map: String,
+        keys: Vec<String>,
+        exists: bool,
+    },
+    /// `lhs <- & address as ty`
+    TypeCast {
+        lhs: String,
+        address: String,
+        ty: Type,
+    },
+    /// `lhs <- & BLOCKNUMBER`, `lhs <- & CHAINID` or `lhs <- & TIMESTAMP(bnum)`
+    ReadFromBC {
+        lhs: String,
+        query: String,
+        args: Vec<String>,
+    },
+    Accept,
+    Send(String),
+    Event(String),
+    Throw(Option<String>),
+    /// `match scrutinee with | pattern => stmts ... end`
+    Match {
+        scrutinee: String,
+        arms: Vec<(Pattern, Vec<Stmt>)>,
+    },
+    /// `Procedure arg1 arg2`
+    CallProc {
+        name: String,
+        args: Vec<String>,
+    },
+    /// `forall list Procedure`
+    Iterate {
+        list: String,
+        proc: String,
+    },
+}
+
+impl Expr {
+    /// Calls `f` on this expression and every expression nested in it, in pre-order.
+    pub fn walk<'a>(&'a self, f: &mut impl FnMut(&'a Expr)) {
+        f(self);
+        match &self.kind {
+            ExprKind::Message(entries) => entries.iter().for_each(|e| e.value.walk(f)),
+            ExprKind::Let { value, body, .. } => {
+                value.walk(f);
+                body.walk(f);
+            }
+            ExprKind::Fun { body, .. } | ExprKind::TFun { body, .. } => body.walk(f),
+            ExprKind::Match { arms, .. } => arms.iter().for_each(|(_, e)| e.walk(f)),
+            ExprKind::Literal(_)
+            | ExprKind::Var(_)
+            | ExprKind::App { .. }
+            | ExprKind::Constructor { .. }
+            | ExprKind::Builtin { .. }
+            | ExprKind::TApp { .. } => {}
+        }
+    }
+}
+
+impl Stmt {
+    /// Calls `f` on this statement and every statement nested in it, in pre-order.
+    pub fn walk<'a>(&'a self, f: &mut impl FnMut(&'a Stmt)) {
+        f(self);
+        if let StmtKind::Match { arms, .. } = &self.kind {
+            for (_, stmts) in arms {
+                stmts.iter().for_each(|s| s.walk(f));
+            }
+        }
+    }
+}
+
+impl Pattern {
+    /// Names bound by this pattern.
+    pub fn binders(&self) -> Vec<&str> {
+        match self {
+            Pattern::Wildcard => vec![],
+            Pattern::Binder(name) => vec![name],
+            Pattern::Constructor(_, args) => args.iter().flat_map(|p| p.binders()).collect(),
+        }
+    }
+}
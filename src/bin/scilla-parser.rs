@@ -0,0 +1,479 @@
+//! Command line interface answering quick questions about Scilla contracts, e.g.
+//! `scilla-parser transitions contracts/**/*.scilla`.
+
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand};
+use scilla_parser::{
+    ast::LibEntry,
+    printer::{print_type, PrettyPrinter},
+    Contract, Error, FieldList, Linter, Rule, Severity, TransitionList, Type,
+};
+use serde_json::{json, Value as JsonValue};
+
+#[derive(Parser)]
+#[command(
+    name = "scilla-parser",
+    version,
+    about = "Inspect Scilla smart contracts"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print a summary of the interface of each contract.
+    Inspect(Inputs),
+    /// Print the interface of each contract as JSON: its parameters, fields, transitions and
+    /// procedures.
+    Json(Inputs),
+    /// Print the transitions and their parameters.
+    Transitions(Inputs),
+    /// Print the mutable fields and their types.
+    Fields(Inputs),
+    /// Print the events emitted by each contract.
+    Events(Inputs),
+    /// Print the types defined in the library of each contract.
+    Types(Inputs),
+    /// Print the fields read, written, updated and deleted from by each transition and procedure.
+    Effects(Inputs),
+    /// Print the callers each transition accepts, inferred from its checks of `_sender` and
+    /// `_origin`, flagging the transitions anyone can use to change the state.
+    Access(Inputs),
+    /// Print the fields written after sending a message and the callbacks reading state written
+    /// by the transition they answer. Exits with an error status if any is found.
+    Interactions(InteractionsArgs),
+    /// Check the contracts for common mistakes. Exits with an error status if a warning is
+    /// reported.
+    Lint(LintArgs),
+    /// Type check the contracts. Exits with an error status if a type error is found.
+    Typecheck(Inputs),
+    /// Print bounds of the gas used by each transition, estimated from the Scilla source.
+    Gas(Inputs),
+    /// Compare the interface of two versions of a contract. Exits with an error status if the
+    /// changes break the existing users of the contract.
+    Diff(DiffArgs),
+    /// Print the call graph of a contract in the Graphviz DOT language, e.g.
+    /// `scilla-parser call-graph contract.scilla | dot -Tsvg > graph.svg`.
+    CallGraph(CallGraphArgs),
+}
+
+#[derive(Args)]
+struct Inputs {
+    /// Contract files, directories or glob patterns such as `contracts/**/*.scilla`. Directories
+    /// are searched for `.scilla` and `.sexp` files. Files ending with `.sexp` are read as the
+    /// output of `scilla-fmt --sexp` and files ending with `.json` as a contract info JSON; since
+    /// init and state files are JSON too, these are only read when named or matched by a pattern.
+    #[arg(required = true)]
+    paths: Vec<String>,
+}
+
+#[derive(Args)]
+struct InteractionsArgs {
+    #[command(flatten)]
+    inputs: Inputs,
+    /// Print the writes, sends and events of each transition along each path instead.
+    #[arg(long = "paths")]
+    print_paths: bool,
+}
+
+#[derive(Args)]
+struct LintArgs {
+    #[command(flatten)]
+    inputs: Inputs,
+    /// Do not run the rule, e.g. `--disable magic-number`. Can be repeated.
+    #[arg(long, value_name = "RULE")]
+    disable: Vec<Rule>,
+    /// Only run the rule. Can be repeated.
+    #[arg(long, value_name = "RULE", conflicts_with = "disable")]
+    only: Vec<Rule>,
+}
+
+#[derive(Args)]
+struct DiffArgs {
+    /// The current version of the contract.
+    old: PathBuf,
+    /// The new version of the contract.
+    new: PathBuf,
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct CallGraphArgs {
+    /// The contract source file.
+    path: PathBuf,
+    /// Print the graph as JSON.
+    #[arg(long)]
+    json: bool,
+    /// Print the procedures and library entries that no transition uses instead of the graph.
+    #[arg(long, conflicts_with = "json")]
+    unused: bool,
+}
+
+/// Extensions of the files searched for in directories.
+const EXTENSIONS: &[&str] = &["scilla", "sexp"];
+
+/// Expand directories and glob patterns to the contract files they contain.
+fn expand(paths: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut files = vec![];
+    for pattern in paths {
+        let path = Path::new(pattern);
+        if path.is_file() {
+            files.push(path.to_path_buf());
+            continue;
+        }
+        let patterns = if path.is_dir() {
+            EXTENSIONS
+                .iter()
+                .map(|ext| path.join("**").join(format!("*.{ext}")))
+                .map(|p| p.display().to_string())
+                .collect()
+        } else {
+            vec![pattern.clone()]
+        };
+        let mut matched = vec![];
+        for pattern in patterns {
+            let entries = glob::glob(&pattern).map_err(|e| format!("{pattern}: {e}"))?;
+            matched.extend(entries.filter_map(Result::ok).filter(|p| p.is_file()));
+        }
+        if matched.is_empty() {
+            return Err(format!("{pattern}: no contract found"));
+        }
+        matched.sort();
+        files.extend(matched);
+    }
+    Ok(files)
+}
+
+fn read_contract(path: &Path) -> Result<Contract, Error> {
+    let content = std::fs::read_to_string(path)?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("sexp") => content.parse(),
+        Some("json") => Contract::from_contract_info_json(&content),
+        _ => Contract::from_source(&content),
+    }
+}
+
+/// `name (p1 : T1, p2 : T2)`. Parameters whose type is unknown are printed without type.
+fn signature<'a>(name: &str, params: impl Iterator<Item = (&'a str, Option<&'a Type>)>) -> String {
+    let params: Vec<String> = params
+        .map(|(name, ty)| match ty {
+            Some(ty) => format!("{name} : {}", print_type(ty)),
+            None => name.to_string(),
+        })
+        .collect();
+    format!("{name} ({})", params.join(", "))
+}
+
+fn typed(params: &FieldList) -> impl Iterator<Item = (&str, Option<&Type>)> {
+    params.iter().map(|p| (p.name.as_str(), Some(&p.r#type)))
+}
+
+fn fields(params: &FieldList) -> String {
+    params
+        .iter()
+        .map(|p| format!("{} : {}\n", p.name, print_type(&p.r#type)))
+        .collect()
+}
+
+/// The interface of `contract` as JSON, with the types in Scilla syntax.
+fn contract_json(contract: &Contract) -> JsonValue {
+    let params = |params: &FieldList| -> JsonValue {
+        params
+            .iter()
+            .map(|p| json!({"name": p.name, "type": p.r#type.to_scilla_string()}))
+            .collect()
+    };
+    let components = |components: &TransitionList| -> JsonValue {
+        components
+            .iter()
+            .map(|c| json!({"name": c.name, "params": params(&c.params)}))
+            .collect()
+    };
+    json!({
+        "name": contract.name,
+        "init_params": params(&contract.init_params),
+        "fields": params(&contract.fields),
+        "transitions": components(&contract.transitions),
+        "procedures": components(&contract.procedures),
+    })
+}
+
+fn transitions(contract: &Contract) -> String {
+    contract
+        .transitions
+        .iter()
+        .map(|t| signature(&t.name, typed(&t.params)) + "\n")
+        .collect()
+}
+
+fn events(contract: &Contract) -> Result<String, Error> {
+    Ok(contract
+        .events()?
+        .iter()
+        .map(|e| {
+            let params = e
+                .params
+                .iter()
+                .map(|p| (p.name.as_str(), p.r#type.as_ref()));
+            signature(&e.name, params) + "\n"
+        })
+        .collect())
+}
+
+fn types(contract: &Contract) -> Result<String, Error> {
+    let printer = PrettyPrinter::default();
+    Ok(contract
+        .syntax_tree()?
+        .library
+        .iter()
+        .flat_map(|l| &l.entries)
+        .filter(|e| matches!(e, LibEntry::Type { .. }))
+        .map(|e| printer.print_lib_entry(e).replace('\n', " ") + "\n")
+        .collect())
+}
+
+fn effects(contract: &Contract) -> Result<String, Error> {
+    Ok(contract
+        .effects()?
+        .iter()
+        .map(|e| e.to_string() + "\n")
+        .collect())
+}
+
+fn access(contract: &Contract) -> Result<String, Error> {
+    Ok(contract
+        .authorization()?
+        .iter()
+        .map(|a| a.to_string() + "\n")
+        .collect())
+}
+
+fn interactions(
+    contract: &Contract,
+    args: &InteractionsArgs,
+    status: &mut ExitCode,
+) -> Result<String, Error> {
+    if args.print_paths {
+        return Ok(contract
+            .transition_orders()?
+            .iter()
+            .map(ToString::to_string)
+            .collect());
+    }
+    let warnings = contract.interaction_warnings()?;
+    if !warnings.is_empty() {
+        *status = ExitCode::FAILURE;
+    }
+    Ok(warnings.iter().map(|w| w.to_string() + "\n").collect())
+}
+
+fn lint(contract: &Contract, args: &LintArgs, status: &mut ExitCode) -> Result<String, Error> {
+    let linter = if args.only.is_empty() {
+        args.disable
+            .iter()
+            .fold(Linter::default(), |linter, rule| linter.disable(*rule))
+    } else {
+        args.only
+            .iter()
+            .fold(Linter::empty(), |linter, rule| linter.enable(*rule))
+    };
+    let diagnostics = linter.lint(contract)?;
+    if diagnostics.max_severity() > Some(Severity::Note) {
+        *status = ExitCode::FAILURE;
+    }
+    Ok(diagnostics.iter().map(|d| d.to_string() + "\n").collect())
+}
+
+fn typecheck(contract: &Contract, status: &mut ExitCode) -> Result<String, Error> {
+    let errors = contract.type_check()?;
+    if !errors.is_empty() {
+        *status = ExitCode::FAILURE;
+    }
+    Ok(errors.iter().map(|e| e.to_string() + "\n").collect())
+}
+
+fn gas(contract: &Contract) -> Result<String, Error> {
+    Ok(contract
+        .gas_bounds()?
+        .iter()
+        .map(|g| g.to_string() + "\n")
+        .collect())
+}
+
+/// The events are only listed when the contract is parsed from its Scilla source.
+fn inspect(contract: &Contract) -> String {
+    let mut out = format!(
+        "{}: {} immutable parameters, {} mutable fields, {} transitions, {} procedures",
+        contract.name,
+        contract.init_params.len(),
+        contract.fields.len(),
+        contract.transitions.len(),
+        contract.procedures.len(),
+    );
+    if let Ok(events) = contract.events() {
+        let _ = write!(out, ", {} events", events.len());
+    }
+    out.push('\n');
+    let sections = [
+        ("Immutable parameters", fields(&contract.init_params)),
+        ("Mutable fields", fields(&contract.fields)),
+        ("Transitions", transitions(contract)),
+        (
+            "Procedures",
+            contract
+                .procedures
+                .iter()
+                .map(|p| signature(&p.name, typed(&p.params)) + "\n")
+                .collect(),
+        ),
+        ("Events", self::events(contract).unwrap_or_default()),
+    ];
+    for (title, lines) in sections {
+        if !lines.is_empty() {
+            let _ = write!(out, "\n{title}:\n");
+            for line in lines.lines() {
+                let _ = writeln!(out, "  {line}");
+            }
+        }
+    }
+    out
+}
+
+fn diff(args: &DiffArgs) -> ExitCode {
+    let (old, new) = match (read_contract(&args.old), read_contract(&args.new)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let diff = old.diff(&new);
+    if args.json {
+        println!("{:#}", diff.to_json());
+    } else {
+        print!("{diff}");
+    }
+    if diff.is_breaking() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn call_graph(args: &CallGraphArgs) -> ExitCode {
+    let contract = match read_contract(&args.path) {
+        Ok(contract) => contract,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let graph = match contract.call_graph() {
+        Ok(graph) => graph,
+        Err(e) => {
+            eprintln!("error: {}: {e}", args.path.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.json {
+        println!("{:#}", graph.to_json());
+    } else if args.unused {
+        for node in graph.unused() {
+            println!("{} ({})", node.name, node.kind);
+        }
+    } else {
+        print!("{}", graph.to_dot());
+    }
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let inputs = match &cli.command {
+        Command::Diff(args) => return diff(args),
+        Command::CallGraph(args) => return call_graph(args),
+        Command::Inspect(inputs)
+        | Command::Json(inputs)
+        | Command::Transitions(inputs)
+        | Command::Fields(inputs)
+        | Command::Events(inputs)
+        | Command::Types(inputs)
+        | Command::Effects(inputs)
+        | Command::Access(inputs)
+        | Command::Typecheck(inputs)
+        | Command::Gas(inputs) => inputs,
+        Command::Interactions(args) => &args.inputs,
+        Command::Lint(args) => &args.inputs,
+    };
+
+    let files = match expand(&inputs.paths) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut status = ExitCode::SUCCESS;
+    let mut contracts = vec![];
+    for file in files {
+        match read_contract(&file) {
+            Ok(contract) => contracts.push((file, contract)),
+            Err(e) => {
+                eprintln!("error: {}: {e}", file.display());
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if let Command::Json(_) = cli.command {
+        let json = match contracts.as_slice() {
+            [(_, contract)] => contract_json(contract),
+            _ => contracts
+                .iter()
+                .map(|(file, c)| (file.display().to_string(), contract_json(c)))
+                .collect(),
+        };
+        println!("{json:#}");
+        return status;
+    }
+
+    let multiple = contracts.len() > 1;
+    for (i, (file, contract)) in contracts.iter().enumerate() {
+        if multiple {
+            let separator = if i > 0 { "\n" } else { "" };
+            println!("{separator}==> {} <==", file.display());
+        }
+        let output = match &cli.command {
+            Command::Inspect(_) => Ok(inspect(contract)),
+            Command::Transitions(_) => Ok(transitions(contract)),
+            Command::Fields(_) => Ok(fields(&contract.fields)),
+            Command::Events(_) => events(contract),
+            Command::Types(_) => types(contract),
+            Command::Effects(_) => effects(contract),
+            Command::Access(_) => access(contract),
+            Command::Interactions(args) => interactions(contract, args, &mut status),
+            Command::Lint(args) => lint(contract, args, &mut status),
+            Command::Typecheck(_) => typecheck(contract, &mut status),
+            Command::Gas(_) => gas(contract),
+            Command::Json(_) | Command::Diff(_) | Command::CallGraph(_) => unreachable!(),
+        };
+        match output {
+            Ok(output) => print!("{output}"),
+            Err(e) => {
+                eprintln!("error: {}: {e}", file.display());
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
@@ -0,0 +1,389 @@
+//! Runtime support for the Rust bindings generated from a [`Contract`](crate::Contract).
+//!
+//! Values are converted from and to the JSON representation used by the Zilliqa API, e.g.
+//! `"100"` for a `Uint128` or `{"constructor": "Some", "argtypes": [...], "arguments": [...]}`
+//! for an `Option`.
+
+use std::collections::BTreeMap;
+
+pub use num_bigint::BigInt;
+pub use primitive_types::U256;
+pub use serde_json::{json, Value as JsonValue};
+
+use crate::{value::decode_hex, Error};
+
+/// A block number, `BNum` in Scilla.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct BNum(pub u64);
+
+/// A byte string of arbitrary length, `ByStr` in Scilla.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Bytes(pub Vec<u8>);
+
+/// A Rust type that maps to a Scilla type.
+pub trait ScillaValue: Sized {
+    /// The Scilla type, e.g. `Option (Uint128)`. Empty for values whose type is not known
+    /// statically, i.e. raw JSON.
+    fn scilla_type() -> String;
+
+    fn to_json(&self) -> JsonValue;
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error>;
+}
+
+fn invalid(json: &JsonValue, ty: &str) -> Error {
+    Error::InvalidValue(format!("{json} is not a valid {ty}"))
+}
+
+fn adt(constructor: &str, argtypes: Vec<String>, arguments: Vec<JsonValue>) -> JsonValue {
+    json!({"constructor": constructor, "argtypes": argtypes, "arguments": arguments})
+}
+
+/// The constructor and the arguments of an ADT value.
+fn adt_parts<'a>(json: &'a JsonValue, ty: &str) -> Result<(&'a str, &'a [JsonValue]), Error> {
+    let constructor = json["constructor"]
+        .as_str()
+        .ok_or_else(|| invalid(json, ty))?;
+    let arguments = json["arguments"].as_array().map_or(&[][..], Vec::as_slice);
+    Ok((constructor, arguments))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let digits: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("0x{digits}")
+}
+
+fn from_hex(json: &JsonValue, ty: &str) -> Result<Vec<u8>, Error> {
+    json.as_str()
+        .and_then(decode_hex)
+        .ok_or_else(|| invalid(json, ty))
+}
+
+macro_rules! impl_integer {
+    ($($rust:ty => $scilla:literal),*) => {$(
+        impl ScillaValue for $rust {
+            fn scilla_type() -> String {
+                $scilla.to_string()
+            }
+
+            fn to_json(&self) -> JsonValue {
+                JsonValue::String(self.to_string())
+            }
+
+            fn from_json(json: &JsonValue) -> Result<Self, Error> {
+                json.as_str()
+                    .and_then(|s| s.parse().ok())
+                    .ok_or_else(|| invalid(json, $scilla))
+            }
+        }
+    )*};
+}
+
+impl_integer!(
+    i32 => "Int32",
+    i64 => "Int64",
+    i128 => "Int128",
+    BigInt => "Int256",
+    u32 => "Uint32",
+    u64 => "Uint64",
+    u128 => "Uint128"
+);
+
+impl ScillaValue for U256 {
+    fn scilla_type() -> String {
+        "Uint256".to_string()
+    }
+
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.to_string())
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        json.as_str()
+            .and_then(|s| U256::from_dec_str(s).ok())
+            .ok_or_else(|| invalid(json, "Uint256"))
+    }
+}
+
+impl ScillaValue for BNum {
+    fn scilla_type() -> String {
+        "BNum".to_string()
+    }
+
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.0.to_string())
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        json.as_str()
+            .and_then(|s| s.parse().ok())
+            .map(BNum)
+            .ok_or_else(|| invalid(json, "BNum"))
+    }
+}
+
+impl ScillaValue for String {
+    fn scilla_type() -> String {
+        "String".to_string()
+    }
+
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.clone())
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        json.as_str()
+            .map(ToString::to_string)
+            .ok_or_else(|| invalid(json, "String"))
+    }
+}
+
+impl ScillaValue for bool {
+    fn scilla_type() -> String {
+        "Bool".to_string()
+    }
+
+    fn to_json(&self) -> JsonValue {
+        adt(if *self { "True" } else { "False" }, vec![], vec![])
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        match adt_parts(json, "Bool")?.0 {
+            "True" => Ok(true),
+            "False" => Ok(false),
+            _ => Err(invalid(json, "Bool")),
+        }
+    }
+}
+
+impl<const N: usize> ScillaValue for [u8; N] {
+    fn scilla_type() -> String {
+        format!("ByStr{N}")
+    }
+
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(hex(self))
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        from_hex(json, &Self::scilla_type())?
+            .try_into()
+            .map_err(|_| invalid(json, &Self::scilla_type()))
+    }
+}
+
+impl ScillaValue for Bytes {
+    fn scilla_type() -> String {
+        "ByStr".to_string()
+    }
+
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(hex(&self.0))
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        from_hex(json, "ByStr").map(Bytes)
+    }
+}
+
+impl<T: ScillaValue> ScillaValue for Option<T> {
+    fn scilla_type() -> String {
+        format!("Option ({})", T::scilla_type())
+    }
+
+    fn to_json(&self) -> JsonValue {
+        match self {
+            Some(value) => adt("Some", vec![T::scilla_type()], vec![value.to_json()]),
+            None => adt("None", vec![T::scilla_type()], vec![]),
+        }
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        match adt_parts(json, "Option")? {
+            ("Some", [value]) => Ok(Some(T::from_json(value)?)),
+            ("None", []) => Ok(None),
+            _ => Err(invalid(json, &Self::scilla_type())),
+        }
+    }
+}
+
+impl<T: ScillaValue> ScillaValue for Vec<T> {
+    fn scilla_type() -> String {
+        format!("List ({})", T::scilla_type())
+    }
+
+    fn to_json(&self) -> JsonValue {
+        self.iter().map(ScillaValue::to_json).collect()
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        json.as_array()
+            .ok_or_else(|| invalid(json, &Self::scilla_type()))?
+            .iter()
+            .map(T::from_json)
+            .collect()
+    }
+}
+
+impl<A: ScillaValue, B: ScillaValue> ScillaValue for (A, B) {
+    fn scilla_type() -> String {
+        format!("Pair ({}) ({})", A::scilla_type(), B::scilla_type())
+    }
+
+    fn to_json(&self) -> JsonValue {
+        adt(
+            "Pair",
+            vec![A::scilla_type(), B::scilla_type()],
+            vec![self.0.to_json(), self.1.to_json()],
+        )
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        match adt_parts(json, "Pair")? {
+            ("Pair", [a, b]) => Ok((A::from_json(a)?, B::from_json(b)?)),
+            _ => Err(invalid(json, &Self::scilla_type())),
+        }
+    }
+}
+
+impl<K: ScillaValue + Ord, V: ScillaValue> ScillaValue for BTreeMap<K, V> {
+    fn scilla_type() -> String {
+        format!("Map ({}) ({})", K::scilla_type(), V::scilla_type())
+    }
+
+    /// Maps are JSON objects keyed by the string representation of the keys.
+    fn to_json(&self) -> JsonValue {
+        let entries = self.iter().map(|(key, value)| {
+            let key = match key.to_json() {
+                JsonValue::String(key) => key,
+                key => key.to_string(),
+            };
+            (key, value.to_json())
+        });
+        JsonValue::Object(entries.collect())
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        json.as_object()
+            .ok_or_else(|| invalid(json, &Self::scilla_type()))?
+            .iter()
+            .map(|(key, value)| {
+                Ok((
+                    K::from_json(&JsonValue::String(key.clone()))?,
+                    V::from_json(value)?,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Values of user-defined ADTs are kept as raw JSON.
+impl ScillaValue for JsonValue {
+    fn scilla_type() -> String {
+        String::new()
+    }
+
+    fn to_json(&self) -> JsonValue {
+        self.clone()
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, Error> {
+        Ok(json.clone())
+    }
+}
+
+/// A parameter of a transition call or of the contract initialization.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallParam {
+    pub name: String,
+    /// The Scilla type of the parameter, e.g. `Uint128`.
+    pub r#type: String,
+    pub value: JsonValue,
+}
+
+impl CallParam {
+    pub fn new(name: &str, r#type: &str, value: &impl ScillaValue) -> Self {
+        Self {
+            name: name.to_string(),
+            r#type: r#type.to_string(),
+            value: value.to_json(),
+        }
+    }
+}
+
+/// Parameters in the `[{"vname": ..., "type": ..., "value": ...}]` format of the Zilliqa API.
+pub fn params_json(params: &[CallParam]) -> JsonValue {
+    params
+        .iter()
+        .map(|p| json!({"vname": p.name, "type": p.r#type, "value": p.value}))
+        .collect()
+}
+
+/// A call to a transition of a contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionCall {
+    /// Address of the contract.
+    pub contract: [u8; 20],
+    /// Name of the transition.
+    pub tag: String,
+    /// Amount of QA sent with the call.
+    pub amount: u128,
+    pub params: Vec<CallParam>,
+}
+
+impl TransitionCall {
+    pub fn new(contract: [u8; 20], tag: &str, params: Vec<CallParam>) -> Self {
+        Self {
+            contract,
+            tag: tag.to_string(),
+            amount: 0,
+            params,
+        }
+    }
+
+    /// Send `amount` QA with the call.
+    pub fn with_amount(mut self, amount: u128) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    /// The `data` of the transaction invoking the transition.
+    pub fn to_json(&self) -> JsonValue {
+        json!({"_tag": self.tag, "params": params_json(&self.params)})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let value: BTreeMap<[u8; 20], Option<(u128, Vec<bool>)>> =
+            BTreeMap::from([([1; 20], Some((42, vec![true, false]))), ([2; 20], None)]);
+        let json = value.to_json();
+        assert_eq!(
+            json["0x0101010101010101010101010101010101010101"]["arguments"][0]["argtypes"],
+            json!(["Uint128", "List (Bool)"])
+        );
+        assert_eq!(BTreeMap::from_json(&json).unwrap(), value);
+        assert_eq!(
+            <BTreeMap<[u8; 20], Option<(u128, Vec<bool>)>>>::scilla_type(),
+            "Map (ByStr20) (Option (Pair (Uint128) (List (Bool))))"
+        );
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        assert!(u32::from_json(&json!("-1")).is_err());
+        assert!(<[u8; 20]>::from_json(&json!("0x1234")).is_err());
+        assert!(bool::from_json(&json!({"constructor": "Some", "arguments": []})).is_err());
+        assert_eq!(
+            U256::from_json(&json!(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+            ))
+            .unwrap(),
+            U256::MAX
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! The call graph of a contract: transitions and procedures calling procedures, and the library
+//! functions and values they use.
+
+use std::fmt::{Display, Write};
+
+use serde_json::{json, Value as JsonValue};
+
+use crate::{
+    ast::{ComponentKind, Expr, ExprKind, LibEntry, Stmt, StmtKind},
+    Contract, Error,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    /// The contract itself, using library entries in its field initializers and constraint.
+    Contract,
+    Transition,
+    Procedure,
+    /// A `let` entry of the library, i.e. a library function or value.
+    Library,
+}
+
+impl NodeKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            NodeKind::Contract => "contract",
+            NodeKind::Transition => "transition",
+            NodeKind::Procedure => "procedure",
+            NodeKind::Library => "library",
+        }
+    }
+}
+
+impl Display for NodeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub name: String,
+    pub kind: NodeKind,
+}
+
+/// Calls of procedures and uses of library entries, as `(caller, callee)` edges between nodes
+/// named after the contract, its transitions and procedures, and its library entries.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CallGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<(String, String)>,
+}
+
+/// Names used by `expr` that are not bound in it or in `bound`.
+pub(crate) fn expr_refs<'a>(expr: &'a Expr, bound: &[&'a str], refs: &mut Vec<String>) {
+    let mut use_name = |name: &String| {
+        if !bound.contains(&name.as_str()) && !refs.contains(name) {
+            refs.push(name.clone());
+        }
+    };
+    let with = |names: Vec<&'a str>| -> Vec<&'a str> { [bound, &names].concat() };
+    match &expr.kind {
+        ExprKind::Literal(_) => {}
+        ExprKind::Var(name) | ExprKind::TApp { func: name, .. } => use_name(name),
+        ExprKind::Message(entries) => entries
+            .iter()
+            .for_each(|entry| expr_refs(&entry.value, bound, refs)),
+        ExprKind::Let {
+            name, value, body, ..
+        } => {
+            expr_refs(value, bound, refs);
+            expr_refs(body, &with(vec![name]), refs);
+        }
+        ExprKind::Fun { param, body, .. } => expr_refs(body, &with(vec![param]), refs),
+        ExprKind::TFun { body, .. } => expr_refs(body, bound, refs),
+        ExprKind::App { func, args } => {
+            use_name(func);
+            args.iter().for_each(use_name);
+        }
+        ExprKind::Constructor { args, .. } | ExprKind::Builtin { args, .. } => {
+            args.iter().for_each(use_name)
+        }
+        ExprKind::Match { scrutinee, arms } => {
+            use_name(scrutinee);
+            for (pattern, arm) in arms {
+                expr_refs(arm, &with(pattern.binders()), refs);
+            }
+        }
+    }
+}
+
+/// Names used by `stmts` that are not bound by them or in `bound`.
+pub(crate) fn stmts_refs<'a>(stmts: &'a [Stmt], bound: &mut Vec<&'a str>, refs: &mut Vec<String>) {
+    for stmt in stmts {
+        let mut used: Vec<&String> = vec![];
+        let mut bind = None;
+        match &stmt.kind {
+            StmtKind::Load { lhs, .. } => bind = Some(lhs),
+            StmtKind::Store { rhs, .. } => used.push(rhs),
+            StmtKind::Bind { lhs, expr } => {
+                expr_refs(expr, bound, refs);
+                bind = Some(lhs);
+            }
+            StmtKind::MapUpdate { keys, value, .. } => {
+                used.extend(keys);
+                used.push(value);
+            }
+            StmtKind::MapDelete { keys, .. } => used.extend(keys),
+            StmtKind::MapGet { lhs, keys, .. } => {
+                used.extend(keys);
+                bind = Some(lhs);
+            }
+            StmtKind::RemoteLoad { lhs, address, .. } | StmtKind::TypeCast { lhs, address, .. } => {
+                used.push(address);
+                bind = Some(lhs);
+            }
+            StmtKind::RemoteMapGet {
+                lhs, address, keys, ..
+            } => {
+                used.push(address);
+                used.extend(keys);
+                bind = Some(lhs);
+            }
+            StmtKind::ReadFromBC { lhs, args, .. } => {
+                used.extend(args);
+                bind = Some(lhs);
+            }
+            StmtKind::Accept | StmtKind::Throw(None) => {}
+            StmtKind::Send(name) | StmtKind::Event(name) | StmtKind::Throw(Some(name)) => {
+                used.push(name)
+            }
+            StmtKind::Match { scrutinee, arms } => {
+                used.push(scrutinee);
+                for (pattern, arm) in arms {
+                    let mut bound = bound.clone();
+                    bound.extend(pattern.binders());
+                    stmts_refs(arm, &mut bound, refs);
+                }
+            }
+            StmtKind::CallProc { args, .. } => used.extend(args),
+            StmtKind::Iterate { list, .. } => used.push(list),
+        }
+        for name in used {
+            if !bound.contains(&name.as_str()) && !refs.contains(name) {
+                refs.push(name.clone());
+            }
+        }
+        if let Some(lhs) = bind {
+            bound.push(lhs);
+        }
+    }
+}
+
+impl CallGraph {
+    pub fn node(&self, name: &str) -> Option<&Node> {
+        self.nodes.iter().find(|n| n.name == name)
+    }
+
+    /// The procedures and library entries `name` uses directly.
+    pub fn callees<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.edges
+            .iter()
+            .filter(move |(from, _)| from == name)
+            .map(|(_, to)| to.as_str())
+    }
+
+    /// The nodes using `name` directly.
+    pub fn callers<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.edges
+            .iter()
+            .filter(move |(_, to)| to == name)
+            .map(|(from, _)| from.as_str())
+    }
+
+    /// The nodes `name` uses, directly or indirectly.
+    pub fn reachable_from<'a>(&'a self, name: &'a str) -> Vec<&'a str> {
+        let mut reached: Vec<&str> = self.callees(name).collect();
+        let mut index = 0;
+        while index < reached.len() {
+            for callee in self.callees(reached[index]) {
+                if !reached.contains(&callee) {
+                    reached.push(callee);
+                }
+            }
+            index += 1;
+        }
+        reached
+    }
+
+    /// The transitions that are `name` or use it, directly or indirectly, e.g. the transitions
+    /// that can reach the `ThrowError` procedure.
+    pub fn transitions_reaching(&self, name: &str) -> Vec<&str> {
+        self.nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Transition)
+            .filter(|n| n.name == name || self.reachable_from(&n.name).contains(&name))
+            .map(|n| n.name.as_str())
+            .collect()
+    }
+
+    /// The procedures and library entries that neither the contract nor any transition uses.
+    pub fn unused(&self) -> Vec<&Node> {
+        let mut used: Vec<&str> = vec![];
+        for root in self
+            .nodes
+            .iter()
+            .filter(|n| matches!(n.kind, NodeKind::Contract | NodeKind::Transition))
+        {
+            used.extend(self.reachable_from(&root.name));
+        }
+        self.nodes
+            .iter()
+            .filter(|n| matches!(n.kind, NodeKind::Procedure | NodeKind::Library))
+            .filter(|n| !used.contains(&n.name.as_str()))
+            .collect()
+    }
+
+    /// The graph in the Graphviz DOT language: transitions are boxes, procedures ellipses and
+    /// library entries notes.
+    pub fn to_dot(&self) -> String {
+        let name = self
+            .nodes
+            .iter()
+            .find(|n| n.kind == NodeKind::Contract)
+            .map(|n| n.name.as_str())
+            .unwrap_or_default();
+        let mut dot = format!("digraph \"{name}\" {{\n");
+        for node in &self.nodes {
+            let shape = match node.kind {
+                NodeKind::Contract => "house",
+                NodeKind::Transition => "box",
+                NodeKind::Procedure => "ellipse",
+                NodeKind::Library => "note",
+            };
+            let _ = writeln!(dot, "  \"{}\" [shape={shape}];", node.name);
+        }
+        for (from, to) in &self.edges {
+            let _ = writeln!(dot, "  \"{from}\" -> \"{to}\";");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The graph as `{"nodes": [{"name": ..., "kind": ...}], "edges": [{"from": ..., "to": ...}]}`.
+    pub fn to_json(&self) -> JsonValue {
+        json!({
+            "nodes": self
+                .nodes
+                .iter()
+                .map(|n| json!({"name": n.name, "kind": n.kind.as_str()}))
+                .collect::<Vec<_>>(),
+            "edges": self
+                .edges
+                .iter()
+                .map(|(from, to)| json!({"from": from, "to": to}))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl Contract {
+    /// Build the call graph of the contract. Local names shadowing library entries are not
+    /// reported as uses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    /// let graph = contract.call_graph().unwrap();
+    /// assert_eq!(
+    ///     graph.callees("Transfer").collect::<Vec<_>>(),
+    ///     vec!["AuthorizedMoveIfSufficientBalance", "zero", "two_msgs"]
+    /// );
+    /// assert_eq!(graph.transitions_reaching("IsOwner"), vec!["Mint", "Burn"]);
+    /// ```
+    pub fn call_graph(&self) -> Result<CallGraph, Error> {
+        let module = self.syntax_tree()?;
+        let contract = &module.contract;
+        let library: Vec<(&String, &Expr)> = module
+            .library
+            .iter()
+            .flat_map(|lib| &lib.entries)
+            .filter_map(|entry| match entry {
+                LibEntry::Let { name, expr, .. } => Some((name, expr)),
+                LibEntry::Type { .. } => None,
+            })
+            .collect();
+
+        let mut graph = CallGraph::default();
+        let add = |graph: &mut CallGraph, name: &str, kind, refs: Vec<String>| {
+            graph.nodes.push(Node {
+                name: name.to_string(),
+                kind,
+            });
+            for callee in refs {
+                let edge = (name.to_string(), callee);
+                if !graph.edges.contains(&edge) {
+                    graph.edges.push(edge);
+                }
+            }
+        };
+        let library_refs = |refs: Vec<String>| -> Vec<String> {
+            refs.into_iter()
+                .filter(|name| library.iter().any(|(entry, _)| *entry == name))
+                .collect()
+        };
+
+        let params: Vec<&str> = contract.params.iter().map(|p| p.name.as_str()).collect();
+        let mut refs = vec![];
+        for expr in contract
+            .constraint
+            .iter()
+            .chain(contract.fields.iter().map(|f| &f.init))
+        {
+            expr_refs(expr, &params, &mut refs);
+        }
+        add(
+            &mut graph,
+            &contract.name,
+            NodeKind::Contract,
+            library_refs(refs),
+        );
+
+        for component in &contract.components {
+            let mut bound: Vec<&str> = ["_sender", "_origin", "_this_address", "_amount"]
+                .into_iter()
+                .chain(params.iter().copied())
+                .chain(component.params.iter().map(|p| p.name.as_str()))
+                .collect();
+            let mut refs = vec![];
+            for stmt in &component.body {
+                stmt.walk(&mut |stmt| match &stmt.kind {
+                    StmtKind::CallProc { name, .. } | StmtKind::Iterate { proc: name, .. }
+                        if !refs.contains(name) =>
+                    {
+                        refs.push(name.clone())
+                    }
+                    _ => {}
+                });
+            }
+            let mut used = vec![];
+            stmts_refs(&component.body, &mut bound, &mut used);
+            refs.extend(library_refs(used));
+            let kind = match component.kind {
+                ComponentKind::Transition => NodeKind::Transition,
+                ComponentKind::Procedure => NodeKind::Procedure,
+            };
+            add(&mut graph, &component.name, kind, refs);
+        }
+
+        for (name, expr) in &library {
+            let mut refs = vec![];
+            expr_refs(expr, &[], &mut refs);
+            add(&mut graph, name, NodeKind::Library, library_refs(refs));
+        }
+        Ok(graph)
+    }
+}
@@ -0,0 +1,277 @@
+//! Generation of typed Rust bindings for a contract, in the spirit of ethers' `abigen`.
+//!
+//! The generated code depends on [`scilla_parser::bindings`](crate::bindings) at runtime.
+
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::{Contract, Error, FieldList, Type};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro", "override",
+    "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Convert a Scilla identifier such as `IncreaseAllowance` or `setHello` to snake case.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let chars: Vec<char> = name.chars().collect();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_numeric());
+            let before_lower = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            if (after_lower || before_lower) && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(*c);
+        }
+    }
+    snake
+}
+
+/// A snake case identifier, raw if it is a Rust keyword.
+fn ident(name: &str) -> Ident {
+    let name = snake_case(name);
+    match name.as_str() {
+        "self" | "super" | "crate" => format_ident!("{}_", name),
+        _ if KEYWORDS.contains(&name.as_str()) => Ident::new_raw(&name, Span::call_site()),
+        _ => Ident::new(&name, Span::call_site()),
+    }
+}
+
+/// Identifiers for `names` as by [`ident`], with a `_2`, `_3`, … suffix for those that would clash
+/// with an earlier one, e.g. `setHello` and `SetHello`, or with one of `reserved`.
+fn unique_idents<'a>(names: impl IntoIterator<Item = &'a str>, reserved: &[&str]) -> Vec<Ident> {
+    let mut taken: Vec<String> = reserved.iter().map(ToString::to_string).collect();
+    names
+        .into_iter()
+        .map(|name| {
+            let base = ident(name);
+            let mut unique = base.clone();
+            for n in 2usize.. {
+                if !taken.contains(&unique.to_string()) {
+                    break;
+                }
+                unique = format_ident!("{}_{}", base, n);
+            }
+            taken.push(unique.to_string());
+            unique
+        })
+        .collect()
+}
+
+/// The Rust type representing values of a Scilla type.
+fn rust_type(ty: &Type) -> TokenStream {
+    let bindings = quote!(::scilla_parser::bindings);
+    match ty {
+        Type::Int32 => quote!(i32),
+        Type::Int64 => quote!(i64),
+        Type::Int128 => quote!(i128),
+        Type::Int256 => quote!(#bindings::BigInt),
+        Type::Uint32 => quote!(u32),
+        Type::Uint64 => quote!(u64),
+        Type::Uint128 => quote!(u128),
+        Type::Uint256 => quote!(#bindings::U256),
+        Type::String => quote!(::std::string::String),
+        Type::BNum => quote!(#bindings::BNum),
+        Type::Bool => quote!(bool),
+        Type::ByStr(n) => {
+            let n = Literal::usize_unsuffixed(*n);
+            quote!([u8; #n])
+        }
+        Type::Address(_) => quote!([u8; 20]),
+        Type::Map(k, v) => {
+            let (k, v) = (rust_type(k), rust_type(v));
+            quote!(::std::collections::BTreeMap<#k, #v>)
+        }
+        Type::Option(t) => {
+            let t = rust_type(t);
+            quote!(::std::option::Option<#t>)
+        }
+        Type::List(t) => {
+            let t = rust_type(t);
+            quote!(::std::vec::Vec<#t>)
+        }
+        Type::Pair(a, b) => {
+            let (a, b) = (rust_type(a), rust_type(b));
+            quote!((#a, #b))
+        }
+        Type::Other(name) if name == "ByStr" => quote!(#bindings::Bytes),
+        _ => quote!(#bindings::JsonValue),
+    }
+}
+
+/// Function arguments and the `CallParam`s built from them.
+fn call_params(params: &FieldList) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    let names = unique_idents(params.iter().map(|p| p.name.as_str()), &[]);
+    params
+        .iter()
+        .zip(names)
+        .map(|(p, name)| {
+            let ty = rust_type(&p.r#type);
+            let (vname, scilla_type) = (&p.name, p.r#type.to_scilla_string());
+            (
+                quote!(#name: #ty),
+                quote!(::scilla_parser::bindings::CallParam::new(#vname, #scilla_type, &#name)),
+            )
+        })
+        .unzip()
+}
+
+impl Contract {
+    /// Generate Rust bindings for the contract:
+    ///
+    /// * a struct named after the contract with a method per transition returning a
+    ///   [`TransitionCall`](crate::bindings::TransitionCall),
+    /// * a `<Name>Init` struct with the init parameters of the contract,
+    /// * a `<Name>State` struct wrapping the JSON state of the contract with an accessor per field.
+    ///
+    /// Methods, fields and arguments are named in snake case. Names that would clash, with each
+    /// other or with the `new` constructor, get a `_2`, `_3`, … suffix in declaration order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0 contract Counter () transition Increase (by : Uint128) end",
+    /// )
+    /// .unwrap();
+    /// let bindings = contract.rust_bindings().to_string();
+    /// assert!(bindings.contains("pub fn increase (& self , by : u128)"));
+    /// ```
+    pub fn rust_bindings(&self) -> TokenStream {
+        let name = format_ident!("{}", self.name);
+        let init = format_ident!("{}Init", self.name);
+        let state = format_ident!("{}State", self.name);
+        let doc = format!(" Bindings of the `{}` contract.", self.name);
+        let init_doc = format!(" Init parameters of the `{}` contract.", self.name);
+        let state_doc = format!(" State of the `{}` contract.", self.name);
+
+        let method_names =
+            unique_idents(self.transitions.iter().map(|t| t.name.as_str()), &["new"]);
+        let methods = self
+            .transitions
+            .iter()
+            .zip(method_names)
+            .map(|(transition, method)| {
+                let tag = &transition.name;
+                let doc = format!(" Call the `{tag}` transition.");
+                let (args, params) = call_params(&transition.params);
+                quote! {
+                    #[doc = #doc]
+                    pub fn #method(&self, #(#args),*) -> ::scilla_parser::bindings::TransitionCall {
+                        ::scilla_parser::bindings::TransitionCall::new(
+                            self.address,
+                            #tag,
+                            vec![#(#params),*],
+                        )
+                    }
+                }
+            });
+
+        let init_names = unique_idents(self.init_params.iter().map(|p| p.name.as_str()), &[]);
+        let init_fields = self.init_params.iter().zip(&init_names).map(|(p, name)| {
+            let ty = rust_type(&p.r#type);
+            quote!(pub #name: #ty)
+        });
+        let init_params = self.init_params.iter().zip(&init_names).map(|(p, name)| {
+            let (vname, ty) = (&p.name, p.r#type.to_scilla_string());
+            quote!(::scilla_parser::bindings::CallParam::new(#vname, #ty, &self.#name))
+        });
+
+        let accessor_names = unique_idents(self.fields.iter().map(|f| f.name.as_str()), &[]);
+        let accessors = self
+            .fields
+            .iter()
+            .zip(accessor_names)
+            .map(|(field, method)| {
+                let ty = rust_type(&field.r#type);
+                let vname = &field.name;
+                let doc = format!(" The `{vname}` field.");
+                quote! {
+                    #[doc = #doc]
+                    pub fn #method(&self) -> ::std::result::Result<#ty, ::scilla_parser::Error> {
+                        ::scilla_parser::bindings::ScillaValue::from_json(&self.0[#vname])
+                    }
+                }
+            });
+
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct #name {
+                pub address: [u8; 20],
+            }
+
+            #[allow(clippy::too_many_arguments)]
+            impl #name {
+                pub fn new(address: [u8; 20]) -> Self {
+                    Self { address }
+                }
+
+                #(#methods)*
+            }
+
+            #[doc = #init_doc]
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct #init {
+                #(#init_fields,)*
+            }
+
+            impl #init {
+                /// The init JSON used to deploy the contract.
+                pub fn to_json(&self) -> ::scilla_parser::bindings::JsonValue {
+                    ::scilla_parser::bindings::params_json(&[
+                        ::scilla_parser::bindings::CallParam::new("_scilla_version", "Uint32", &0u32),
+                        #(#init_params),*
+                    ])
+                }
+            }
+
+            #[doc = #state_doc]
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct #state(pub ::scilla_parser::bindings::JsonValue);
+
+            #[allow(clippy::type_complexity)]
+            impl #state {
+                #(#accessors)*
+            }
+        }
+    }
+
+    /// Generate Rust bindings as formatted source code, e.g. to be written by a build script.
+    pub fn rust_bindings_string(&self) -> Result<String, Error> {
+        let file = syn::parse2(self.rust_bindings())
+            .map_err(|e| Error::InvalidValue(format!("Invalid generated code: {e}")))?;
+        Ok(prettyplease::unparse(&file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(snake_case("IncreaseAllowance"), "increase_allowance");
+        assert_eq!(snake_case("setHello"), "set_hello");
+        assert_eq!(snake_case("ZRC6_MintCallback"), "zrc6_mint_callback");
+        assert_eq!(snake_case("token_id"), "token_id");
+        assert_eq!(ident("type").to_string(), "r#type");
+        assert_eq!(ident("Self").to_string(), "self_");
+        let names = unique_idents(["New", "setHello", "SetHello", "type", "Type"], &["new"]);
+        let names: Vec<String> = names.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            names,
+            ["new_2", "set_hello", "set_hello_2", "r#type", "type_2"]
+        );
+    }
+}
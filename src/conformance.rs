@@ -0,0 +1,727 @@
+//! Conformance of contracts to the ZRC token standards.
+
+use std::fmt::Display;
+
+use crate::{parser::parse_type, printer::print_type, Contract, Field, FieldList, Type};
+
+/// A Zilliqa Reference Contract standard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Standard {
+    /// Non-fungible tokens, superseded by ZRC-6.
+    Zrc1,
+    /// Fungible tokens.
+    Zrc2,
+    /// Multi-signature wallets.
+    Zrc4,
+    /// Non-fungible tokens.
+    Zrc6,
+}
+
+impl Display for Standard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Standard::Zrc1 => write!(f, "ZRC-1"),
+            Standard::Zrc2 => write!(f, "ZRC-2"),
+            Standard::Zrc4 => write!(f, "ZRC-4"),
+            Standard::Zrc6 => write!(f, "ZRC-6"),
+        }
+    }
+}
+
+/// An immutable parameter or a field of a standard, with its type in Scilla syntax. Type variables
+/// stand for types the standard leaves to the contract, such as the values of maps used as sets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldSpec {
+    pub name: &'static str,
+    pub r#type: &'static str,
+    pub required: bool,
+}
+
+/// A transition of a standard, with the events it must emit and the callbacks it must send.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransitionSpec {
+    pub name: &'static str,
+    /// Names and types of the parameters, in Scilla syntax.
+    pub params: &'static [(&'static str, &'static str)],
+    pub required: bool,
+    pub events: &'static [&'static str],
+    /// Tags of the messages the transition must send.
+    pub callbacks: &'static [&'static str],
+}
+
+/// The interface a standard requires.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spec {
+    pub init_params: &'static [FieldSpec],
+    pub fields: &'static [FieldSpec],
+    pub transitions: &'static [TransitionSpec],
+}
+
+const fn required(name: &'static str, r#type: &'static str) -> FieldSpec {
+    FieldSpec {
+        name,
+        r#type,
+        required: true,
+    }
+}
+
+const fn optional(name: &'static str, r#type: &'static str) -> FieldSpec {
+    FieldSpec {
+        name,
+        r#type,
+        required: false,
+    }
+}
+
+const fn transition(
+    name: &'static str,
+    params: &'static [(&'static str, &'static str)],
+    events: &'static [&'static str],
+    callbacks: &'static [&'static str],
+) -> TransitionSpec {
+    TransitionSpec {
+        name,
+        params,
+        required: true,
+        events,
+        callbacks,
+    }
+}
+
+impl TransitionSpec {
+    const fn optional(self) -> Self {
+        TransitionSpec {
+            required: false,
+            ..self
+        }
+    }
+}
+
+const ZRC1: Spec = Spec {
+    init_params: &[
+        required("contract_owner", "ByStr20"),
+        required("name", "String"),
+        required("symbol", "String"),
+    ],
+    fields: &[
+        required("minters", "Map ByStr20 'A"),
+        required("token_owners", "Map Uint256 ByStr20"),
+        required("owned_token_count", "Map ByStr20 Uint256"),
+        required("token_approvals", "Map Uint256 ByStr20"),
+        required("operator_approvals", "Map ByStr20 (Map ByStr20 'A)"),
+        required("token_uris", "Map Uint256 String"),
+        required("total_supply", "Uint256"),
+    ],
+    transitions: &[
+        transition(
+            "ConfigureMinter",
+            &[("minter", "ByStr20")],
+            &["AddMinterSuccess", "RemovedMinterSuccess"],
+            &[],
+        ),
+        transition(
+            "Mint",
+            &[("to", "ByStr20"), ("token_uri", "String")],
+            &["MintSuccess"],
+            &["RecipientAcceptMint", "MintCallBack"],
+        ),
+        transition(
+            "Burn",
+            &[("token_id", "Uint256")],
+            &["BurnSuccess"],
+            &["BurnCallBack"],
+        ),
+        transition(
+            "SetApprove",
+            &[("to", "ByStr20"), ("token_id", "Uint256")],
+            &["AddApprovalSuccess", "RemoveApprovalSuccess"],
+            &[
+                "AddApprovalSuccessCallBack",
+                "RemoveApprovalSuccessCallBack",
+            ],
+        ),
+        transition(
+            "SetApprovalForAll",
+            &[("to", "ByStr20")],
+            &["AddApprovalForAllSuccess", "RemoveApprovalForAllSuccess"],
+            &["SetApprovalForAllSuccessCallBack"],
+        ),
+        transition(
+            "Transfer",
+            &[("to", "ByStr20"), ("token_id", "Uint256")],
+            &["TransferSuccess"],
+            &["RecipientAcceptTransfer", "TransferSuccessCallBack"],
+        ),
+        transition(
+            "TransferFrom",
+            &[("to", "ByStr20"), ("token_id", "Uint256")],
+            &["TransferFromSuccess"],
+            &["RecipientAcceptTransferFrom", "TransferFromSuccessCallBack"],
+        ),
+        transition(
+            "BalanceOf",
+            &[("address", "ByStr20")],
+            &[],
+            &["BalanceOfCallBack"],
+        )
+        .optional(),
+        transition("TotalSupply", &[], &[], &["TotalSupplyCallBack"]).optional(),
+        transition("Name", &[], &[], &["NameCallBack"]).optional(),
+        transition("Symbol", &[], &[], &["SymbolCallBack"]).optional(),
+        transition(
+            "GetApproved",
+            &[("token_id", "Uint256")],
+            &[],
+            &["GetApprovedCallBack"],
+        )
+        .optional(),
+        transition(
+            "GetTokenURI",
+            &[("token_id", "Uint256")],
+            &[],
+            &["GetTokenURICallBack"],
+        )
+        .optional(),
+        transition(
+            "CheckTokenOwner",
+            &[("token_id", "Uint256"), ("address", "ByStr20")],
+            &[],
+            &["IsTokenOwnerCallBack"],
+        )
+        .optional(),
+        transition(
+            "CheckApprovedForAll",
+            &[("token_owner", "ByStr20"), ("operator", "ByStr20")],
+            &[],
+            &["IsApprovedForAllCallBack"],
+        )
+        .optional(),
+    ],
+};
+
+const ZRC2: Spec = Spec {
+    init_params: &[
+        required("contract_owner", "ByStr20"),
+        required("name", "String"),
+        required("symbol", "String"),
+        required("decimals", "Uint32"),
+        required("init_supply", "Uint128"),
+    ],
+    fields: &[
+        required("total_supply", "Uint128"),
+        required("balances", "Map ByStr20 Uint128"),
+        required("allowances", "Map ByStr20 (Map ByStr20 Uint128)"),
+    ],
+    transitions: &[
+        transition(
+            "Mint",
+            &[("recipient", "ByStr20"), ("amount", "Uint128")],
+            &["Minted"],
+            &["RecipientAcceptMint", "MintSuccessCallBack"],
+        )
+        .optional(),
+        transition(
+            "Burn",
+            &[("burn_account", "ByStr20"), ("amount", "Uint128")],
+            &["Burnt"],
+            &["BurnSuccessCallBack"],
+        )
+        .optional(),
+        transition(
+            "IncreaseAllowance",
+            &[("spender", "ByStr20"), ("amount", "Uint128")],
+            &["IncreasedAllowance"],
+            &[],
+        ),
+        transition(
+            "DecreaseAllowance",
+            &[("spender", "ByStr20"), ("amount", "Uint128")],
+            &["DecreasedAllowance"],
+            &[],
+        ),
+        transition(
+            "Transfer",
+            &[("to", "ByStr20"), ("amount", "Uint128")],
+            &["TransferSuccess"],
+            &["RecipientAcceptTransfer", "TransferSuccessCallBack"],
+        ),
+        transition(
+            "TransferFrom",
+            &[
+                ("from", "ByStr20"),
+                ("to", "ByStr20"),
+                ("amount", "Uint128"),
+            ],
+            &["TransferFromSuccess"],
+            &["RecipientAcceptTransferFrom", "TransferFromSuccessCallBack"],
+        ),
+    ],
+};
+
+/// The standard leaves the events of the wallet to the implementation.
+const ZRC4: Spec = Spec {
+    init_params: &[
+        required("owners_list", "List ByStr20"),
+        required("required_signatures", "Uint32"),
+    ],
+    fields: &[
+        required("owners", "Map ByStr20 Bool"),
+        required("transactionCount", "Uint32"),
+        required("signatures", "Map Uint32 (Map ByStr20 Bool)"),
+        required("signature_counts", "Map Uint32 Uint32"),
+        required("transactions", "Map Uint32 'A"),
+    ],
+    transitions: &[
+        transition(
+            "SubmitTransaction",
+            &[
+                ("recipient", "ByStr20"),
+                ("amount", "Uint128"),
+                ("tag", "String"),
+            ],
+            &[],
+            &[],
+        ),
+        transition("SignTransaction", &[("transactionId", "Uint32")], &[], &[]),
+        transition("RevokeSignature", &[("transactionId", "Uint32")], &[], &[]),
+        transition(
+            "ExecuteTransaction",
+            &[("transactionId", "Uint32")],
+            &[],
+            &[],
+        ),
+        transition("AddFunds", &[], &[], &[]),
+    ],
+};
+
+const ZRC6: Spec = Spec {
+    init_params: &[
+        required("initial_contract_owner", "ByStr20"),
+        required("initial_base_uri", "String"),
+        required("name", "String"),
+        required("symbol", "String"),
+    ],
+    fields: &[
+        optional("is_paused", "Bool"),
+        optional("token_name", "String"),
+        optional("token_symbol", "String"),
+        required("contract_owner", "ByStr20"),
+        optional("contract_ownership_recipient", "ByStr20"),
+        optional("royalty_recipient", "ByStr20"),
+        optional("royalty_fee_bps", "Uint128"),
+        required("base_uri", "String"),
+        optional("token_uris", "Map Uint256 String"),
+        required("token_owners", "Map Uint256 ByStr20"),
+        required("token_id_count", "Uint256"),
+        required("total_supply", "Uint256"),
+        required("balances", "Map ByStr20 Uint256"),
+        optional("minters", "Map ByStr20 Bool"),
+        required("spenders", "Map Uint256 ByStr20"),
+        required("operators", "Map ByStr20 (Map ByStr20 Bool)"),
+    ],
+    transitions: &[
+        transition("Pause", &[], &["Pause"], &["ZRC6_PauseCallback"]).optional(),
+        transition("Unpause", &[], &["Unpause"], &["ZRC6_UnpauseCallback"]).optional(),
+        transition(
+            "SetRoyaltyRecipient",
+            &[("to", "ByStr20")],
+            &["SetRoyaltyRecipient"],
+            &["ZRC6_SetRoyaltyRecipientCallback"],
+        )
+        .optional(),
+        transition(
+            "SetRoyaltyFeeBPS",
+            &[("fee_bps", "Uint128")],
+            &["SetRoyaltyFeeBPS"],
+            &["ZRC6_SetRoyaltyFeeBPSCallback"],
+        )
+        .optional(),
+        transition(
+            "SetBaseURI",
+            &[("uri", "String")],
+            &["SetBaseURI"],
+            &["ZRC6_SetBaseURICallback"],
+        )
+        .optional(),
+        transition(
+            "Mint",
+            &[("to", "ByStr20"), ("token_uri", "String")],
+            &["Mint"],
+            &["ZRC6_RecipientAcceptMint", "ZRC6_MintCallback"],
+        )
+        .optional(),
+        transition(
+            "BatchMint",
+            &[("to_token_uri_pair_list", "List (Pair ByStr20 String)")],
+            &["BatchMint"],
+            &["ZRC6_BatchMintCallback"],
+        )
+        .optional(),
+        transition(
+            "Burn",
+            &[("token_id", "Uint256")],
+            &["Burn"],
+            &["ZRC6_BurnCallback"],
+        )
+        .optional(),
+        transition(
+            "BatchBurn",
+            &[("token_id_list", "List Uint256")],
+            &["BatchBurn"],
+            &["ZRC6_BatchBurnCallback"],
+        )
+        .optional(),
+        transition(
+            "AddMinter",
+            &[("minter", "ByStr20")],
+            &["AddMinter"],
+            &["ZRC6_AddMinterCallback"],
+        )
+        .optional(),
+        transition(
+            "RemoveMinter",
+            &[("minter", "ByStr20")],
+            &["RemoveMinter"],
+            &["ZRC6_RemoveMinterCallback"],
+        )
+        .optional(),
+        transition(
+            "SetSpender",
+            &[("spender", "ByStr20"), ("token_id", "Uint256")],
+            &["SetSpender"],
+            &["ZRC6_SetSpenderCallback"],
+        ),
+        transition(
+            "AddOperator",
+            &[("operator", "ByStr20")],
+            &["AddOperator"],
+            &["ZRC6_AddOperatorCallback"],
+        ),
+        transition(
+            "RemoveOperator",
+            &[("operator", "ByStr20")],
+            &["RemoveOperator"],
+            &["ZRC6_RemoveOperatorCallback"],
+        ),
+        transition(
+            "TransferFrom",
+            &[("to", "ByStr20"), ("token_id", "Uint256")],
+            &["TransferFrom"],
+            &[
+                "ZRC6_RecipientAcceptTransferFrom",
+                "ZRC6_TransferFromCallback",
+            ],
+        ),
+        transition(
+            "BatchTransferFrom",
+            &[("to_token_id_pair_list", "List (Pair ByStr20 Uint256)")],
+            &["BatchTransferFrom"],
+            &["ZRC6_BatchTransferFromCallback"],
+        )
+        .optional(),
+        transition(
+            "SetContractOwnershipRecipient",
+            &[("to", "ByStr20")],
+            &["SetContractOwnershipRecipient"],
+            &["ZRC6_SetContractOwnershipRecipientCallback"],
+        )
+        .optional(),
+        transition(
+            "AcceptContractOwnership",
+            &[],
+            &["AcceptContractOwnership"],
+            &["ZRC6_AcceptContractOwnershipCallback"],
+        )
+        .optional(),
+    ],
+};
+
+impl Standard {
+    /// The immutable parameters, fields and transitions the standard requires or allows.
+    pub fn spec(&self) -> &'static Spec {
+        match self {
+            Standard::Zrc1 => &ZRC1,
+            Standard::Zrc2 => &ZRC2,
+            Standard::Zrc4 => &ZRC4,
+            Standard::Zrc6 => &ZRC6,
+        }
+    }
+}
+
+fn spec_type(r#type: &str) -> Type {
+    parse_type(r#type).expect("types of the standards are valid")
+}
+
+/// Whether a declared type satisfies the type of a standard: type variables match any type and
+/// `ByStr20 with ... end` is a `ByStr20`.
+pub(crate) fn satisfies(found: &Type, expected: &Type) -> bool {
+    match (found, expected) {
+        (_, Type::TypeVar(_)) => true,
+        (Type::Address(_), Type::ByStr(20)) => true,
+        (Type::Map(k1, v1), Type::Map(k2, v2)) | (Type::Pair(k1, v1), Type::Pair(k2, v2)) => {
+            satisfies(k1, k2) && satisfies(v1, v2)
+        }
+        (Type::Option(t1), Type::Option(t2)) | (Type::List(t1), Type::List(t2)) => {
+            satisfies(t1, t2)
+        }
+        _ => found == expected,
+    }
+}
+
+/// A way a contract does not conform to a standard.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    MissingInitParam(Field),
+    InitParamType {
+        name: String,
+        expected: Type,
+        found: Type,
+    },
+    MissingField(Field),
+    FieldType {
+        name: String,
+        expected: Type,
+        found: Type,
+    },
+    MissingTransition {
+        name: String,
+        params: FieldList,
+    },
+    /// The transition does not take exactly the parameters of the standard, in any order.
+    TransitionParams {
+        name: String,
+        expected: FieldList,
+        found: FieldList,
+    },
+    EventNotEmitted {
+        transition: String,
+        event: String,
+    },
+    CallbackNotSent {
+        transition: String,
+        tag: String,
+    },
+}
+
+fn signature(params: &FieldList) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|p| format!("{} : {}", p.name, print_type(&p.r#type)))
+        .collect();
+    format!("({})", params.join(", "))
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::MissingInitParam(param) => write!(
+                f,
+                "missing immutable parameter `{} : {}`",
+                param.name,
+                print_type(&param.r#type)
+            ),
+            Violation::InitParamType {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "immutable parameter `{name}` has type `{}` instead of `{}`",
+                print_type(found),
+                print_type(expected)
+            ),
+            Violation::MissingField(field) => write!(
+                f,
+                "missing field `{} : {}`",
+                field.name,
+                print_type(&field.r#type)
+            ),
+            Violation::FieldType {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "field `{name}` has type `{}` instead of `{}`",
+                print_type(found),
+                print_type(expected)
+            ),
+            Violation::MissingTransition { name, params } => {
+                write!(f, "missing transition `{name} {}`", signature(params))
+            }
+            Violation::TransitionParams {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "transition `{name}` takes `{}` instead of `{}`",
+                signature(found),
+                signature(expected)
+            ),
+            Violation::EventNotEmitted { transition, event } => {
+                write!(f, "transition `{transition}` does not emit `{event}`")
+            }
+            Violation::CallbackNotSent { transition, tag } => {
+                write!(f, "transition `{transition}` does not send `{tag}`")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ViolationList(pub Vec<Violation>);
+
+impl std::ops::Deref for ViolationList {
+    type Target = Vec<Violation>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Compare declared parameters or fields with those of a standard.
+fn check_fields(
+    specs: &[FieldSpec],
+    declared: &FieldList,
+    missing: fn(Field) -> Violation,
+    wrong_type: fn(String, Type, Type) -> Violation,
+    violations: &mut Vec<Violation>,
+) {
+    for spec in specs {
+        let expected = spec_type(spec.r#type);
+        match declared.iter().find(|f| f.name == spec.name) {
+            None if spec.required => violations.push(missing(Field::new(spec.name, expected))),
+            Some(field) if !satisfies(&field.r#type, &expected) => violations.push(wrong_type(
+                spec.name.to_string(),
+                expected,
+                field.r#type.clone(),
+            )),
+            _ => {}
+        }
+    }
+}
+
+/// Check that the contract implements the interface of `standard`: its immutable parameters, fields
+/// and transitions with their exact parameter names and types, where an address type satisfies
+/// `ByStr20`. The events and callbacks of the transitions are only checked when the contract is
+/// parsed from its Scilla source. Optional transitions and fields are checked when the contract
+/// declares them.
+///
+/// # Examples
+///
+/// ```
+/// use scilla_parser::{check_conformance, Contract, Standard};
+/// let contract = Contract::from_source(
+///     "scilla_version 0
+///     contract Token (
+///       contract_owner : ByStr20, name : String, symbol : String,
+///       decimals : Uint32, init_supply : Uint128
+///     )
+///     field total_supply : Uint128 = init_supply
+///     field balances : Map ByStr20 Uint128 = Emp ByStr20 Uint128
+///     field allowances : Map ByStr20 (Map ByStr20 Uint128) = Emp ByStr20 (Map ByStr20 Uint128)
+///     transition Transfer (to : ByStr20, amount : Uint64) end",
+/// )
+/// .unwrap();
+/// let violations: Vec<String> = check_conformance(&contract, Standard::Zrc2)
+///     .iter()
+///     .map(ToString::to_string)
+///     .collect();
+/// assert_eq!(
+///     violations[2],
+///     "transition `Transfer` takes `(to : ByStr20, amount : Uint64)` instead of \
+///      `(to : ByStr20, amount : Uint128)`"
+/// );
+/// assert_eq!(violations[3], "transition `Transfer` does not emit `TransferSuccess`");
+/// ```
+pub fn check_conformance(contract: &Contract, standard: Standard) -> ViolationList {
+    let spec = standard.spec();
+    let mut violations = vec![];
+    check_fields(
+        spec.init_params,
+        &contract.init_params,
+        Violation::MissingInitParam,
+        |name, expected, found| Violation::InitParamType {
+            name,
+            expected,
+            found,
+        },
+        &mut violations,
+    );
+    check_fields(
+        spec.fields,
+        &contract.fields,
+        Violation::MissingField,
+        |name, expected, found| Violation::FieldType {
+            name,
+            expected,
+            found,
+        },
+        &mut violations,
+    );
+
+    // Events and callbacks are only known from the Scilla source.
+    let code = contract
+        .events()
+        .and_then(|events| Ok((events, contract.messages()?)))
+        .ok();
+    for spec in spec.transitions {
+        let expected = FieldList(
+            spec.params
+                .iter()
+                .map(|(name, r#type)| Field::new(name, spec_type(r#type)))
+                .collect(),
+        );
+        let Some(transition) = contract.transitions.iter().find(|t| t.name == spec.name) else {
+            if spec.required {
+                violations.push(Violation::MissingTransition {
+                    name: spec.name.to_string(),
+                    params: expected,
+                });
+            }
+            continue;
+        };
+
+        let found = &transition.params;
+        let declared = |p: &Field| {
+            found
+                .iter()
+                .any(|f| f.name == p.name && satisfies(&f.r#type, &p.r#type))
+        };
+        if found.len() != expected.len() || !expected.iter().all(declared) {
+            violations.push(Violation::TransitionParams {
+                name: spec.name.to_string(),
+                expected,
+                found: found.clone(),
+            });
+        }
+
+        let Some((events, messages)) = &code else {
+            continue;
+        };
+        for event in spec.events {
+            if !events
+                .iter()
+                .any(|e| e.name == *event && e.emitted_by.iter().any(|t| t == spec.name))
+            {
+                violations.push(Violation::EventNotEmitted {
+                    transition: spec.name.to_string(),
+                    event: event.to_string(),
+                });
+            }
+        }
+        for tag in spec.callbacks {
+            if !messages
+                .sent_by(spec.name)
+                .any(|m| m.tag.as_deref() == Some(tag))
+            {
+                violations.push(Violation::CallbackNotSent {
+                    transition: spec.name.to_string(),
+                    tag: tag.to_string(),
+                });
+            }
+        }
+    }
+    ViolationList(violations)
+}
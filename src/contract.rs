@@ -1,8 +1,10 @@
 use std::{path::Path, str::FromStr};
 
-use crate::{run_scilla_fmt, Error, FieldList, TransitionList};
+use crate::{
+    ast::Module, parser::parse_module, Error, Field, FieldList, Transition, TransitionList,
+};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default)]
 /// The `Contract` struct represents a parsed contract in Rust, including its name, initialization
 /// parameters, fields, and transitions.
 pub struct Contract {
@@ -14,14 +16,47 @@ pub struct Contract {
     pub fields: FieldList,
     /// List of the contract's transitions.
     pub transitions: TransitionList,
+    /// List of the contract's procedures. Not available when the contract is parsed from an
+    /// S-expression.
+    pub procedures: TransitionList,
+    /// The syntax tree the contract was built from. Only available when the contract is parsed
+    /// from its Scilla source, see [`Contract::syntax_tree`].
+    pub module: Option<Module>,
+}
+
+impl PartialEq for Contract {
+    /// Two contracts are equal if they expose the same interface. Procedures and the syntax tree
+    /// are not compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.init_params == other.init_params
+            && self.fields == other.fields
+            && self.transitions == other.transitions
+    }
+}
+
+impl From<Module> for Contract {
+    fn from(module: Module) -> Self {
+        let contract = &module.contract;
+        Contract {
+            name: contract.name.clone(),
+            init_params: contract.params.iter().collect(),
+            fields: FieldList(contract.fields.iter().map(Field::from).collect()),
+            transitions: TransitionList(contract.transitions().map(Transition::from).collect()),
+            procedures: TransitionList(contract.procedures().map(Transition::from).collect()),
+            module: Some(module),
+        }
+    }
 }
 
 impl FromStr for Contract {
     type Err = Error;
 
-    /// Parse a Contract from a string slice
+    /// Parse a Contract from a string slice containing the S-expression generated by `scilla-fmt`.
+    /// The syntax tree is not available for contracts parsed this way.
     /// # Example
-    /// ```
+    /// `run_scilla_fmt` needs docker, so this example is not run.
+    /// ```no_run
     /// use std::{error::Error, path::PathBuf};
     /// use scilla_parser::{run_scilla_fmt, Contract, Field, FieldList, Transition, TransitionList, Type};
     /// let contract = run_scilla_fmt(&PathBuf::from("tests/contracts/chainid.scilla")).unwrap();
@@ -36,7 +71,8 @@ impl FromStr for Contract {
     ///         transitions: TransitionList(vec![Transition::new(
     ///             "EventChainID",
     ///             FieldList::default()
-    ///         )])
+    ///         )]),
+    ///         ..Default::default()
     ///     }
     /// );
     /// ```
@@ -52,11 +88,30 @@ impl FromStr for Contract {
             transitions,
             init_params,
             fields,
+            ..Default::default()
         })
     }
 }
 
 impl Contract {
+    /// Parse a contract from its Scilla source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::{Contract, Field, FieldList, Transition, TransitionList, Type};
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0 contract Counter () field count : Uint32 = Uint32 0 transition Reset () end",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(contract.name, "Counter");
+    /// assert_eq!(contract.fields, FieldList(vec![Field::new("count", Type::Uint32)]));
+    /// assert_eq!(contract.transitions, TransitionList(vec![Transition::new_without_param("Reset")]));
+    /// ```
+    pub fn from_source(source: &str) -> Result<Self, Error> {
+        Ok(parse_module(source)?.into())
+    }
+
     /// Parse a contract from a given path.
     ///
     /// # Examples
@@ -75,11 +130,31 @@ impl Contract {
     ///         transitions: TransitionList(vec![Transition::new(
     ///             "EventChainID",
     ///             FieldList::default()
-    ///         )])
+    ///         )]),
+    ///         ..Default::default()
     ///     }
     /// );
     /// ```
     pub fn from_path(contract_path: &Path) -> Result<Self, Error> {
-        run_scilla_fmt(contract_path)?.parse()
+        Self::from_source(&std::fs::read_to_string(contract_path)?)
+    }
+
+    /// The syntax tree the contract was built from. The analyses of the code of the contract, such
+    /// as [`Contract::events`], use it and fail with [`Error::NoSource`] when the contract was not
+    /// parsed from its Scilla source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::{Contract, Error};
+    /// let contract = Contract::from_contract_info_json(
+    ///     r#"{"vname": "Counter", "params": [], "fields": [], "transitions": []}"#,
+    /// )
+    /// .unwrap();
+    /// assert!(matches!(contract.syntax_tree(), Err(Error::NoSource)));
+    /// assert!(matches!(contract.events(), Err(Error::NoSource)));
+    /// ```
+    pub fn syntax_tree(&self) -> Result<&Module, Error> {
+        self.module.as_ref().ok_or(Error::NoSource)
     }
 }
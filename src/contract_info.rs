@@ -0,0 +1,89 @@
+//! Contracts built from a contract info JSON, the description of a contract interface used by
+//! wallets and explorers.
+
+use serde_json::Value as JsonValue;
+
+use crate::{parser::parse_type, Contract, Error, Field, FieldList, Transition, TransitionList};
+
+/// Parse a list of `{"vname": ..., "type": ...}` objects.
+fn params_from_info(params: &JsonValue) -> Result<FieldList, Error> {
+    let Some(params) = params.as_array() else {
+        return Ok(FieldList::default());
+    };
+    params
+        .iter()
+        .map(|p| {
+            let name = p["vname"]
+                .as_str()
+                .ok_or_else(|| Error::InvalidValue(format!("Missing vname in {p}")))?;
+            let ty = p["type"]
+                .as_str()
+                .ok_or_else(|| Error::InvalidValue(format!("Missing type in {p}")))?;
+            Ok(Field::new(name, parse_type(ty)?))
+        })
+        .collect::<Result<_, Error>>()
+        .map(FieldList)
+}
+
+/// Parse a list of `{"vname": ..., "params": [...]}` objects.
+fn components_from_info(components: &JsonValue) -> Result<TransitionList, Error> {
+    let Some(components) = components.as_array() else {
+        return Ok(TransitionList::default());
+    };
+    components
+        .iter()
+        .map(|c| {
+            let name = c["vname"]
+                .as_str()
+                .ok_or_else(|| Error::InvalidValue(format!("Missing vname in {c}")))?;
+            Ok(Transition::new(name, params_from_info(&c["params"])?))
+        })
+        .collect::<Result<_, Error>>()
+        .map(TransitionList)
+}
+
+impl Contract {
+    /// Build a contract from a contract info JSON. Both a `contract_info` object and an object
+    /// wrapping it in a `contract_info` key are accepted.
+    ///
+    /// The syntax tree is not available for contracts built this way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::{Contract, Field, FieldList, Type};
+    /// let contract = Contract::from_contract_info_json(
+    ///     r#"{
+    ///         "vname": "Counter",
+    ///         "params": [],
+    ///         "fields": [{"vname": "counts", "type": "Map (ByStr20) (Uint32)", "depth": 1}],
+    ///         "transitions": [{"vname": "Reset", "params": []}],
+    ///         "procedures": []
+    ///     }"#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     contract.fields,
+    ///     FieldList(vec![Field::new(
+    ///         "counts",
+    ///         Type::Map(Box::new(Type::ByStr(20)), Box::new(Type::Uint32))
+    ///     )])
+    /// );
+    /// ```
+    pub fn from_contract_info_json(json: &str) -> Result<Self, Error> {
+        let json: JsonValue = serde_json::from_str(json)?;
+        let info = json.get("contract_info").unwrap_or(&json);
+        let name = info["vname"]
+            .as_str()
+            .ok_or_else(|| Error::InvalidValue("Missing contract vname".to_string()))?;
+
+        Ok(Contract {
+            name: name.to_string(),
+            init_params: params_from_info(&info["params"])?,
+            fields: params_from_info(&info["fields"])?,
+            transitions: components_from_info(&info["transitions"])?,
+            procedures: components_from_info(&info["procedures"])?,
+            module: None,
+        })
+    }
+}
@@ -0,0 +1,368 @@
+//! Comparison of the interfaces of two versions of a contract.
+
+use std::fmt::Display;
+
+use serde_json::{json, Value as JsonValue};
+
+use crate::{printer::print_type, Contract, Field, FieldList, Transition, Type};
+
+/// The declarations a parameter or field belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scope {
+    InitParams,
+    Fields,
+    /// The parameters of a transition.
+    Transition(String),
+}
+
+impl Scope {
+    /// How the parameter or field `name` is referred to in reports.
+    fn subject(&self, name: &str) -> String {
+        match self {
+            Scope::InitParams => format!("immutable parameter `{name}`"),
+            Scope::Fields => format!("field `{name}`"),
+            Scope::Transition(transition) => format!("parameter `{name}` of `{transition}`"),
+        }
+    }
+}
+
+/// A change of the interface between two versions of a contract.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    TransitionAdded(String),
+    TransitionRemoved(String),
+    /// A transition removed while another one with the same parameters was added.
+    TransitionRenamed {
+        from: String,
+        to: String,
+    },
+    Added(Scope, Field),
+    Removed(Scope, Field),
+    TypeChanged {
+        scope: Scope,
+        name: String,
+        from: Type,
+        to: Type,
+    },
+    /// The same parameters or fields declared in another order.
+    Reordered {
+        scope: Scope,
+        from: Vec<String>,
+        to: Vec<String>,
+    },
+}
+
+impl Change {
+    /// Whether the change breaks the existing users of the contract: callers of its transitions,
+    /// deployment scripts providing its immutable parameters, and contracts or tools reading its
+    /// fields. Parameters are passed by name, so their order does not matter.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            Change::TransitionAdded(_) | Change::Reordered { .. } => false,
+            Change::Added(scope, _) => *scope != Scope::Fields,
+            Change::TransitionRemoved(_)
+            | Change::TransitionRenamed { .. }
+            | Change::Removed(..)
+            | Change::TypeChanged { .. } => true,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Change::TransitionAdded(_) => "transition_added",
+            Change::TransitionRemoved(_) => "transition_removed",
+            Change::TransitionRenamed { .. } => "transition_renamed",
+            Change::Added(..) => "added",
+            Change::Removed(..) => "removed",
+            Change::TypeChanged { .. } => "type_changed",
+            Change::Reordered { .. } => "reordered",
+        }
+    }
+
+    /// The change as a JSON object with its `kind`, whether it is `breaking`, a `description` and
+    /// the details of the change.
+    pub fn to_json(&self) -> JsonValue {
+        let mut json = json!({
+            "kind": self.kind(),
+            "breaking": self.is_breaking(),
+            "description": self.to_string(),
+        });
+        let scope_json = |scope: &Scope| match scope {
+            Scope::InitParams => json!({"scope": "init_params"}),
+            Scope::Fields => json!({"scope": "fields"}),
+            Scope::Transition(name) => json!({"scope": "transition", "transition": name}),
+        };
+        let details = match self {
+            Change::TransitionAdded(name) | Change::TransitionRemoved(name) => {
+                json!({"transition": name})
+            }
+            Change::TransitionRenamed { from, to } => json!({"from": from, "to": to}),
+            Change::Added(scope, field) | Change::Removed(scope, field) => {
+                let mut details = scope_json(scope);
+                details["name"] = json!(field.name);
+                details["type"] = json!(field.r#type.to_scilla_string());
+                details
+            }
+            Change::TypeChanged {
+                scope,
+                name,
+                from,
+                to,
+            } => {
+                let mut details = scope_json(scope);
+                details["name"] = json!(name);
+                details["from"] = json!(from.to_scilla_string());
+                details["to"] = json!(to.to_scilla_string());
+                details
+            }
+            Change::Reordered { scope, from, to } => {
+                let mut details = scope_json(scope);
+                details["from"] = json!(from);
+                details["to"] = json!(to);
+                details
+            }
+        };
+        for (key, value) in details.as_object().unwrap() {
+            json[key] = value.clone();
+        }
+        json
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::TransitionAdded(name) => write!(f, "transition `{name}` added"),
+            Change::TransitionRemoved(name) => write!(f, "transition `{name}` removed"),
+            Change::TransitionRenamed { from, to } => {
+                write!(f, "transition `{from}` renamed to `{to}`")
+            }
+            Change::Added(scope, field) | Change::Removed(scope, field) => {
+                let subject =
+                    scope.subject(&format!("{} : {}", field.name, print_type(&field.r#type)));
+                let verb = if let Change::Added(..) = self {
+                    "added"
+                } else {
+                    "removed"
+                };
+                write!(f, "{subject} {verb}")
+            }
+            Change::TypeChanged {
+                scope,
+                name,
+                from,
+                to,
+            } => write!(
+                f,
+                "type of {} changed from `{}` to `{}`",
+                scope.subject(name),
+                print_type(from),
+                print_type(to)
+            ),
+            Change::Reordered { scope, from, to } => {
+                let kind = match scope {
+                    Scope::InitParams => "immutable parameters".to_string(),
+                    Scope::Fields => "fields".to_string(),
+                    Scope::Transition(name) => format!("parameters of `{name}`"),
+                };
+                write!(
+                    f,
+                    "{kind} reordered from ({}) to ({})",
+                    from.join(", "),
+                    to.join(", ")
+                )
+            }
+        }
+    }
+}
+
+/// The changes of the interface of a contract, as returned by [`Contract::diff`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InterfaceDiff(pub Vec<Change>);
+
+impl std::ops::Deref for InterfaceDiff {
+    type Target = Vec<Change>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl InterfaceDiff {
+    /// Whether any change breaks the existing users of the contract.
+    pub fn is_breaking(&self) -> bool {
+        self.iter().any(Change::is_breaking)
+    }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.iter().filter(|c| c.is_breaking())
+    }
+
+    pub fn compatible(&self) -> impl Iterator<Item = &Change> {
+        self.iter().filter(|c| !c.is_breaking())
+    }
+
+    /// The report as a JSON object: `{"breaking": true, "changes": [...]}`.
+    pub fn to_json(&self) -> JsonValue {
+        json!({
+            "breaking": self.is_breaking(),
+            "changes": self.iter().map(Change::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+impl Display for InterfaceDiff {
+    /// A report listing the breaking changes, then the compatible ones.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No interface change.");
+        }
+        for (title, changes) in [
+            ("Breaking changes", self.breaking().collect::<Vec<_>>()),
+            ("Compatible changes", self.compatible().collect()),
+        ] {
+            if changes.is_empty() {
+                continue;
+            }
+            writeln!(f, "{title}:")?;
+            for change in changes {
+                writeln!(f, "  - {change}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The changes between two lists of parameters or fields, matched by name.
+fn diff_fields(scope: Scope, old: &FieldList, new: &FieldList, changes: &mut Vec<Change>) {
+    let find = |list: &FieldList, name: &str| list.iter().find(|f| f.name == name).cloned();
+    for field in old.iter() {
+        match find(new, &field.name) {
+            None => changes.push(Change::Removed(scope.clone(), field.clone())),
+            Some(new) if new.r#type != field.r#type => changes.push(Change::TypeChanged {
+                scope: scope.clone(),
+                name: field.name.clone(),
+                from: field.r#type.clone(),
+                to: new.r#type,
+            }),
+            Some(_) => {}
+        }
+    }
+    for field in new.iter() {
+        if find(old, &field.name).is_none() {
+            changes.push(Change::Added(scope.clone(), field.clone()));
+        }
+    }
+
+    // Compare the order of the names both lists share.
+    let names = |list: &FieldList, other: &FieldList| -> Vec<String> {
+        list.iter()
+            .filter(|f| other.iter().any(|o| o.name == f.name))
+            .map(|f| f.name.clone())
+            .collect()
+    };
+    let (from, to) = (names(old, new), names(new, old));
+    if from != to {
+        changes.push(Change::Reordered { scope, from, to });
+    }
+}
+
+impl Contract {
+    /// Compare the interface of the contract with the one of its new version `other`: its
+    /// transitions and their parameters, its immutable parameters and its fields. A removed
+    /// transition is reported as renamed if a single added transition has the same parameters, and
+    /// at least one of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::{Change, Contract};
+    /// let old = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Counter ()
+    ///     field count : Uint32 = Uint32 0
+    ///     transition Increase (by : Uint32) end",
+    /// )
+    /// .unwrap();
+    /// let new = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Counter ()
+    ///     field count : Uint32 = Uint32 0
+    ///     transition Increment (by : Uint32) end
+    ///     transition Reset () end",
+    /// )
+    /// .unwrap();
+    /// let diff = old.diff(&new);
+    /// assert!(diff.is_breaking());
+    /// assert_eq!(
+    ///     diff.to_string(),
+    ///     "Breaking changes:
+    ///   - transition `Increase` renamed to `Increment`
+    /// Compatible changes:
+    ///   - transition `Reset` added
+    /// "
+    /// );
+    /// ```
+    pub fn diff(&self, other: &Contract) -> InterfaceDiff {
+        let mut changes = vec![];
+        let find = |contract: &Contract, name: &str| -> bool {
+            contract.transitions.iter().any(|t| t.name == name)
+        };
+        let removed: Vec<&Transition> = self
+            .transitions
+            .iter()
+            .filter(|t| !find(other, &t.name))
+            .collect();
+        let added: Vec<&Transition> = other
+            .transitions
+            .iter()
+            .filter(|t| !find(self, &t.name))
+            .collect();
+
+        // Transitions without parameters, e.g. `Pause` and `Unpause`, tell nothing about a rename.
+        let same_params =
+            |a: &Transition, b: &Transition| !a.params.is_empty() && a.params == b.params;
+        let mut renamed = vec![];
+        for old in &removed {
+            let candidates: Vec<&&Transition> =
+                added.iter().filter(|new| same_params(old, new)).collect();
+            if let [new] = candidates.as_slice() {
+                if removed.iter().filter(|o| same_params(o, new)).count() == 1 {
+                    renamed.push((old.name.clone(), new.name.clone()));
+                }
+            }
+        }
+
+        for transition in self.transitions.iter() {
+            if let Some((_, to)) = renamed.iter().find(|(from, _)| *from == transition.name) {
+                changes.push(Change::TransitionRenamed {
+                    from: transition.name.clone(),
+                    to: to.clone(),
+                });
+            } else if let Some(new) = other.transitions.iter().find(|t| t.name == transition.name) {
+                diff_fields(
+                    Scope::Transition(transition.name.clone()),
+                    &transition.params,
+                    &new.params,
+                    &mut changes,
+                );
+            } else {
+                changes.push(Change::TransitionRemoved(transition.name.clone()));
+            }
+        }
+        for transition in added {
+            if !renamed.iter().any(|(_, to)| *to == transition.name) {
+                changes.push(Change::TransitionAdded(transition.name.clone()));
+            }
+        }
+
+        diff_fields(
+            Scope::InitParams,
+            &self.init_params,
+            &other.init_params,
+            &mut changes,
+        );
+        diff_fields(Scope::Fields, &self.fields, &other.fields, &mut changes);
+        InterfaceDiff(changes)
+    }
+}
@@ -0,0 +1,380 @@
+//! Generation of a documentation page per contract, in Markdown or as a static HTML page.
+
+use std::fmt::Write;
+
+use crate::{
+    printer::{print_type, PrettyPrinter},
+    Contract, Docs, FieldList, InferredField,
+};
+
+/// A block of the page, rendered to Markdown or HTML.
+enum Block {
+    Heading(usize, String),
+    /// Text where code is delimited by backticks and lines starting with `- ` are list items.
+    Text(String),
+    Table(&'static [&'static str], Vec<Vec<String>>),
+}
+
+fn code(text: &str) -> String {
+    format!("`{text}`")
+}
+
+/// `1 field` or `2 fields`.
+fn count(n: usize, noun: &str) -> String {
+    format!("{n} {noun}{}", if n == 1 { "" } else { "s" })
+}
+
+fn code_list<'a>(items: impl IntoIterator<Item = &'a String>) -> String {
+    items
+        .into_iter()
+        .map(|i| code(i))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn typed_params(params: &[InferredField]) -> String {
+    params
+        .iter()
+        .map(|p| match &p.r#type {
+            Some(ty) => code(&format!("{} : {}", p.name, print_type(ty))),
+            None => code(&p.name),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The description and tags of a declaration.
+fn docs_blocks(docs: Option<&Docs>, blocks: &mut Vec<Block>) {
+    let Some(docs) = docs else {
+        return;
+    };
+    for text in [
+        Some(&docs.description),
+        docs.notice.as_ref(),
+        docs.dev.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .filter(|text| !text.is_empty())
+    {
+        blocks.push(Block::Text(text.clone()));
+    }
+    for (tag, text) in &docs.tags {
+        blocks.push(Block::Heading(4, tag.clone()));
+        if !text.is_empty() {
+            blocks.push(Block::Text(text.clone()));
+        }
+    }
+}
+
+/// A table of parameters with their documentation from `docs`.
+fn params_table(params: &FieldList, docs: Option<&Docs>) -> Block {
+    let rows = params
+        .iter()
+        .map(|p| {
+            vec![
+                code(&p.name),
+                code(&print_type(&p.r#type)),
+                docs.and_then(|d| d.param(&p.name))
+                    .unwrap_or_default()
+                    .to_string(),
+            ]
+        })
+        .collect();
+    Block::Table(&["Name", "Type", "Description"], rows)
+}
+
+impl Contract {
+    fn page(&self) -> Vec<Block> {
+        let events = self.events().unwrap_or_default();
+        let messages = self.messages().unwrap_or_default();
+        let error_codes = self.error_codes().unwrap_or_default();
+        let docs = |name: &str| self.docs(name).ok().flatten();
+        let contract_docs = docs(&self.name);
+
+        let mut blocks = vec![Block::Heading(1, self.name.clone())];
+        docs_blocks(contract_docs, &mut blocks);
+        blocks.push(Block::Text(format!(
+            "{} has {}, {}, {} and {}.",
+            code(&self.name),
+            count(self.init_params.len(), "immutable parameter"),
+            count(self.fields.len(), "mutable field"),
+            count(self.transitions.len(), "transition"),
+            count(self.procedures.len(), "procedure")
+        )));
+
+        blocks.push(Block::Heading(2, "Immutable parameters".to_string()));
+        if self.init_params.is_empty() {
+            blocks.push(Block::Text("None.".to_string()));
+        } else {
+            blocks.push(params_table(&self.init_params, contract_docs));
+        }
+
+        blocks.push(Block::Heading(2, "Mutable fields".to_string()));
+        if self.fields.is_empty() {
+            blocks.push(Block::Text("None.".to_string()));
+        } else {
+            let printer = PrettyPrinter::default();
+            let rows = self
+                .fields
+                .iter()
+                .map(|field| {
+                    let init = self
+                        .module
+                        .iter()
+                        .flat_map(|m| &m.contract.fields)
+                        .find(|f| f.name == field.name)
+                        .map(|f| {
+                            let init = printer.print_expr(&f.init);
+                            code(&init.split_whitespace().collect::<Vec<_>>().join(" "))
+                        })
+                        .unwrap_or_default();
+                    let docs = docs(&field.name);
+                    vec![
+                        code(&field.name),
+                        code(&print_type(&field.r#type)),
+                        init,
+                        docs.map(|d| d.description.clone()).unwrap_or_default(),
+                    ]
+                })
+                .collect();
+            blocks.push(Block::Table(
+                &["Name", "Type", "Initial value", "Description"],
+                rows,
+            ));
+        }
+
+        for (title, components) in [
+            ("Transitions", &self.transitions),
+            ("Procedures", &self.procedures),
+        ] {
+            if components.is_empty() {
+                continue;
+            }
+            blocks.push(Block::Heading(2, title.to_string()));
+            for component in components.iter() {
+                let name = &component.name;
+                let docs = docs(name);
+                blocks.push(Block::Heading(3, name.clone()));
+                docs_blocks(docs, &mut blocks);
+
+                blocks.push(Block::Heading(4, "Parameters".to_string()));
+                if component.params.is_empty() {
+                    blocks.push(Block::Text("None.".to_string()));
+                } else {
+                    blocks.push(params_table(&component.params, docs));
+                }
+
+                let emitted: Vec<String> = events
+                    .iter()
+                    .filter(|e| e.emitted_by.contains(name))
+                    .map(|e| format!("- {} ({})", code(&e.name), typed_params(&e.params)))
+                    .collect();
+                if !emitted.is_empty() {
+                    blocks.push(Block::Heading(4, "Events".to_string()));
+                    blocks.push(Block::Text(emitted.join("\n")));
+                }
+
+                let sent: Vec<String> = messages
+                    .sent_by(name)
+                    .map(|m| {
+                        let tag = m
+                            .tag
+                            .as_deref()
+                            .map(code)
+                            .unwrap_or("A message".to_string());
+                        let mut line = format!("- {tag} to {}", code(&m.recipient));
+                        if !m.amount.is_empty() {
+                            let _ = write!(line, " with {}", code(&m.amount));
+                        }
+                        line
+                    })
+                    .collect();
+                if !sent.is_empty() {
+                    blocks.push(Block::Heading(4, "Messages".to_string()));
+                    blocks.push(Block::Text(sent.join("\n")));
+                }
+
+                let thrown: Vec<String> = error_codes
+                    .thrown_by(name)
+                    .map(|e| format!("- {} ({})", code(&e.name), e.code))
+                    .collect();
+                if !thrown.is_empty() {
+                    blocks.push(Block::Heading(4, "Error codes".to_string()));
+                    blocks.push(Block::Text(thrown.join("\n")));
+                }
+            }
+        }
+
+        if !events.is_empty() {
+            blocks.push(Block::Heading(2, "Events".to_string()));
+            let rows = events
+                .iter()
+                .map(|e| {
+                    vec![
+                        code(&e.name),
+                        typed_params(&e.params),
+                        code_list(&e.emitted_by),
+                    ]
+                })
+                .collect();
+            blocks.push(Block::Table(&["Name", "Parameters", "Emitted by"], rows));
+        }
+
+        if !error_codes.is_empty() {
+            blocks.push(Block::Heading(2, "Error codes".to_string()));
+            let rows = error_codes
+                .iter()
+                .map(|e| vec![code(&e.name), e.code.to_string(), code_list(&e.thrown_by)])
+                .collect();
+            blocks.push(Block::Table(&["Name", "Code", "Thrown by"], rows));
+        }
+        blocks
+    }
+
+    /// Generate a Markdown page documenting the contract: its documentation comments, immutable
+    /// parameters, mutable fields with their initial values, transitions and procedures with their
+    /// parameters, the events they emit, the messages they send and the error codes they throw.
+    ///
+    /// Events, messages, error codes and documentation are only listed when the contract is
+    /// parsed from its Scilla source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Counter ()
+    ///     field count : Uint32 = Uint32 0
+    ///     (* @notice: Reset the counter. *)
+    ///     transition Reset () end",
+    /// )
+    /// .unwrap();
+    /// let page = contract.markdown();
+    /// assert!(page.contains("| `count` | `Uint32` | `Uint32 0` |  |"));
+    /// assert!(page.contains("### Reset\n\nReset the counter."));
+    /// ```
+    pub fn markdown(&self) -> String {
+        let mut out = String::new();
+        for block in self.page() {
+            match block {
+                Block::Heading(level, text) => {
+                    let _ = writeln!(out, "{} {text}\n", "#".repeat(level));
+                }
+                Block::Text(text) => {
+                    let _ = writeln!(out, "{text}\n");
+                }
+                Block::Table(headers, rows) => {
+                    let _ = writeln!(out, "| {} |", headers.join(" | "));
+                    let _ = writeln!(out, "|{}", " --- |".repeat(headers.len()));
+                    for row in rows {
+                        let cells: Vec<String> = row
+                            .iter()
+                            .map(|c| c.replace('|', "\\|").replace('\n', "<br>"))
+                            .collect();
+                        let _ = writeln!(out, "| {} |", cells.join(" | "));
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+        out.pop();
+        out
+    }
+
+    /// Generate the page of [`markdown`](Contract::markdown) as a standalone HTML document.
+    pub fn html(&self) -> String {
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n",
+            escape(&self.name)
+        );
+        for block in self.page() {
+            match block {
+                Block::Heading(level, text) => {
+                    let _ = writeln!(out, "<h{level}>{}</h{level}>", inline(&text));
+                }
+                Block::Text(text) => {
+                    let mut in_list = false;
+                    let mut paragraph: Vec<String> = vec![];
+                    for line in text.lines() {
+                        let item = line.strip_prefix("- ");
+                        if item.is_some() && !paragraph.is_empty() {
+                            let _ = writeln!(out, "<p>{}</p>", paragraph.join("\n"));
+                            paragraph.clear();
+                        }
+                        match (item, in_list) {
+                            (Some(_), false) => out.push_str("<ul>\n"),
+                            (None, true) => out.push_str("</ul>\n"),
+                            _ => {}
+                        }
+                        in_list = item.is_some();
+                        match item {
+                            Some(item) => {
+                                let _ = writeln!(out, "<li>{}</li>", inline(item));
+                            }
+                            None => paragraph.push(inline(line)),
+                        }
+                    }
+                    if in_list {
+                        out.push_str("</ul>\n");
+                    }
+                    if !paragraph.is_empty() {
+                        let _ = writeln!(out, "<p>{}</p>", paragraph.join("\n"));
+                    }
+                }
+                Block::Table(headers, rows) => {
+                    out.push_str("<table>\n<thead>\n<tr>");
+                    for header in headers {
+                        let _ = write!(out, "<th>{header}</th>");
+                    }
+                    out.push_str("</tr>\n</thead>\n<tbody>\n");
+                    for row in rows {
+                        out.push_str("<tr>");
+                        for cell in row {
+                            let _ = write!(out, "<td>{}</td>", inline(&cell).replace('\n', "<br>"));
+                        }
+                        out.push_str("</tr>\n");
+                    }
+                    out.push_str("</tbody>\n</table>\n");
+                }
+            }
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escape text for HTML and turn backtick delimited spans into `<code>` elements.
+fn inline(text: &str) -> String {
+    text.split('`')
+        .enumerate()
+        .map(|(i, part)| {
+            if i % 2 == 1 {
+                format!("<code>{}</code>", escape(part))
+            } else {
+                escape(part)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline() {
+        assert_eq!(
+            inline("Returns `a < b` & more"),
+            "Returns <code>a &lt; b</code> &amp; more"
+        );
+    }
+}
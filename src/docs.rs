@@ -0,0 +1,214 @@
+//! Documentation comments of library entries, fields, transitions and procedures.
+
+use crate::{ast::LibEntry, Contract, Error};
+
+/// The comments preceding a declaration, with their `@tag`s parsed.
+///
+/// A line starting with `@param name:` or `@param: name -` documents a parameter, `@notice:` and `@dev:` the
+/// declaration itself. Lines without a tag continue the previous tag, or the description if no tag
+/// was seen yet.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Docs {
+    /// The text before the first tag.
+    pub description: String,
+    /// The `@notice` text, describing the declaration to its users.
+    pub notice: Option<String>,
+    /// The `@dev` text, describing the declaration to developers.
+    pub dev: Option<String>,
+    /// The `@param` texts by parameter name, in order.
+    pub params: Vec<(String, String)>,
+    /// Other tags, e.g. `@Requirements`, with their text.
+    pub tags: Vec<(String, String)>,
+    /// The comments as written, including their delimiters.
+    pub comments: Vec<String>,
+}
+
+/// Where the text of the current line goes.
+enum Target {
+    Description,
+    Notice,
+    Dev,
+    Param,
+    Tag,
+}
+
+fn append(text: &mut String, line: &str) {
+    if !text.is_empty() && !line.is_empty() {
+        text.push('\n');
+    }
+    text.push_str(line);
+}
+
+impl Docs {
+    /// Parse comments given with their `(*` and `*)` delimiters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Docs;
+    /// let docs = Docs::from_comments(vec![
+    ///     "(* @dev: Mint new tokens. *)".to_string(),
+    ///     "(* @param amount: Number of tokens to be minted. *)".to_string(),
+    /// ]);
+    /// assert_eq!(docs.dev.as_deref(), Some("Mint new tokens."));
+    /// assert_eq!(docs.param("amount"), Some("Number of tokens to be minted."));
+    /// ```
+    pub fn from_comments(comments: Vec<String>) -> Self {
+        let mut docs = Docs::default();
+        let mut target = Target::Description;
+        let lines: Vec<String> = comments
+            .iter()
+            .flat_map(|comment| {
+                let inner = comment
+                    .strip_prefix("(*")
+                    .and_then(|c| c.strip_suffix("*)"))
+                    .unwrap_or(comment);
+                inner
+                    .lines()
+                    .map(|line| line.trim().to_string())
+                    .collect::<Vec<_>>()
+            })
+            // Skip blank lines and banners made of stars.
+            .filter(|line| !line.chars().all(|c| c == '*'))
+            .collect();
+
+        for line in &lines {
+            let Some(tagged) = line.strip_prefix('@') else {
+                let text = match target {
+                    Target::Description => &mut docs.description,
+                    Target::Notice => docs.notice.get_or_insert_with(String::new),
+                    Target::Dev => docs.dev.get_or_insert_with(String::new),
+                    Target::Param => &mut docs.params.last_mut().unwrap().1,
+                    Target::Tag => &mut docs.tags.last_mut().unwrap().1,
+                };
+                append(text, line);
+                continue;
+            };
+            let end = tagged
+                .find(|c: char| c.is_whitespace() || c == ':')
+                .unwrap_or(tagged.len());
+            let (tag, rest) = tagged.split_at(end);
+            let rest = rest.trim_start().trim_start_matches(':').trim_start();
+            match tag {
+                "notice" => {
+                    target = Target::Notice;
+                    append(docs.notice.get_or_insert_with(String::new), rest);
+                }
+                "dev" => {
+                    target = Target::Dev;
+                    append(docs.dev.get_or_insert_with(String::new), rest);
+                }
+                "param" => {
+                    target = Target::Param;
+                    let end = rest
+                        .find(|c: char| c.is_whitespace() || c == ':')
+                        .unwrap_or(rest.len());
+                    let (name, text) = rest.split_at(end);
+                    // Both `@param name: text` and `@param: name - text` are used.
+                    let text = text
+                        .trim_start()
+                        .trim_start_matches([':', '-'])
+                        .trim_start();
+                    docs.params.push((name.to_string(), text.to_string()));
+                }
+                _ => {
+                    target = Target::Tag;
+                    docs.tags.push((tag.to_string(), rest.to_string()));
+                }
+            }
+        }
+        docs.comments = comments;
+        docs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.comments.is_empty()
+    }
+
+    /// The documentation of the parameter `name`.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, text)| text.as_str())
+    }
+
+    /// The documentation of the tag `name`, e.g. `Requirements`.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, text)| text.as_str())
+    }
+}
+
+impl Contract {
+    /// The documentation of the contract or of its library entry, field, transition or procedure
+    /// named `name`, `None` if it has no documentation comment or there is no such entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Counter ()
+    ///     (* @notice: Reset the counter. *)
+    ///     transition Reset () end",
+    /// )
+    /// .unwrap();
+    /// let docs = contract.docs("Reset").unwrap().unwrap();
+    /// assert_eq!(docs.notice.as_deref(), Some("Reset the counter."));
+    /// ```
+    pub fn docs(&self, name: &str) -> Result<Option<&Docs>, Error> {
+        let module = self.syntax_tree()?;
+        let contract = &module.contract;
+        let docs = if contract.name == name {
+            Some(&contract.docs)
+        } else if let Some(component) = contract.component(name) {
+            Some(&component.docs)
+        } else if let Some(field) = contract.fields.iter().find(|f| f.name == name) {
+            Some(&field.docs)
+        } else {
+            module
+                .library
+                .iter()
+                .flat_map(|lib| &lib.entries)
+                .find(|e| e.name() == name)
+                .map(LibEntry::docs)
+        };
+        Ok(docs.filter(|docs| !docs.is_empty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags() {
+        let docs = Docs::from_comments(vec![
+            "(* Transfer tokens. *)".to_string(),
+            "(***************)".to_string(),
+            "(* @dev: Moves tokens.      *)".to_string(),
+            "(* @dev: Balance of _sender will decrease. *)".to_string(),
+            "(* @param to  Address of the recipient *)".to_string(),
+            "(* @param: amount - Amount of tokens *)".to_string(),
+            "(* @Requirements:\n   - `to` must not be the zero address.\n   - `amount` must be positive. *)"
+                .to_string(),
+        ]);
+        assert_eq!(docs.description, "Transfer tokens.");
+        assert_eq!(
+            docs.dev.as_deref(),
+            Some("Moves tokens.\nBalance of _sender will decrease.")
+        );
+        assert_eq!(docs.notice, None);
+        assert_eq!(docs.param("to"), Some("Address of the recipient"));
+        assert_eq!(
+            docs.tag("Requirements"),
+            Some("- `to` must not be the zero address.\n- `amount` must be positive.")
+        );
+        assert_eq!(docs.param("amount"), Some("Amount of tokens"));
+        assert_eq!(docs.comments.len(), 7);
+    }
+}
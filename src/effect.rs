@@ -0,0 +1,148 @@
+//! Field effects of transitions and procedures: the fields each one reads, writes, updates and
+//! deletes from, including through the procedures it calls.
+
+use std::fmt::Display;
+
+use crate::{ast::StmtKind, Contract, Error};
+
+/// The fields a transition or procedure accesses, either directly or through the procedures it
+/// calls. Each field is listed once.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Effects {
+    /// Name of the transition or procedure.
+    pub name: String,
+    /// Fields read with `x <- f`, `x <- m[k]` or `x <- exists m[k]`.
+    pub reads: Vec<String>,
+    /// Fields written with `f := x`.
+    pub writes: Vec<String>,
+    /// Maps updated with `m[k] := v`.
+    pub map_updates: Vec<String>,
+    /// Maps whose entries are deleted with `delete m[k]`.
+    pub deletes: Vec<String>,
+}
+
+fn push(fields: &mut Vec<String>, field: &str) {
+    if !fields.iter().any(|f| f == field) {
+        fields.push(field.to_string());
+    }
+}
+
+impl Effects {
+    /// Whether the component changes a field.
+    pub fn modifies(&self, field: &str) -> bool {
+        [&self.writes, &self.map_updates, &self.deletes]
+            .into_iter()
+            .flatten()
+            .any(|f| f == field)
+    }
+
+    /// Whether the component does not change any field.
+    pub fn is_read_only(&self) -> bool {
+        self.writes.is_empty() && self.map_updates.is_empty() && self.deletes.is_empty()
+    }
+}
+
+impl Display for Effects {
+    /// `Transfer: reads balances; updates balances`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let groups: Vec<String> = [
+            ("reads", &self.reads),
+            ("writes", &self.writes),
+            ("updates", &self.map_updates),
+            ("deletes from", &self.deletes),
+        ]
+        .into_iter()
+        .filter(|(_, fields)| !fields.is_empty())
+        .map(|(verb, fields)| format!("{verb} {}", fields.join(", ")))
+        .collect();
+        if groups.is_empty() {
+            write!(f, "{}: no field access", self.name)
+        } else {
+            write!(f, "{}: {}", self.name, groups.join("; "))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EffectsList(pub Vec<Effects>);
+
+impl std::ops::Deref for EffectsList {
+    type Target = Vec<Effects>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl EffectsList {
+    /// The effects of the transition or procedure `name`.
+    pub fn of(&self, name: &str) -> Option<&Effects> {
+        self.iter().find(|e| e.name == name)
+    }
+
+    /// Transitions and procedures that change the field `field`.
+    pub fn modifying<'a>(&'a self, field: &'a str) -> impl Iterator<Item = &'a Effects> {
+        self.iter().filter(move |e| e.modifies(field))
+    }
+}
+
+impl Contract {
+    /// Collect the fields read, written, updated and deleted from by each transition and
+    /// procedure, following the procedures they call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Counter ()
+    ///     field count : Uint32 = Uint32 0
+    ///     field counts : Map ByStr20 Uint32 = Emp ByStr20 Uint32
+    ///     procedure Save (c : Uint32)
+    ///       counts[_sender] := c
+    ///     end
+    ///     transition Increase ()
+    ///       c <- count;
+    ///       one = Uint32 1;
+    ///       new_count = builtin add c one;
+    ///       count := new_count;
+    ///       Save new_count
+    ///     end",
+    /// )
+    /// .unwrap();
+    /// let effects = contract.effects().unwrap();
+    /// assert_eq!(
+    ///     effects.of("Increase").unwrap().to_string(),
+    ///     "Increase: reads count; writes count; updates counts"
+    /// );
+    /// ```
+    pub fn effects(&self) -> Result<EffectsList, Error> {
+        let module = self.syntax_tree()?;
+        let contract = &module.contract;
+        let effects = contract
+            .components
+            .iter()
+            .map(|component| {
+                let mut effects = Effects {
+                    name: component.name.clone(),
+                    ..Default::default()
+                };
+                for reached in contract.reachable_from(component) {
+                    for stmt in &reached.body {
+                        stmt.walk(&mut |stmt| match &stmt.kind {
+                            StmtKind::Load { field, .. } => push(&mut effects.reads, field),
+                            StmtKind::MapGet { map, .. } => push(&mut effects.reads, map),
+                            StmtKind::Store { field, .. } => push(&mut effects.writes, field),
+                            StmtKind::MapUpdate { map, .. } => push(&mut effects.map_updates, map),
+                            StmtKind::MapDelete { map, .. } => push(&mut effects.deletes, map),
+                            _ => {}
+                        });
+                    }
+                }
+                effects
+            })
+            .collect();
+        Ok(EffectsList(effects))
+    }
+}
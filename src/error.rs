@@ -2,6 +2,8 @@ use std::string::FromUtf8Error;
 
 use thiserror::Error as ThisError;
 
+use crate::Value;
+
 #[derive(Debug, ThisError)]
 pub enum Error {
     #[error("The requested entry {0} does not exist in the given S-expression")]
@@ -10,12 +12,37 @@ pub enum Error {
     #[error("Comptype is not transition. It's {0}")]
     CompTypeIsNotTransition(String),
 
+    #[error("Syntax error at line {line}, column {column}: {message}")]
+    SyntaxError {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    /// The contract was built from an S-expression or a contract info JSON, so its code cannot be
+    /// analysed.
+    #[error("The contract was not parsed from its Scilla source")]
+    NoSource,
+
+    #[error("Invalid value: {0}")]
+    InvalidValue(String),
+
+    #[error("Runtime error: {0}")]
+    RuntimeError(String),
+
+    /// A transition threw, with the value given to `throw` if any.
+    #[error("Exception thrown{}", .0.as_ref().map(|e| format!(": {e}")).unwrap_or_default())]
+    Exception(Option<Value>),
+
     #[error(transparent)]
     IoError(#[from] std::io::Error),
 
     #[error(transparent)]
     LexprParseError(#[from] lexpr::parse::Error),
 
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+
     #[error(transparent)]
     FromUtf8Error(#[from] FromUtf8Error),
 }
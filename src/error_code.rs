@@ -0,0 +1,148 @@
+use crate::{
+    ast::{ExprKind, LibEntry, Literal, Pattern, StmtKind},
+    infer::Context,
+    Contract, Error,
+};
+
+/// An error code of a contract, declared as a constructor of a library ADT mapped to an integer,
+/// e.g. `| CodeNotOwner => Int32 -4` in the `make_error` function of ZRC-2.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorCode {
+    /// Name of the constructor.
+    pub name: String,
+    pub code: i64,
+    /// Transitions and procedures that can throw the error, either directly or by calling a
+    /// procedure that throws it.
+    pub thrown_by: Vec<String>,
+}
+
+impl ErrorCode {
+    pub fn new(name: &str, code: i64, thrown_by: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            code,
+            thrown_by: thrown_by.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ErrorCodeList(pub Vec<ErrorCode>);
+
+impl std::ops::Deref for ErrorCodeList {
+    type Target = Vec<ErrorCode>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl ErrorCodeList {
+    /// Error codes the component `name` can throw.
+    pub fn thrown_by<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ErrorCode> {
+        self.iter()
+            .filter(move |e| e.thrown_by.iter().any(|t| t == name))
+    }
+}
+
+impl Contract {
+    /// Collect the error codes of the contract from the library `match` expressions mapping the
+    /// constructors of an ADT to integer literals, together with the transitions and procedures
+    /// using these constructors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::{Contract, ErrorCode};
+    /// let contract = Contract::from_source(
+    ///     r#"scilla_version 0
+    ///     library Token
+    ///     type Error = | CodeNotOwner
+    ///     let make_error = fun (e : Error) =>
+    ///       let code = match e with | CodeNotOwner => Int32 -1 end in
+    ///       {_exception : "Error"; code : code}
+    ///     contract Token (owner : ByStr20)
+    ///     procedure RequireOwner ()
+    ///       is_owner = builtin eq owner _sender;
+    ///       match is_owner with
+    ///       | True =>
+    ///       | False => e = CodeNotOwner; err = make_error e; throw err
+    ///       end
+    ///     end
+    ///     transition Pause () RequireOwner end"#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     contract.error_codes().unwrap()[0],
+    ///     ErrorCode::new("CodeNotOwner", -1, &["RequireOwner", "Pause"])
+    /// );
+    /// ```
+    pub fn error_codes(&self) -> Result<ErrorCodeList, Error> {
+        let module = self.syntax_tree()?;
+        let entries = module.library.iter().flat_map(|lib| &lib.entries);
+        let constructors: Vec<&str> = entries
+            .clone()
+            .filter_map(|entry| match entry {
+                LibEntry::Type { constructors, .. } => Some(constructors),
+                LibEntry::Let { .. } => None,
+            })
+            .flatten()
+            .map(|ctr| ctr.name.as_str())
+            .collect();
+
+        let mut codes: Vec<ErrorCode> = vec![];
+        for entry in entries {
+            let LibEntry::Let { expr, .. } = entry else {
+                continue;
+            };
+            expr.walk(&mut |expr| {
+                let ExprKind::Match { arms, .. } = &expr.kind else {
+                    return;
+                };
+                let mapped: Option<Vec<(&String, i64)>> = arms
+                    .iter()
+                    .map(|(pattern, value)| match (pattern, &value.kind) {
+                        (
+                            Pattern::Constructor(name, args),
+                            ExprKind::Literal(Literal::Int(_, code)),
+                        ) if args.is_empty() && constructors.contains(&name.as_str()) => {
+                            Some((name, code.parse().ok()?))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                for (name, code) in mapped.unwrap_or_default() {
+                    if !codes.iter().any(|c| c.name == *name) {
+                        codes.push(ErrorCode {
+                            name: name.clone(),
+                            code,
+                            thrown_by: vec![],
+                        });
+                    }
+                }
+            });
+        }
+
+        let context = Context::new(module);
+        for code in &mut codes {
+            let mut using = vec![];
+            for component in &module.contract.components {
+                for stmt in &component.body {
+                    stmt.walk(&mut |stmt| {
+                        let StmtKind::Bind { expr, .. } = &stmt.kind else {
+                            return;
+                        };
+                        expr.walk(&mut |expr| match &expr.kind {
+                            ExprKind::Constructor { name, .. } if *name == code.name => {
+                                using.push(component.name.clone())
+                            }
+                            _ => {}
+                        });
+                    });
+                }
+            }
+            code.thrown_by = context.reaching(&using);
+        }
+        Ok(ErrorCodeList(codes))
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{infer::Context, Contract, Error, InferredField};
+
+/// An event a contract can emit, e.g. `{_eventname : "TransferSuccess"; sender : _sender}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub name: String,
+    pub params: Vec<InferredField>,
+    /// Transitions and procedures that can emit the event, either directly or by calling a
+    /// procedure that emits it.
+    pub emitted_by: Vec<String>,
+}
+
+impl Event {
+    pub fn new(name: &str, params: Vec<InferredField>, emitted_by: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            params,
+            emitted_by: emitted_by.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EventList(pub Vec<Event>);
+
+impl std::ops::Deref for EventList {
+    type Target = Vec<Event>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Contract {
+    /// Collect the events constructed by the transitions and procedures of the contract.
+    ///
+    /// Events sharing a name but not their parameters are listed separately. Events whose
+    /// `_eventname` is not a string literal are not listed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::{Contract, Event, InferredField, Type};
+    /// let contract = Contract::from_source(
+    ///     r#"scilla_version 0
+    ///     contract Counter ()
+    ///     field count : Uint32 = Uint32 0
+    ///     transition Increase (by : Uint32)
+    ///       c <- count;
+    ///       new_count = builtin add c by;
+    ///       count := new_count;
+    ///       e = {_eventname : "Increased"; count : new_count};
+    ///       event e
+    ///     end"#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     contract.events().unwrap()[0],
+    ///     Event::new(
+    ///         "Increased",
+    ///         vec![InferredField::new("count", Type::Uint32)],
+    ///         &["Increase"]
+    ///     )
+    /// );
+    /// ```
+    pub fn events(&self) -> Result<EventList, Error> {
+        let module = self.syntax_tree()?;
+        let context = Context::new(module);
+
+        let mut events: Vec<Event> = vec![];
+        for construction in context.constructions() {
+            let Some(name) = construction.string_entry("_eventname") else {
+                continue;
+            };
+            let params = construction.params(&["_eventname"]);
+
+            let index = match events
+                .iter()
+                .position(|e| e.name == name && e.params == params)
+            {
+                Some(index) => index,
+                None => {
+                    events.push(Event::new(name, params, &[]));
+                    events.len() - 1
+                }
+            };
+            let emitter = construction.component;
+            events[index].emitted_by.push(emitter.name.clone());
+        }
+
+        // Components calling an emitting procedure can emit the event as well.
+        for event in &mut events {
+            event.emitted_by = context.reaching(&event.emitted_by);
+        }
+        Ok(EventList(events))
+    }
+}
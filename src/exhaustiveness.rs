@@ -0,0 +1,288 @@
+//! Exhaustiveness and redundancy of pattern matches, following the usefulness algorithm of
+//! Maranget's "Warnings for pattern matching".
+
+use std::fmt::Display;
+
+use crate::{
+    ast::{Pattern, Span},
+    printer::print_pattern,
+    type_check, Contract, Error,
+};
+
+/// A problem with the arms of a `match` expression or statement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchIssue {
+    /// No arm matches the values described by the `missing` patterns.
+    NonExhaustive {
+        scrutinee: String,
+        missing: Vec<Pattern>,
+        span: Span,
+    },
+    /// The arm is never used because the arms before it match all of its values.
+    UnreachableArm {
+        scrutinee: String,
+        arm: Pattern,
+        span: Span,
+    },
+}
+
+impl MatchIssue {
+    /// The location of the match.
+    pub fn span(&self) -> Span {
+        match self {
+            MatchIssue::NonExhaustive { span, .. } | MatchIssue::UnreachableArm { span, .. } => {
+                *span
+            }
+        }
+    }
+}
+
+impl Display for MatchIssue {
+    /// `match on `c` is not exhaustive, missing Green, Some _` or
+    /// `arm `Some _` of the match on `c` is unreachable`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchIssue::NonExhaustive {
+                scrutinee, missing, ..
+            } => {
+                let missing: Vec<String> = missing.iter().map(print_pattern).collect();
+                write!(
+                    f,
+                    "match on `{scrutinee}` is not exhaustive, missing {}",
+                    missing.join(", ")
+                )
+            }
+            MatchIssue::UnreachableArm { scrutinee, arm, .. } => write!(
+                f,
+                "arm `{}` of the match on `{scrutinee}` is unreachable",
+                print_pattern(arm)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MatchIssueList(pub Vec<MatchIssue>);
+
+impl std::ops::Deref for MatchIssueList {
+    type Target = Vec<MatchIssue>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl MatchIssueList {
+    pub fn non_exhaustive(&self) -> impl Iterator<Item = &MatchIssue> {
+        self.iter()
+            .filter(|i| matches!(i, MatchIssue::NonExhaustive { .. }))
+    }
+
+    pub fn unreachable_arms(&self) -> impl Iterator<Item = &MatchIssue> {
+        self.iter()
+            .filter(|i| matches!(i, MatchIssue::UnreachableArm { .. }))
+    }
+}
+
+/// The constructors of the types of the values being matched.
+pub(crate) trait Constructors<T> {
+    /// The constructors of `ty` and the types of their arguments, or `None` if its values are
+    /// not built with constructors, e.g. integers.
+    fn constructors(&mut self, ty: &T) -> Option<Vec<(String, Vec<T>)>>;
+}
+
+static WILDCARD: Pattern = Pattern::Wildcard;
+
+type Row<'p> = Vec<&'p Pattern>;
+
+/// Names of the constructors at the head of the rows.
+fn heads<'p>(rows: &[Row<'p>]) -> Vec<&'p str> {
+    rows.iter()
+        .filter_map(|row| match row[0] {
+            Pattern::Constructor(name, _) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The rows matching the constructor `name` with `arity` arguments, with its arguments in place
+/// of their first pattern.
+fn specialize<'p>(rows: &[Row<'p>], name: &str, arity: usize) -> Vec<Row<'p>> {
+    rows.iter()
+        .filter_map(|row| match row[0] {
+            Pattern::Constructor(n, args) if n == name && args.len() == arity => {
+                Some(args.iter().chain(row[1..].iter().copied()).collect())
+            }
+            Pattern::Constructor(..) => None,
+            _ => Some(
+                std::iter::repeat_n(&WILDCARD, arity)
+                    .chain(row[1..].iter().copied())
+                    .collect(),
+            ),
+        })
+        .collect()
+}
+
+/// The rows matching any value with their first pattern, without it.
+fn default<'p>(rows: &[Row<'p>]) -> Vec<Row<'p>> {
+    rows.iter()
+        .filter(|row| !matches!(row[0], Pattern::Constructor(..)))
+        .map(|row| row[1..].to_vec())
+        .collect()
+}
+
+/// The constructors of `ty` if all of them appear in `heads`.
+fn complete<T>(
+    ctx: &mut impl Constructors<T>,
+    ty: &T,
+    heads: &[&str],
+) -> Option<Vec<(String, Vec<T>)>> {
+    let ctrs = ctx.constructors(ty)?;
+    let complete = !heads.is_empty() && ctrs.iter().all(|(name, _)| heads.contains(&&**name));
+    complete.then_some(ctrs)
+}
+
+/// Sequences of patterns matching values of the types `tys` that no row matches.
+fn missing<T: Clone>(ctx: &mut impl Constructors<T>, rows: &[Row], tys: &[T]) -> Vec<Vec<Pattern>> {
+    let Some((ty, rest)) = tys.split_first() else {
+        return if rows.is_empty() {
+            vec![vec![]]
+        } else {
+            vec![]
+        };
+    };
+    let heads = heads(rows);
+    // Name the constructors of the missing values, unless no arm uses any constructor.
+    let ctrs = match ctx.constructors(ty) {
+        Some(ctrs) if !heads.is_empty() => ctrs,
+        _ => {
+            return missing(ctx, &default(rows), rest)
+                .into_iter()
+                .map(|w| std::iter::once(Pattern::Wildcard).chain(w).collect())
+                .collect()
+        }
+    };
+    let mut found = vec![];
+    for (name, args) in ctrs {
+        let arity = args.len();
+        let tys = [args, rest.to_vec()].concat();
+        for mut witness in missing(ctx, &specialize(rows, &name, arity), &tys) {
+            let rest = witness.split_off(arity);
+            found.push(
+                std::iter::once(Pattern::Constructor(name.clone(), witness))
+                    .chain(rest)
+                    .collect(),
+            );
+        }
+    }
+    found
+}
+
+/// Whether some values of the types `tys` are matched by `patterns` but by no row.
+fn useful<T: Clone>(
+    ctx: &mut impl Constructors<T>,
+    rows: &[Row],
+    patterns: &[&Pattern],
+    tys: &[T],
+) -> bool {
+    let (Some((first, patterns)), Some((ty, rest))) = (patterns.split_first(), tys.split_first())
+    else {
+        return rows.is_empty();
+    };
+    match first {
+        Pattern::Constructor(name, args) => {
+            let Some(ctr_args) = ctx
+                .constructors(ty)
+                .and_then(|ctrs| ctrs.into_iter().find(|(n, _)| n == name))
+                .map(|(_, args)| args)
+                .filter(|ctr_args| ctr_args.len() == args.len())
+            else {
+                // Ill-typed patterns are reported by the type checker.
+                return true;
+            };
+            let patterns: Vec<&Pattern> = args.iter().chain(patterns.iter().copied()).collect();
+            let tys = [ctr_args, rest.to_vec()].concat();
+            useful(ctx, &specialize(rows, name, args.len()), &patterns, &tys)
+        }
+        Pattern::Wildcard | Pattern::Binder(_) => match complete(ctx, ty, &heads(rows)) {
+            Some(ctrs) => ctrs.into_iter().any(|(name, args)| {
+                let patterns: Vec<&Pattern> = std::iter::repeat_n(&WILDCARD, args.len())
+                    .chain(patterns.iter().copied())
+                    .collect();
+                let tys = [args.clone(), rest.to_vec()].concat();
+                useful(ctx, &specialize(rows, &name, args.len()), &patterns, &tys)
+            }),
+            None => useful(ctx, &default(rows), patterns, rest),
+        },
+    }
+}
+
+/// The issues of the arms `patterns` of a match on `scrutinee`, a value of type `ty`.
+pub(crate) fn check_match<T: Clone>(
+    ctx: &mut impl Constructors<T>,
+    scrutinee: &str,
+    ty: &T,
+    patterns: &[&Pattern],
+    span: Span,
+) -> Vec<MatchIssue> {
+    let tys = std::slice::from_ref(ty);
+    let mut issues = vec![];
+    let mut rows: Vec<Row> = vec![];
+    for pattern in patterns {
+        if !useful(ctx, &rows, &[pattern], tys) {
+            issues.push(MatchIssue::UnreachableArm {
+                scrutinee: scrutinee.to_string(),
+                arm: (*pattern).clone(),
+                span,
+            });
+        }
+        rows.push(vec![pattern]);
+    }
+    let missing: Vec<Pattern> = missing(ctx, &rows, tys)
+        .into_iter()
+        .filter_map(|mut witness| witness.pop())
+        .collect();
+    if !missing.is_empty() {
+        issues.insert(
+            0,
+            MatchIssue::NonExhaustive {
+                scrutinee: scrutinee.to_string(),
+                missing,
+                span,
+            },
+        );
+    }
+    issues
+}
+
+impl Contract {
+    /// Find the `match` expressions and statements that do not cover every value of their
+    /// scrutinee, with patterns describing the values left out, and the arms that can never be
+    /// used because the arms before them already match all of their values. The constructors of
+    /// the scrutinees are known from their inferred types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Wallet ()
+    ///     field owner : Option ByStr20 = None {ByStr20}
+    ///     transition Claim ()
+    ///       o <- owner;
+    ///       match o with
+    ///       | Some _ =>
+    ///       | Some a =>
+    ///       end
+    ///     end",
+    /// )
+    /// .unwrap();
+    /// let issues = contract.match_issues().unwrap();
+    /// assert_eq!(issues[0].to_string(), "match on `o` is not exhaustive, missing None");
+    /// assert_eq!(issues[1].to_string(), "arm `Some a` of the match on `o` is unreachable");
+    /// ```
+    pub fn match_issues(&self) -> Result<MatchIssueList, Error> {
+        Ok(type_check::check_module(self.syntax_tree()?).1)
+    }
+}
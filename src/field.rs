@@ -1,8 +1,11 @@
 use lexpr::Value;
 
-use crate::{Error, Type};
+use crate::{
+    ast::{FieldDef, Param},
+    Error, Type,
+};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Field {
     pub name: String,
     pub r#type: Type,
@@ -32,6 +35,31 @@ impl Field {
     }
 }
 
+/// A parameter of an event or a message, whose type is inferred from the value given to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferredField {
+    pub name: String,
+    /// `None` if the type cannot be inferred.
+    pub r#type: Option<Type>,
+}
+
+impl InferredField {
+    pub fn new(name: &str, r#type: Type) -> Self {
+        Self {
+            name: name.to_string(),
+            r#type: Some(r#type),
+        }
+    }
+
+    /// A parameter whose type cannot be inferred.
+    pub fn unknown(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            r#type: None,
+        }
+    }
+}
+
 impl TryFrom<&Value> for Field {
     type Error = Error;
 
@@ -44,7 +72,19 @@ impl TryFrom<&Value> for Field {
     }
 }
 
-#[derive(Debug, PartialEq, Default)]
+impl From<&Param> for Field {
+    fn from(param: &Param) -> Self {
+        Field::new(&param.name, param.ty.clone())
+    }
+}
+
+impl From<&FieldDef> for Field {
+    fn from(field: &FieldDef) -> Self {
+        Field::new(&field.name, field.ty.clone())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct FieldList(pub Vec<Field>);
 
 impl std::ops::Deref for FieldList {
@@ -73,3 +113,9 @@ impl TryFrom<&Value> for FieldList {
         Ok(FieldList(fields?))
     }
 }
+
+impl<'a> FromIterator<&'a Param> for FieldList {
+    fn from_iter<I: IntoIterator<Item = &'a Param>>(params: I) -> Self {
+        FieldList(params.into_iter().map(Field::from).collect())
+    }
+}
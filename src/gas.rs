@@ -0,0 +1,853 @@
+//! Static estimation of the gas used by transitions, following the gas accounting of the Scilla
+//! interpreter: every expression and statement has a cost, and reading, writing, hashing or
+//! sending a value costs in proportion to its size. The result approximates the interpreter's
+//! charges; it is meant to compare transitions and to find those whose cost has no upper bound.
+
+use std::{cell::Cell, collections::HashMap, fmt::Display, ops::Add, rc::Rc};
+
+use serde_json::Value as JsonValue;
+
+use crate::{
+    ast::{Component, Expr, ExprKind, LibEntry, Literal, Module, Pattern, Stmt, StmtKind},
+    infer::{Context, Env},
+    Contract, Error, Type, Value,
+};
+
+/// Size of a block number.
+const BNUM_SIZE: u64 = 32;
+/// Size of an address.
+const ADDRESS_SIZE: u64 = 20;
+/// Cost of verifying a signature or recovering a public key, besides hashing the message.
+const SIGNATURE_COST: u64 = 250;
+/// Cost of hashing each block of 64 bytes.
+const HASH_BLOCK_COST: u64 = 15;
+/// Applications of library functions and calls of procedures nested deeper than this are not
+/// followed, which only happens in contracts that do not type check.
+const MAX_DEPTH: usize = 64;
+
+/// A range of amounts of gas, also used for the sizes of values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasBounds {
+    pub min: u64,
+    /// `None` if the amount depends on sizes that are not known statically, such as the length
+    /// of a string parameter or the number of elements of a list iterated with `forall`.
+    pub max: Option<u64>,
+}
+
+const UNKNOWN: GasBounds = GasBounds { min: 0, max: None };
+
+impl GasBounds {
+    pub fn exact(gas: u64) -> Self {
+        Self {
+            min: gas,
+            max: Some(gas),
+        }
+    }
+
+    pub fn is_bounded(&self) -> bool {
+        self.max.is_some()
+    }
+
+    /// The bounds of an amount that is either in `self` or in `other`.
+    pub fn join(self, other: Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.zip(other.max).map(|(a, b)| a.max(b)),
+        }
+    }
+
+    /// The bounds of the larger of an amount in `self` and an amount in `other`.
+    fn largest(self, other: Self) -> Self {
+        Self {
+            min: self.min.max(other.min),
+            max: self.max.zip(other.max).map(|(a, b)| a.max(b)),
+        }
+    }
+
+    /// The bounds of `count` repetitions of an amount in `self`.
+    fn times(self, count: Self) -> Self {
+        Self {
+            min: self.min.saturating_mul(count.min),
+            max: match (self.max, count.max) {
+                (Some(0), _) | (_, Some(0)) => Some(0),
+                (Some(a), Some(b)) => Some(a.saturating_mul(b)),
+                _ => None,
+            },
+        }
+    }
+
+    fn map(self, f: impl Fn(u64) -> u64) -> Self {
+        Self {
+            min: f(self.min),
+            max: self.max.map(f),
+        }
+    }
+}
+
+impl Add for GasBounds {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            min: self.min.saturating_add(other.min),
+            max: self.max.zip(other.max).map(|(a, b)| a.saturating_add(b)),
+        }
+    }
+}
+
+impl Display for GasBounds {
+    /// `42`, `42 to 100` or `at least 42`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{max}"),
+            Some(max) => write!(f, "{} to {max}", self.min),
+            None => write!(f, "at least {}", self.min),
+        }
+    }
+}
+
+/// The gas used by a transition, over its executions that do not throw.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionGas {
+    pub transition: String,
+    /// `None` if every execution of the transition throws.
+    pub gas: Option<GasBounds>,
+}
+
+impl Display for TransitionGas {
+    /// `Transfer: 120 to 180` or `Fail: always throws`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.gas {
+            Some(gas) => write!(f, "{}: {gas}", self.transition),
+            None => write!(f, "{}: always throws", self.transition),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GasList(pub Vec<TransitionGas>);
+
+impl std::ops::Deref for GasList {
+    type Target = Vec<TransitionGas>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl GasList {
+    pub fn transition(&self, name: &str) -> Option<&TransitionGas> {
+        self.iter().find(|t| t.transition == name)
+    }
+}
+
+/// Estimates the gas used by the transitions of a contract. Without inputs, every value is
+/// assumed to have any size allowed by its type; the values of transition parameters and of
+/// fields can be given to get a tighter estimate.
+///
+/// # Examples
+///
+/// ```
+/// use scilla_parser::{Contract, GasEstimator, Type, Value};
+/// let contract = Contract::from_source(
+///     "scilla_version 0
+///     contract Greeter ()
+///     field greeting : String = \"\"
+///     transition SetGreeting (msg : String)
+///       greeting := msg
+///     end",
+/// )
+/// .unwrap();
+/// let estimator = GasEstimator::new(&contract);
+/// assert_eq!(estimator.estimate("SetGreeting").unwrap().unwrap().to_string(), "at least 0");
+///
+/// let estimator = estimator.with_param("msg", Value::String("hello".to_string()));
+/// assert_eq!(estimator.estimate("SetGreeting").unwrap().unwrap().to_string(), "10");
+/// ```
+#[derive(Debug, Clone)]
+pub struct GasEstimator<'a> {
+    contract: &'a Contract,
+    params: HashMap<String, Value>,
+    fields: HashMap<String, Value>,
+}
+
+impl<'a> GasEstimator<'a> {
+    pub fn new(contract: &'a Contract) -> Self {
+        Self {
+            contract,
+            params: HashMap::new(),
+            fields: HashMap::new(),
+        }
+    }
+
+    /// Use the value of the parameter `name` of the estimated transitions.
+    pub fn with_param(mut self, name: &str, value: Value) -> Self {
+        self.params.insert(name.to_string(), value);
+        self
+    }
+
+    /// Use the current value of the field `name`, including `_balance`.
+    pub fn with_field(mut self, name: &str, value: Value) -> Self {
+        self.fields.insert(name.to_string(), value);
+        self
+    }
+
+    /// Use the values of the fields in `state`, a contract state as returned by the Zilliqa API.
+    /// Entries that are not fields of the contract are ignored.
+    pub fn with_state(mut self, state: &JsonValue) -> Result<Self, Error> {
+        let object = state
+            .as_object()
+            .ok_or_else(|| Error::InvalidValue(format!("{state} is not a contract state")))?;
+        let module = self.contract.syntax_tree()?;
+        for (name, json) in object {
+            if let Some(ty) = field_type(module, name) {
+                let value = Value::from_json(json, &ty, module.library.as_ref())?;
+                self.fields.insert(name.clone(), value);
+            }
+        }
+        Ok(self)
+    }
+
+    /// The gas used by `transition`, `None` if it always throws. Fails if the contract has no
+    /// such transition.
+    pub fn estimate(&self, transition: &str) -> Result<Option<GasBounds>, Error> {
+        let module = self.contract.syntax_tree()?;
+        let component = module
+            .contract
+            .transitions()
+            .find(|t| t.name == transition)
+            .ok_or_else(|| Error::InvalidValue(format!("unknown transition `{transition}`")))?;
+        Ok(Estimator::new(module, &self.params, &self.fields).transition(component))
+    }
+
+    /// The gas used by every transition of the contract.
+    pub fn estimate_all(&self) -> Result<GasList, Error> {
+        let module = self.contract.syntax_tree()?;
+        let estimator = Estimator::new(module, &self.params, &self.fields);
+        Ok(GasList(
+            module
+                .contract
+                .transitions()
+                .map(|transition| TransitionGas {
+                    transition: transition.name.clone(),
+                    gas: estimator.transition(transition),
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// Size of a value, from which the cost of storing, loading and sending it is computed.
+pub fn value_size(value: &Value) -> u64 {
+    match value {
+        Value::Int(ty, _) => int_size(ty).unwrap_or_default(),
+        Value::String(s) => s.len() as u64,
+        Value::BNum(_) => BNUM_SIZE,
+        Value::ByStrX(bytes) | Value::ByStr(bytes) => bytes.len() as u64,
+        Value::Map { entries, .. } => entries
+            .iter()
+            .map(|(k, v)| value_size(k) + value_size(v))
+            .sum(),
+        Value::Adt { args, .. } => args.iter().map(value_size).sum(),
+        Value::Message(entries) => entries
+            .iter()
+            .map(|(name, v)| name.len() as u64 + value_size(v))
+            .sum(),
+    }
+}
+
+fn int_size(ty: &Type) -> Option<u64> {
+    match ty {
+        Type::Int32 | Type::Uint32 => Some(4),
+        Type::Int64 | Type::Uint64 => Some(8),
+        Type::Int128 | Type::Uint128 => Some(16),
+        Type::Int256 | Type::Uint256 => Some(32),
+        _ => None,
+    }
+}
+
+fn literal_size(literal: &Literal) -> GasBounds {
+    GasBounds::exact(match literal {
+        Literal::Int(ty, _) => int_size(ty).unwrap_or_default(),
+        Literal::String(s) => s.len() as u64,
+        Literal::ByStr(hex) => (hex.len() as u64).saturating_sub(2) / 2,
+        Literal::BNum(_) => BNUM_SIZE,
+        Literal::EmptyMap(..) => 0,
+    })
+}
+
+/// Cost of hashing a value of `size` bytes.
+fn hash_cost(size: GasBounds) -> GasBounds {
+    size.map(|size| (size / 64 + 1) * HASH_BLOCK_COST)
+}
+
+/// The values of the maps nested `depth` levels in `map`.
+fn map_values(map: &Value, depth: usize) -> Vec<&Value> {
+    match map {
+        _ if depth == 0 => vec![map],
+        Value::Map { entries, .. } => entries
+            .iter()
+            .flat_map(|(_, v)| map_values(v, depth - 1))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// What is known of a value while estimating.
+#[derive(Clone)]
+struct Abstract<'a> {
+    ty: Option<Type>,
+    size: GasBounds,
+    /// The number of elements of a list or of entries of a map given as input.
+    len: Option<u64>,
+    /// The code of a function and the scope it was defined in.
+    closure: Option<Rc<Closure<'a>>>,
+}
+
+struct Closure<'a> {
+    /// A `fun` or `tfun` expression.
+    expr: &'a Expr,
+    scope: Scope<'a>,
+}
+
+type Scope<'a> = HashMap<String, Abstract<'a>>;
+
+impl Abstract<'_> {
+    fn join(self, other: Self) -> Self {
+        Self {
+            ty: self.ty.or(other.ty),
+            size: self.size.join(other.size),
+            len: self.len.filter(|_| self.len == other.len),
+            closure: self.closure.or(other.closure),
+        }
+    }
+}
+
+fn types(scope: &Scope) -> Env {
+    scope
+        .iter()
+        .filter_map(|(name, value)| Some((name.clone(), value.ty.clone()?)))
+        .collect()
+}
+
+struct Estimator<'a> {
+    context: Context<'a>,
+    module: &'a Module,
+    params: &'a HashMap<String, Value>,
+    fields: &'a HashMap<String, Value>,
+    depth: Cell<usize>,
+}
+
+impl<'a> Estimator<'a> {
+    fn new(
+        module: &'a Module,
+        params: &'a HashMap<String, Value>,
+        fields: &'a HashMap<String, Value>,
+    ) -> Self {
+        Self {
+            context: Context::new(module),
+            module,
+            params,
+            fields,
+            depth: Cell::new(0),
+        }
+    }
+
+    /// The gas used by `transition`, which is charged for the size of its parameters and the
+    /// statements of its body.
+    fn transition(&self, transition: &'a Component) -> Option<GasBounds> {
+        let mut scope = self.component_scope(transition);
+        for param in &transition.params {
+            if let Some(value) = self.params.get(&param.name) {
+                scope.insert(param.name.clone(), Self::of_value(value, &param.ty));
+            }
+        }
+        let params = transition
+            .params
+            .iter()
+            .map(|p| scope[&p.name].size)
+            .fold(GasBounds::exact(0), Add::add);
+        Some(params + self.stmts(&transition.body, &mut scope)?)
+    }
+
+    fn component_scope(&self, component: &Component) -> Scope<'a> {
+        self.context
+            .component_env(component)
+            .into_iter()
+            .map(|(name, ty)| (name, self.of_type(Some(ty))))
+            .collect()
+    }
+
+    /// Run `f` one level of application deeper, or return `None` past `MAX_DEPTH`.
+    fn nested<T>(&self, f: impl FnOnce() -> T) -> Option<T> {
+        let depth = self.depth.get();
+        if depth >= MAX_DEPTH {
+            return None;
+        }
+        self.depth.set(depth + 1);
+        let result = f();
+        self.depth.set(depth);
+        Some(result)
+    }
+
+    fn of_type(&self, ty: Option<Type>) -> Abstract<'a> {
+        Abstract {
+            size: ty
+                .as_ref()
+                .map_or(UNKNOWN, |ty| self.type_size(ty, &mut vec![])),
+            ty,
+            len: None,
+            closure: None,
+        }
+    }
+
+    fn of_value(value: &Value, ty: &Type) -> Abstract<'a> {
+        Abstract {
+            ty: Some(ty.clone()),
+            size: GasBounds::exact(value_size(value)),
+            len: match value {
+                Value::Map { entries, .. } => Some(entries.len() as u64),
+                _ => value.as_list().map(|items| items.len() as u64),
+            },
+            closure: None,
+        }
+    }
+
+    /// The range of sizes of the values of type `ty`. `seen` holds the user defined types being
+    /// sized, whose recursive values have no largest size.
+    fn type_size(&self, ty: &Type, seen: &mut Vec<String>) -> GasBounds {
+        if let Some(size) = int_size(ty) {
+            return GasBounds::exact(size);
+        }
+        match ty {
+            Type::BNum => GasBounds::exact(BNUM_SIZE),
+            Type::ByStr(n) => GasBounds::exact(*n as u64),
+            Type::Address(_) => GasBounds::exact(ADDRESS_SIZE),
+            Type::Bool | Type::Function(..) | Type::PolyFun(..) => GasBounds::exact(0),
+            Type::Option(ty) => GasBounds::exact(0).join(self.type_size(ty, seen)),
+            Type::Pair(a, b) => self.type_size(a, seen) + self.type_size(b, seen),
+            Type::Other(name) if !seen.contains(name) => {
+                let constructors = self
+                    .module
+                    .library
+                    .iter()
+                    .flat_map(|lib| &lib.entries)
+                    .find_map(|entry| match entry {
+                        LibEntry::Type {
+                            name: n,
+                            constructors,
+                            ..
+                        } if n == name => Some(constructors),
+                        _ => None,
+                    });
+                let Some(constructors) = constructors else {
+                    return UNKNOWN;
+                };
+                seen.push(name.clone());
+                let size = constructors
+                    .iter()
+                    .map(|ctr| {
+                        ctr.args
+                            .iter()
+                            .map(|arg| self.type_size(arg, seen))
+                            .fold(GasBounds::exact(0), Add::add)
+                    })
+                    .reduce(GasBounds::join)
+                    .unwrap_or(GasBounds::exact(0));
+                seen.pop();
+                size
+            }
+            _ => UNKNOWN,
+        }
+    }
+
+    /// The value of `name`: a variable in scope, a library value or a standard library function.
+    fn lookup(&self, name: &str, scope: &Scope<'a>) -> Abstract<'a> {
+        if let Some(value) = scope.get(name) {
+            return value.clone();
+        }
+        let ty = self.context.lookup(name, &Env::new());
+        let expr = self
+            .module
+            .library
+            .iter()
+            .flat_map(|lib| &lib.entries)
+            .find_map(|entry| match entry {
+                LibEntry::Let { name: n, expr, .. } if n == name => Some(expr),
+                _ => None,
+            });
+        match expr.and_then(|expr| self.nested(|| self.eval(expr, &Scope::new()).1)) {
+            Some(value) => Abstract {
+                ty: ty.or(value.ty),
+                ..value
+            },
+            None => self.of_type(ty),
+        }
+    }
+
+    fn size(&self, names: &[String], scope: &Scope<'a>) -> GasBounds {
+        names
+            .iter()
+            .map(|name| self.lookup(name, scope).size)
+            .fold(GasBounds::exact(0), Add::add)
+    }
+
+    /// Add the binders of `pattern`, matched against a value of type `ty`, to `scope`.
+    fn bind(&self, pattern: &Pattern, ty: Option<&Type>, scope: &mut Scope<'a>) {
+        let mut env = Env::new();
+        self.context.bind_pattern(pattern, ty, &mut env);
+        for name in pattern.binders() {
+            scope.insert(name.to_string(), self.of_type(env.remove(name)));
+        }
+    }
+
+    /// The cost of evaluating `expr` and its value.
+    fn eval(&self, expr: &'a Expr, scope: &Scope<'a>) -> (GasBounds, Abstract<'a>) {
+        let one = GasBounds::exact(1);
+        let ty = || self.context.infer(expr, &types(scope));
+        match &expr.kind {
+            ExprKind::Literal(literal) => {
+                let size = literal_size(literal);
+                let value = Abstract {
+                    size,
+                    ..self.of_type(ty())
+                };
+                (size, value)
+            }
+            ExprKind::Var(name) => (one, self.lookup(name, scope)),
+            ExprKind::Message(entries) => {
+                let size = entries
+                    .iter()
+                    .map(|e| {
+                        GasBounds::exact(e.name.len() as u64) + self.eval(&e.value, scope).1.size
+                    })
+                    .fold(GasBounds::exact(0), Add::add);
+                let value = Abstract {
+                    size,
+                    ..self.of_type(ty())
+                };
+                (one, value)
+            }
+            ExprKind::Let {
+                name, value, body, ..
+            } => {
+                let (value_cost, value) = self.eval(value, scope);
+                let mut scope = scope.clone();
+                scope.insert(name.clone(), value);
+                let (body_cost, value) = self.eval(body, &scope);
+                (one + value_cost + body_cost, value)
+            }
+            ExprKind::Fun { .. } | ExprKind::TFun { .. } => {
+                let value = Abstract {
+                    size: GasBounds::exact(0),
+                    closure: Some(Rc::new(Closure {
+                        expr,
+                        scope: scope.clone(),
+                    })),
+                    ..self.of_type(ty())
+                };
+                (one, value)
+            }
+            ExprKind::TApp { func, .. } => {
+                let value = self.lookup(func, scope);
+                (
+                    one,
+                    Abstract {
+                        ty: ty().or(value.ty),
+                        ..value
+                    },
+                )
+            }
+            ExprKind::App { func, args } => {
+                let func = self.lookup(func, scope);
+                let args: Vec<_> = args.iter().map(|arg| self.lookup(arg, scope)).collect();
+                let (cost, value) = self.apply(func, &args, ty());
+                (one + cost, value)
+            }
+            ExprKind::Constructor { args, .. } => {
+                let value = Abstract {
+                    size: self.size(args, scope),
+                    ..self.of_type(ty())
+                };
+                (one, value)
+            }
+            ExprKind::Builtin { name, args } => {
+                let args: Vec<_> = args.iter().map(|arg| self.lookup(arg, scope)).collect();
+                let value = self.of_type(ty());
+                let size = builtin_size(name, &args).unwrap_or(value.size);
+                (builtin_cost(name, &args), Abstract { size, ..value })
+            }
+            ExprKind::Match { scrutinee, arms } => {
+                let scrutinee = self.lookup(scrutinee, scope);
+                let (cost, value) = arms
+                    .iter()
+                    .map(|(pattern, body)| {
+                        let mut scope = scope.clone();
+                        self.bind(pattern, scrutinee.ty.as_ref(), &mut scope);
+                        self.eval(body, &scope)
+                    })
+                    .reduce(|(c1, v1), (c2, v2)| (c1.join(c2), v1.join(v2)))
+                    .unwrap_or_else(|| (GasBounds::exact(0), self.of_type(None)));
+                (GasBounds::exact(arms.len() as u64) + cost, value)
+            }
+        }
+    }
+
+    /// The cost of applying `func` to `args` and the result, of type `ty`. The code of library
+    /// functions is followed; functions of the standard library are charged for the size of
+    /// their arguments.
+    fn apply(
+        &self,
+        func: Abstract<'a>,
+        args: &[Abstract<'a>],
+        ty: Option<Type>,
+    ) -> (GasBounds, Abstract<'a>) {
+        let Some((arg, rest)) = args.split_first() else {
+            return (
+                GasBounds::exact(0),
+                Abstract {
+                    ty: ty.or(func.ty),
+                    ..func
+                },
+            );
+        };
+        let applied = func.closure.and_then(|closure| {
+            self.nested(|| match &closure.expr.kind {
+                ExprKind::TFun { body, .. } => {
+                    let (cost, func) = self.eval(body, &closure.scope);
+                    let (rest_cost, value) = self.apply(func, args, ty.clone());
+                    Some((cost + rest_cost, value))
+                }
+                ExprKind::Fun { param, body, .. } => {
+                    let mut scope = closure.scope.clone();
+                    scope.insert(param.clone(), arg.clone());
+                    let (cost, func) = self.eval(body, &scope);
+                    let (rest_cost, value) = self.apply(func, rest, ty.clone());
+                    Some((cost + rest_cost, value))
+                }
+                _ => None,
+            })
+            .flatten()
+        });
+        applied.unwrap_or_else(|| {
+            let size = args
+                .iter()
+                .map(|arg| arg.size)
+                .fold(GasBounds::exact(0), Add::add);
+            (GasBounds::exact(1) + size, self.of_type(ty))
+        })
+    }
+
+    /// The cost of `stmts`, or `None` if all of their paths throw.
+    fn stmts(&self, stmts: &'a [Stmt], scope: &mut Scope<'a>) -> Option<GasBounds> {
+        let mut cost = GasBounds::exact(0);
+        for stmt in stmts {
+            cost = cost + self.stmt(stmt, scope)?;
+        }
+        Some(cost)
+    }
+
+    /// The value bound by the statement `stmt` reading state or the blockchain, by its type.
+    fn read(&self, stmt: &Stmt, lhs: &str, scope: &Scope<'a>) -> Abstract<'a> {
+        let mut env = types(scope);
+        self.context.bind_stmt(stmt, &mut env);
+        self.of_type(env.remove(lhs))
+    }
+
+    fn stmt(&self, stmt: &'a Stmt, scope: &mut Scope<'a>) -> Option<GasBounds> {
+        let cost = match &stmt.kind {
+            StmtKind::Load { lhs, field } => {
+                let value = match (self.fields.get(field), field_type(self.module, field)) {
+                    (Some(value), Some(ty)) => Self::of_value(value, &ty),
+                    _ => self.read(stmt, lhs, scope),
+                };
+                let cost = value.size;
+                scope.insert(lhs.clone(), value);
+                cost
+            }
+            StmtKind::Store { rhs, .. } => self.lookup(rhs, scope).size,
+            StmtKind::Bind { lhs, expr } => {
+                let (cost, value) = self.eval(expr, scope);
+                scope.insert(lhs.clone(), value);
+                cost
+            }
+            StmtKind::MapUpdate { keys, value, .. } => {
+                self.size(keys, scope) + self.lookup(value, scope).size
+            }
+            StmtKind::MapDelete { keys, .. } => GasBounds::exact(1) + self.size(keys, scope),
+            StmtKind::MapGet {
+                lhs,
+                map,
+                keys,
+                exists,
+            } => {
+                let mut value = self.read(stmt, lhs, scope);
+                if let (Some(map), false) = (self.fields.get(map), exists) {
+                    // The key may be missing, in which case the value is `None`.
+                    value.size = map_values(map, keys.len())
+                        .into_iter()
+                        .map(|v| GasBounds::exact(value_size(v)))
+                        .fold(GasBounds::exact(0), GasBounds::join);
+                }
+                let cost = self.size(keys, scope) + value.size.largest(GasBounds::exact(1));
+                scope.insert(lhs.clone(), value);
+                cost
+            }
+            StmtKind::RemoteLoad { lhs, .. } | StmtKind::ReadFromBC { lhs, .. } => {
+                let value = self.read(stmt, lhs, scope);
+                let cost = value.size.largest(GasBounds::exact(1));
+                scope.insert(lhs.clone(), value);
+                cost
+            }
+            StmtKind::RemoteMapGet { lhs, keys, .. } => {
+                let value = self.read(stmt, lhs, scope);
+                let cost = self.size(keys, scope) + value.size.largest(GasBounds::exact(1));
+                scope.insert(lhs.clone(), value);
+                cost
+            }
+            StmtKind::TypeCast { lhs, .. } => {
+                let value = self.read(stmt, lhs, scope);
+                scope.insert(lhs.clone(), value);
+                GasBounds::exact(ADDRESS_SIZE)
+            }
+            StmtKind::Accept => GasBounds::exact(1),
+            StmtKind::Send(name) | StmtKind::Event(name) => {
+                self.lookup(name, scope).size.largest(GasBounds::exact(1))
+            }
+            StmtKind::Throw(_) => return None,
+            StmtKind::Match { scrutinee, arms } => {
+                let scrutinee = self.lookup(scrutinee, scope);
+                let cost = arms
+                    .iter()
+                    .filter_map(|(pattern, body)| {
+                        let mut scope = scope.clone();
+                        self.bind(pattern, scrutinee.ty.as_ref(), &mut scope);
+                        self.stmts(body, &mut scope)
+                    })
+                    .reduce(GasBounds::join)?;
+                GasBounds::exact(arms.len() as u64) + cost
+            }
+            StmtKind::CallProc { name, args } => {
+                let args: Vec<_> = args.iter().map(|arg| self.lookup(arg, scope)).collect();
+                GasBounds::exact(1) + self.call(name, &args)?
+            }
+            StmtKind::Iterate { list, proc } => {
+                let list = self.lookup(list, scope);
+                let elem = match &list.ty {
+                    Some(Type::List(ty)) => Some(*ty.clone()),
+                    _ => None,
+                };
+                let count = list.len.map_or(UNKNOWN, GasBounds::exact);
+                match self.call(proc, &[self.of_type(elem)]) {
+                    Some(cost) => (GasBounds::exact(1) + cost).times(count),
+                    // Only an empty list does not throw.
+                    None if count.min == 0 => GasBounds::exact(0),
+                    None => return None,
+                }
+            }
+        };
+        Some(cost)
+    }
+
+    /// The cost of calling the procedure `name` with `args`, or `None` if it always throws.
+    fn call(&self, name: &str, args: &[Abstract<'a>]) -> Option<GasBounds> {
+        let Some(procedure) = self.module.contract.component(name) else {
+            return Some(GasBounds::exact(0));
+        };
+        let mut scope = self.component_scope(procedure);
+        for (param, arg) in procedure.params.iter().zip(args) {
+            scope.insert(param.name.clone(), arg.clone());
+        }
+        self.nested(|| self.stmts(&procedure.body, &mut scope))
+            .unwrap_or(Some(UNKNOWN))
+    }
+}
+
+pub(crate) fn field_type(module: &Module, name: &str) -> Option<Type> {
+    if name == "_balance" {
+        return Some(Type::Uint128);
+    }
+    module
+        .contract
+        .fields
+        .iter()
+        .find(|f| f.name == name)
+        .map(|f| f.ty.clone())
+}
+
+/// The cost of the builtin `name` applied to `args`: operations on integers and other values
+/// of fixed size cost as much as their largest operand, operations on strings and byte strings
+/// as much as all of them, and hashes by blocks of 64 bytes.
+fn builtin_cost(name: &str, args: &[Abstract]) -> GasBounds {
+    let sizes = args.iter().map(|arg| arg.size);
+    let total = sizes.clone().fold(GasBounds::exact(0), Add::add);
+    let largest = sizes.fold(GasBounds::exact(1), GasBounds::largest);
+    let size_of = |i: usize| args.get(i).map_or(GasBounds::exact(0), |arg| arg.size);
+    match name {
+        "concat" | "substr" | "strrev" | "strlen" | "to_string" | "to_ascii" | "to_bystr"
+        | "bech32_to_bystr20" | "bystr20_to_bech32" => total.largest(GasBounds::exact(1)),
+        "sha256hash" | "keccak256hash" | "ripemd160hash" => hash_cost(total),
+        "schnorr_verify" | "ecdsa_verify" => {
+            GasBounds::exact(SIGNATURE_COST) + hash_cost(size_of(1))
+        }
+        "schnorr_get_address" | "ecdsa_recover_pk" => GasBounds::exact(SIGNATURE_COST),
+        "put" | "get" | "contains" | "remove" => {
+            GasBounds::exact(1)
+                + args
+                    .iter()
+                    .skip(1)
+                    .map(|arg| arg.size)
+                    .fold(GasBounds::exact(0), Add::add)
+        }
+        "size" => GasBounds::exact(1),
+        "to_list" => size_of(0).largest(GasBounds::exact(1)),
+        // The cost depends on the value of the argument.
+        "to_nat" => GasBounds { min: 1, max: None },
+        "pow" => largest.map(|size| size * 8),
+        _ => largest,
+    }
+}
+
+/// The size of the result of the builtin `name` when it does not follow from its type.
+fn builtin_size(name: &str, args: &[Abstract]) -> Option<GasBounds> {
+    let size_of = |i: usize| args.get(i).map_or(GasBounds::exact(0), |arg| arg.size);
+    match name {
+        "concat" => Some(size_of(0) + size_of(1)),
+        "substr" => Some(GasBounds::exact(0).join(size_of(0))),
+        "strrev" | "to_bystr" => Some(size_of(0)),
+        // The decimal representation of a 256-bit integer has at most 78 digits.
+        "to_string" => Some(GasBounds {
+            min: 1,
+            max: Some(78),
+        }),
+        _ => None,
+    }
+}
+
+impl Contract {
+    /// Static bounds of the gas used by each transition, over its executions that do not throw.
+    /// See [`GasEstimator`] to estimate it from the values of parameters and fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Counter ()
+    ///     field count : Uint128 = Uint128 0
+    ///     transition Increment ()
+    ///       c <- count;
+    ///       one = Uint128 1;
+    ///       c = builtin add c one;
+    ///       count := c
+    ///     end",
+    /// )
+    /// .unwrap();
+    /// let gas = contract.gas_bounds().unwrap();
+    /// assert_eq!(gas[0].to_string(), "Increment: 64");
+    /// ```
+    pub fn gas_bounds(&self) -> Result<GasList, Error> {
+        GasEstimator::new(self).estimate_all()
+    }
+}
@@ -0,0 +1,744 @@
+//! Best-effort type inference of the values bound in transitions, procedures and library
+//! functions. It assumes the contract is well-typed and is used to describe events and messages;
+//! whenever a type cannot be determined, `None` is returned instead of an error.
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{
+        Component, Expr, ExprKind, Import, LibEntry, Literal, MessageEntry, Module, Pattern, Stmt,
+        StmtKind,
+    },
+    parser::parse_type,
+    InferredField, Type,
+};
+
+/// Types of the variables in scope.
+pub(crate) type Env = HashMap<String, Type>;
+
+/// Signatures of the recursion primitives, in scope without import.
+const PRELUDE: &[(&str, &str)] = &[
+    (
+        "list_foldl",
+        "forall 'A. forall 'B. ('B -> 'A -> 'B) -> 'B -> List 'A -> 'B",
+    ),
+    (
+        "list_foldr",
+        "forall 'A. forall 'B. ('A -> 'B -> 'B) -> 'B -> List 'A -> 'B",
+    ),
+    (
+        "nat_fold",
+        "forall 'T. ('T -> Nat -> 'T) -> 'T -> Nat -> 'T",
+    ),
+];
+
+/// Signatures of the standard library functions commonly used in contracts, by library. The
+/// integer comparisons of `IntUtils` are generated by [`stdlib`].
+const STDLIB: &[(&str, &[(&str, &str)])] = &[
+    (
+        "BoolUtils",
+        &[
+            ("andb", "Bool -> Bool -> Bool"),
+            ("orb", "Bool -> Bool -> Bool"),
+            ("xorb", "Bool -> Bool -> Bool"),
+            ("negb", "Bool -> Bool"),
+            ("bool_to_string", "Bool -> String"),
+        ],
+    ),
+    (
+        "PairUtils",
+        &[
+            ("fst", "forall 'A. forall 'B. Pair 'A 'B -> 'A"),
+            ("snd", "forall 'A. forall 'B. Pair 'A 'B -> 'B"),
+        ],
+    ),
+    (
+        "ListUtils",
+        &[
+            ("list_length", "forall 'A. List 'A -> Uint32"),
+            ("list_is_empty", "forall 'A. List 'A -> Bool"),
+            ("list_head", "forall 'A. List 'A -> Option 'A"),
+            ("list_tail", "forall 'A. List 'A -> Option (List 'A)"),
+            ("list_append", "forall 'A. List 'A -> List 'A -> List 'A"),
+            ("list_reverse", "forall 'A. List 'A -> List 'A"),
+            ("list_flatten", "forall 'A. List (List 'A) -> List 'A"),
+            ("list_nth", "forall 'A. Uint32 -> List 'A -> Option 'A"),
+            (
+                "list_map",
+                "forall 'A. forall 'B. ('A -> 'B) -> List 'A -> List 'B",
+            ),
+            (
+                "list_filter",
+                "forall 'A. ('A -> Bool) -> List 'A -> List 'A",
+            ),
+            ("list_exists", "forall 'A. ('A -> Bool) -> List 'A -> Bool"),
+            ("list_forall", "forall 'A. ('A -> Bool) -> List 'A -> Bool"),
+            (
+                "list_find",
+                "forall 'A. ('A -> Bool) -> List 'A -> Option 'A",
+            ),
+            (
+                "list_mem",
+                "forall 'A. ('A -> 'A -> Bool) -> 'A -> List 'A -> Bool",
+            ),
+            (
+                "list_zip",
+                "forall 'A. forall 'B. List 'A -> List 'B -> List (Pair 'A 'B)",
+            ),
+            (
+                "list_unzip",
+                "forall 'A. forall 'B. List (Pair 'A 'B) -> Pair (List 'A) (List 'B)",
+            ),
+            (
+                "list_sort",
+                "forall 'A. ('A -> 'A -> Bool) -> List 'A -> List 'A",
+            ),
+            (
+                "list_eq",
+                "forall 'A. ('A -> 'A -> Bool) -> List 'A -> List 'A -> Bool",
+            ),
+        ],
+    ),
+    (
+        "NatUtils",
+        &[
+            ("nat_prev", "Nat -> Option Nat"),
+            ("nat_eq", "Nat -> Nat -> Bool"),
+            ("nat_to_int", "Nat -> Uint32"),
+        ],
+    ),
+];
+
+const INT_TYPES: [Type; 8] = [
+    Type::Int32,
+    Type::Int64,
+    Type::Int128,
+    Type::Int256,
+    Type::Uint32,
+    Type::Uint64,
+    Type::Uint128,
+    Type::Uint256,
+];
+
+fn named(name: &str) -> Type {
+    Type::Other(name.to_string())
+}
+
+fn boxed(ty: Type) -> Box<Type> {
+    Box::new(ty)
+}
+
+/// Replace the type variable `tvar` with `replacement` in `ty`.
+pub(crate) fn substitute(ty: &Type, tvar: &str, replacement: &Type) -> Type {
+    let sub = |t: &Type| boxed(substitute(t, tvar, replacement));
+    match ty {
+        Type::TypeVar(v) if v == tvar => replacement.clone(),
+        Type::Map(k, v) => Type::Map(sub(k), sub(v)),
+        Type::Option(t) => Type::Option(sub(t)),
+        Type::List(t) => Type::List(sub(t)),
+        Type::Pair(a, b) => Type::Pair(sub(a), sub(b)),
+        Type::Function(a, b) => Type::Function(sub(a), sub(b)),
+        Type::PolyFun(v, t) if v != tvar => Type::PolyFun(v.clone(), sub(t)),
+        _ => ty.clone(),
+    }
+}
+
+/// Whether the functions of an imported library are known. Libraries imported under an alias are
+/// not modelled.
+pub(crate) fn is_modelled(import: &Import) -> bool {
+    import.alias.is_none()
+        && (import.name == "IntUtils" || STDLIB.iter().any(|(name, _)| *name == import.name))
+}
+
+/// Types of the recursion primitives and of the functions of the imported standard libraries,
+/// including the integer comparisons of `IntUtils` such as `uint128_le`.
+pub(crate) fn stdlib(imports: &[Import]) -> Env {
+    let imported = |library: &str| imports.iter().any(|i| i.name == library && is_modelled(i));
+    let mut library: Env = STDLIB
+        .iter()
+        .filter(|(name, _)| imported(name))
+        .flat_map(|(_, functions)| functions.iter())
+        .chain(PRELUDE)
+        .filter_map(|(name, ty)| Some((name.to_string(), parse_type(ty).ok()?)))
+        .collect();
+    if !imported("IntUtils") {
+        return library;
+    }
+    for ty in INT_TYPES {
+        for op in ["eq", "lt", "le", "gt", "ge"] {
+            let name = format!("{}_{}", ty.to_string().to_lowercase(), op);
+            let signature = Type::Function(
+                boxed(ty.clone()),
+                boxed(Type::Function(boxed(ty.clone()), boxed(Type::Bool))),
+            );
+            library.insert(name, signature);
+        }
+    }
+    library
+}
+
+pub(crate) struct Context<'a> {
+    pub module: &'a Module,
+    /// Types of the library values and the imported standard library functions.
+    library: Env,
+    /// Constructor name to the name of its ADT and the types of its arguments.
+    constructors: HashMap<String, (String, Vec<Type>)>,
+    fields: Env,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(module: &'a Module) -> Self {
+        let mut context = Self {
+            module,
+            library: stdlib(&module.imports),
+            constructors: HashMap::new(),
+            fields: module
+                .contract
+                .fields
+                .iter()
+                .map(|f| (f.name.clone(), f.ty.clone()))
+                .chain([("_balance".to_string(), Type::Uint128)])
+                .collect(),
+        };
+        for entry in module.library.iter().flat_map(|lib| &lib.entries) {
+            match entry {
+                LibEntry::Let { name, ty, expr, .. } => {
+                    let ty = ty.clone().or_else(|| context.infer(expr, &Env::new()));
+                    if let Some(ty) = ty {
+                        context.library.insert(name.clone(), ty);
+                    }
+                }
+                LibEntry::Type {
+                    name, constructors, ..
+                } => {
+                    for ctr in constructors {
+                        context
+                            .constructors
+                            .insert(ctr.name.clone(), (name.clone(), ctr.args.clone()));
+                    }
+                }
+            }
+        }
+        context
+    }
+
+    /// The environment at the beginning of a component: implicit parameters, contract parameters
+    /// and the component's own parameters.
+    pub fn component_env(&self, component: &Component) -> Env {
+        let contract = &self.module.contract;
+        [
+            ("_sender", Type::ByStr(20)),
+            ("_origin", Type::ByStr(20)),
+            ("_this_address", Type::ByStr(20)),
+            ("_amount", Type::Uint128),
+        ]
+        .into_iter()
+        .map(|(name, ty)| (name.to_string(), ty))
+        .chain(
+            contract
+                .params
+                .iter()
+                .chain(&component.params)
+                .map(|p| (p.name.clone(), p.ty.clone())),
+        )
+        .collect()
+    }
+
+    pub fn lookup(&self, name: &str, env: &Env) -> Option<Type> {
+        env.get(name).or_else(|| self.library.get(name)).cloned()
+    }
+
+    /// Infer the type of `expr` in the environment `env`.
+    pub fn infer(&self, expr: &Expr, env: &Env) -> Option<Type> {
+        match &expr.kind {
+            ExprKind::Literal(literal) => Some(match literal {
+                Literal::Int(ty, _) => ty.clone(),
+                Literal::String(_) => Type::String,
+                Literal::ByStr(hex) => Type::ByStr((hex.len() - 2) / 2),
+                Literal::BNum(_) => Type::BNum,
+                Literal::EmptyMap(k, v) => Type::Map(boxed(k.clone()), boxed(v.clone())),
+            }),
+            ExprKind::Var(name) => self.lookup(name, env),
+            ExprKind::Message(entries) => {
+                Some(named(if entries.iter().any(|e| e.name == "_eventname") {
+                    "Event"
+                } else if entries.iter().any(|e| e.name == "_exception") {
+                    "Exception"
+                } else {
+                    "Message"
+                }))
+            }
+            ExprKind::Let {
+                name,
+                ty,
+                value,
+                body,
+            } => {
+                let mut env = env.clone();
+                if let Some(ty) = ty.clone().or_else(|| self.infer(value, &env)) {
+                    env.insert(name.clone(), ty);
+                }
+                self.infer(body, &env)
+            }
+            ExprKind::Fun { param, ty, body } => {
+                let mut env = env.clone();
+                env.insert(param.clone(), ty.clone());
+                Some(Type::Function(
+                    boxed(ty.clone()),
+                    boxed(self.infer(body, &env)?),
+                ))
+            }
+            ExprKind::App { func, args } => {
+                let mut ty = self.lookup(func, env)?;
+                for _ in args {
+                    match ty {
+                        Type::Function(_, result) => ty = *result,
+                        _ => return None,
+                    }
+                }
+                Some(ty)
+            }
+            ExprKind::Constructor {
+                name,
+                type_args,
+                args,
+            } => self.constructor_type(name, type_args, args, env),
+            ExprKind::Builtin { name, args } => {
+                let arg_types: Vec<Option<Type>> =
+                    args.iter().map(|a| self.lookup(a, env)).collect();
+                builtin_type(name, &arg_types)
+            }
+            ExprKind::Match { scrutinee, arms } => {
+                let scrutinee = self.lookup(scrutinee, env);
+                arms.iter().find_map(|(pattern, arm)| {
+                    let mut env = env.clone();
+                    self.bind_pattern(pattern, scrutinee.as_ref(), &mut env);
+                    self.infer(arm, &env)
+                })
+            }
+            ExprKind::TFun { tvar, body } => {
+                Some(Type::PolyFun(tvar.clone(), boxed(self.infer(body, env)?)))
+            }
+            ExprKind::TApp { func, type_args } => {
+                let mut ty = self.lookup(func, env)?;
+                for arg in type_args {
+                    match ty {
+                        Type::PolyFun(tvar, body) => ty = substitute(&body, &tvar, arg),
+                        _ => return None,
+                    }
+                }
+                Some(ty)
+            }
+        }
+    }
+
+    fn constructor_type(
+        &self,
+        name: &str,
+        type_args: &[Type],
+        args: &[String],
+        env: &Env,
+    ) -> Option<Type> {
+        let type_arg = |i: usize| {
+            type_args
+                .get(i)
+                .cloned()
+                .or_else(|| args.get(i).and_then(|a| self.lookup(a, env)))
+        };
+        match name {
+            "True" | "False" => Some(Type::Bool),
+            "Some" | "None" => Some(Type::Option(boxed(type_arg(0)?))),
+            "Nil" | "Cons" => Some(Type::List(boxed(type_arg(0)?))),
+            "Pair" => Some(Type::Pair(boxed(type_arg(0)?), boxed(type_arg(1)?))),
+            "Zero" | "Succ" => Some(named("Nat")),
+            _ => self
+                .constructors
+                .get(name)
+                .map(|(adt, _)| Type::Other(adt.clone())),
+        }
+    }
+
+    /// Types of the arguments of constructor `name` when matching a value of type `ty`.
+    fn constructor_args(&self, name: &str, ty: Option<&Type>) -> Vec<Option<Type>> {
+        match (name, ty) {
+            ("Some", Some(Type::Option(t))) => vec![Some(*t.clone())],
+            ("Cons", Some(Type::List(t))) => vec![Some(*t.clone()), Some(Type::List(t.clone()))],
+            ("Pair", Some(Type::Pair(a, b))) => vec![Some(*a.clone()), Some(*b.clone())],
+            ("Succ", _) => vec![Some(named("Nat"))],
+            _ => match self.constructors.get(name) {
+                Some((_, args)) => args.iter().cloned().map(Some).collect(),
+                None => vec![],
+            },
+        }
+    }
+
+    /// Add the binders of `pattern`, matched against a value of type `ty`, to `env`.
+    pub fn bind_pattern(&self, pattern: &Pattern, ty: Option<&Type>, env: &mut Env) {
+        match pattern {
+            Pattern::Wildcard => {}
+            Pattern::Binder(name) => match ty {
+                Some(ty) => {
+                    env.insert(name.clone(), ty.clone());
+                }
+                None => {
+                    env.remove(name);
+                }
+            },
+            Pattern::Constructor(name, args) => {
+                let arg_types = self.constructor_args(name, ty);
+                for (i, arg) in args.iter().enumerate() {
+                    self.bind_pattern(arg, arg_types.get(i).and_then(Option::as_ref), env);
+                }
+            }
+        }
+    }
+
+    /// Type of `map[k1]...[kn]`, which is an `Option` of the value, or `Bool` for `exists`.
+    fn map_access(map: Option<Type>, keys: usize, exists: bool) -> Option<Type> {
+        let mut ty = map?;
+        for _ in 0..keys {
+            match ty {
+                Type::Map(_, v) => ty = *v,
+                _ => return None,
+            }
+        }
+        Some(if exists {
+            Type::Bool
+        } else {
+            Type::Option(boxed(ty))
+        })
+    }
+
+    /// Remote fields can be typed only if the address was cast to a contract type declaring them.
+    fn remote_field(&self, address: &str, field: &str, env: &Env) -> Option<Type> {
+        match env.get(address)? {
+            Type::Address(Some(fields)) => fields
+                .iter()
+                .find(|f| f.name == field)
+                .map(|f| f.r#type.clone()),
+            _ => None,
+        }
+    }
+
+    /// Add the value bound by `stmt`, if any, to `env`. The binders of the arms of a `match`
+    /// are local to them and are not added.
+    pub fn bind_stmt(&self, stmt: &Stmt, env: &mut Env) {
+        let (lhs, ty) = match &stmt.kind {
+            StmtKind::Load { lhs, field } => (lhs, self.fields.get(field).cloned()),
+            StmtKind::Bind { lhs, expr } => (lhs, self.infer(expr, env)),
+            StmtKind::MapGet {
+                lhs,
+                map,
+                keys,
+                exists,
+            } => (
+                lhs,
+                Self::map_access(self.fields.get(map).cloned(), keys.len(), *exists),
+            ),
+            StmtKind::RemoteLoad {
+                lhs,
+                address,
+                field,
+            } => (lhs, self.remote_field(address, field, env)),
+            StmtKind::RemoteMapGet {
+                lhs,
+                address,
+                map,
+                keys,
+                exists,
+            } => (
+                lhs,
+                Self::map_access(self.remote_field(address, map, env), keys.len(), *exists),
+            ),
+            StmtKind::TypeCast { lhs, ty, .. } => (lhs, Some(Type::Option(boxed(ty.clone())))),
+            StmtKind::ReadFromBC { lhs, query, .. } => (
+                lhs,
+                match query.as_str() {
+                    "BLOCKNUMBER" => Some(Type::BNum),
+                    "CHAINID" => Some(Type::Uint32),
+                    "TIMESTAMP" => Some(Type::Option(boxed(Type::Uint64))),
+                    _ => None,
+                },
+            ),
+            _ => return,
+        };
+        match ty {
+            Some(ty) => env.insert(lhs.clone(), ty),
+            None => env.remove(lhs),
+        };
+    }
+
+    /// Walk `stmts` in order, calling `f` on every statement with the environment in effect
+    /// before it.
+    pub fn walk_stmts<'s>(
+        &self,
+        stmts: &'s [Stmt],
+        env: &mut Env,
+        f: &mut impl FnMut(&'s Stmt, &Env),
+    ) {
+        for stmt in stmts {
+            f(stmt, env);
+            if let StmtKind::Match { scrutinee, arms } = &stmt.kind {
+                let scrutinee = env.get(scrutinee).cloned();
+                for (pattern, body) in arms {
+                    let mut env = env.clone();
+                    self.bind_pattern(pattern, scrutinee.as_ref(), &mut env);
+                    self.walk_stmts(body, &mut env, f);
+                }
+            } else {
+                self.bind_stmt(stmt, env);
+            }
+        }
+    }
+
+    /// Walk `expr`, calling `f` on every sub-expression with the environment in effect for it.
+    pub fn walk_expr<'e>(&self, expr: &'e Expr, env: &Env, f: &mut impl FnMut(&'e Expr, &Env)) {
+        f(expr, env);
+        match &expr.kind {
+            ExprKind::Let {
+                name,
+                ty,
+                value,
+                body,
+            } => {
+                self.walk_expr(value, env, f);
+                let mut env = env.clone();
+                if let Some(ty) = ty.clone().or_else(|| self.infer(value, &env)) {
+                    env.insert(name.clone(), ty);
+                }
+                self.walk_expr(body, &env, f);
+            }
+            ExprKind::Fun { param, ty, body } => {
+                let mut env = env.clone();
+                env.insert(param.clone(), ty.clone());
+                self.walk_expr(body, &env, f);
+            }
+            ExprKind::Match { scrutinee, arms } => {
+                let scrutinee = self.lookup(scrutinee, env);
+                for (pattern, arm) in arms {
+                    let mut env = env.clone();
+                    self.bind_pattern(pattern, scrutinee.as_ref(), &mut env);
+                    self.walk_expr(arm, &env, f);
+                }
+            }
+            ExprKind::TFun { body, .. } => self.walk_expr(body, env, f),
+            ExprKind::Message(entries) => {
+                for entry in entries {
+                    self.walk_expr(&entry.value, env, f);
+                }
+            }
+            ExprKind::Literal(_)
+            | ExprKind::Var(_)
+            | ExprKind::App { .. }
+            | ExprKind::Constructor { .. }
+            | ExprKind::Builtin { .. }
+            | ExprKind::TApp { .. } => {}
+        }
+    }
+}
+
+/// A message literal (a message, an event or an exception) constructed by a component.
+pub(crate) struct Construction<'a> {
+    pub component: &'a Component,
+    /// Entries of the literal with the inferred types of their values.
+    pub entries: Vec<(&'a MessageEntry, Option<Type>)>,
+}
+
+impl Construction<'_> {
+    pub fn entry(&self, name: &str) -> Option<&MessageEntry> {
+        self.entries
+            .iter()
+            .map(|(entry, _)| *entry)
+            .find(|entry| entry.name == name)
+    }
+
+    /// Entries other than `reserved` with their inferred types.
+    pub fn params(&self, reserved: &[&str]) -> Vec<InferredField> {
+        self.entries
+            .iter()
+            .filter(|(entry, _)| !reserved.contains(&entry.name.as_str()))
+            .map(|(entry, ty)| InferredField {
+                name: entry.name.clone(),
+                r#type: ty.clone(),
+            })
+            .collect()
+    }
+
+    /// The value of the `name` entry, if it is a string literal.
+    pub fn string_entry(&self, name: &str) -> Option<&str> {
+        match &self.entry(name)?.value.kind {
+            ExprKind::Literal(Literal::String(s)) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Context<'a> {
+    /// Names of the components that are in `components` or call one of them, directly or
+    /// indirectly, in declaration order.
+    pub fn reaching(&self, components: &[String]) -> Vec<String> {
+        let contract = &self.module.contract;
+        contract
+            .components
+            .iter()
+            .filter(|c| {
+                contract
+                    .reachable_from(c)
+                    .iter()
+                    .any(|reached| components.contains(&reached.name))
+            })
+            .map(|c| c.name.clone())
+            .collect()
+    }
+
+    /// All message literals constructed by the transitions and procedures of the contract,
+    /// including those constructed by the library functions they use.
+    pub fn constructions(&self) -> Vec<Construction<'a>> {
+        let library: HashMap<&str, &Expr> = self
+            .module
+            .library
+            .iter()
+            .flat_map(|lib| &lib.entries)
+            .filter_map(|entry| match entry {
+                LibEntry::Let { name, expr, .. } => Some((name.as_str(), expr)),
+                LibEntry::Type { .. } => None,
+            })
+            .collect();
+
+        let mut constructions = vec![];
+        for component in &self.module.contract.components {
+            let mut visited = vec![];
+            let mut found = |expr: &'a Expr, env: &Env, refs: &mut Vec<&'a str>| match &expr.kind {
+                ExprKind::Message(entries) => constructions.push(Construction {
+                    component,
+                    entries: entries
+                        .iter()
+                        .map(|entry| (entry, self.infer(&entry.value, env)))
+                        .collect(),
+                }),
+                ExprKind::Var(name)
+                | ExprKind::App { func: name, .. }
+                | ExprKind::TApp { func: name, .. }
+                    if library.contains_key(name.as_str()) && !env.contains_key(name) =>
+                {
+                    refs.push(name)
+                }
+                _ => {}
+            };
+
+            let mut refs = vec![];
+            self.walk_stmts(
+                &component.body,
+                &mut self.component_env(component),
+                &mut |stmt, env| {
+                    if let StmtKind::Bind { expr, .. } = &stmt.kind {
+                        self.walk_expr(expr, env, &mut |e, env| found(e, env, &mut refs));
+                    }
+                },
+            );
+            while let Some(name) = refs.pop() {
+                if visited.contains(&name) {
+                    continue;
+                }
+                visited.push(name);
+                let mut nested = vec![];
+                self.walk_expr(library[name], &Env::new(), &mut |e, env| {
+                    found(e, env, &mut nested)
+                });
+                refs.extend(nested);
+            }
+        }
+        constructions
+    }
+}
+
+/// Result type of a builtin given the types of its arguments.
+fn builtin_type(name: &str, args: &[Option<Type>]) -> Option<Type> {
+    let arg = |i: usize| args.get(i).cloned().flatten();
+    let option = |ty: Type| Some(Type::Option(boxed(ty)));
+    match name {
+        "eq" | "lt" | "blt" | "contains" | "schnorr_verify" | "ecdsa_verify" => Some(Type::Bool),
+        "add" | "sub" | "mul" | "div" | "rem" | "pow" | "isqrt" => arg(0),
+        "remove" | "put" => arg(0),
+        "concat" => match (arg(0)?, arg(1)) {
+            (Type::ByStr(a), Some(Type::ByStr(b))) => Some(Type::ByStr(a + b)),
+            (Type::ByStr(_), _) => None,
+            (ty, _) => Some(ty),
+        },
+        "badd" => Some(Type::BNum),
+        "bsub" => Some(Type::Int256),
+        "size" | "strlen" => Some(Type::Uint32),
+        "substr" | "to_string" | "to_ascii" => Some(Type::String),
+        "sha256hash" | "keccak256hash" => Some(Type::ByStr(32)),
+        "ripemd160hash" => Some(Type::ByStr(20)),
+        "to_bystr" => Some(named("ByStr")),
+        "to_nat" => Some(named("Nat")),
+        "bech32_to_bystr20" => option(Type::ByStr(20)),
+        "to_int32" => option(Type::Int32),
+        "to_int64" => option(Type::Int64),
+        "to_int128" => option(Type::Int128),
+        "to_int256" => option(Type::Int256),
+        "to_uint32" => option(Type::Uint32),
+        "to_uint64" => option(Type::Uint64),
+        "to_uint128" => option(Type::Uint128),
+        "to_uint256" => option(Type::Uint256),
+        "get" => match arg(0)? {
+            Type::Map(_, v) => option(*v),
+            _ => None,
+        },
+        "to_list" => match arg(0)? {
+            Type::Map(k, v) => Some(Type::List(boxed(Type::Pair(k, v)))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_expr, parse_module};
+
+    #[test]
+    fn test_infer_library_and_builtins() {
+        let module = parse_module(
+            r#"scilla_version 0
+            import PairUtils
+            library L
+            let one_msg = fun (msg : Message) => let nil = Nil {Message} in Cons {Message} msg nil
+            contract C ()"#,
+        )
+        .unwrap();
+        let context = Context::new(&module);
+        let env: Env = [
+            (
+                "m".to_string(),
+                Type::Map(boxed(Type::ByStr(20)), boxed(Type::Uint128)),
+            ),
+            ("a".to_string(), Type::ByStr(20)),
+            ("h".to_string(), Type::ByStr(32)),
+            ("s".to_string(), Type::String),
+        ]
+        .into_iter()
+        .collect();
+
+        let infer = |src: &str| context.infer(&parse_expr(src).unwrap(), &env);
+        assert_eq!(
+            infer("one_msg m"),
+            Some(Type::List(boxed(named("Message"))))
+        );
+        assert_eq!(
+            infer("builtin get m m"),
+            Some(Type::Option(boxed(Type::Uint128)))
+        );
+        assert_eq!(infer("builtin concat a h"), Some(Type::ByStr(52)));
+        assert_eq!(infer("builtin concat s s"), Some(Type::String));
+        assert_eq!(
+            infer("let f = @fst Uint32 String in f"),
+            Some(Type::Function(
+                boxed(Type::Pair(boxed(Type::Uint32), boxed(Type::String))),
+                boxed(Type::Uint32)
+            ))
+        );
+    }
+}
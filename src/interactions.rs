@@ -0,0 +1,440 @@
+//! Ordering of the interactions of transitions: the state writes, sent messages and events along
+//! each control-flow path, to check the checks-effects-interactions pattern.
+
+use std::fmt::Display;
+
+use crate::{
+    ast::{ContractDef, Span, Stmt, StmtKind},
+    Contract, Error,
+};
+
+/// The paths of a transition are no longer enumerated past this number.
+const MAX_PATHS: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepKind {
+    /// A field written with `f := x`, updated with `m[k] := v` or deleted from.
+    Write(String),
+    Send,
+    Event,
+}
+
+/// A write, `send` or `event` statement executed by a transition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub kind: StepKind,
+    /// The transition or procedure containing the statement.
+    pub component: String,
+    pub span: Span,
+}
+
+impl Display for Step {
+    /// `write balances`, `send` or `event`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            StepKind::Write(field) => write!(f, "write {field}"),
+            StepKind::Send => write!(f, "send"),
+            StepKind::Event => write!(f, "event"),
+        }
+    }
+}
+
+/// The steps of a transition along each of its control-flow paths. Paths ending with a `throw`
+/// are left out, since their changes are reverted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionOrder {
+    pub transition: String,
+    /// The distinct sequences of steps, in the order of the branches of the transition.
+    pub paths: Vec<Vec<Step>>,
+    /// Whether the transition has more than 1024 distinct paths, in which case only the first
+    /// ones are listed.
+    pub truncated: bool,
+}
+
+impl Display for TransitionOrder {
+    /// One line per path, e.g. `Transfer: write balances -> event -> send`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for path in &self.paths {
+            let steps: Vec<String> = path.iter().map(ToString::to_string).collect();
+            if steps.is_empty() {
+                writeln!(f, "{}: nothing", self.transition)?;
+            } else {
+                writeln!(f, "{}: {}", self.transition, steps.join(" -> "))?;
+            }
+        }
+        if self.truncated {
+            writeln!(f, "{}: ...", self.transition)?;
+        }
+        Ok(())
+    }
+}
+
+/// A problem in the order of the interactions of a transition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteractionWarning {
+    /// A field is written after a message was sent, on a path that does not throw. The message
+    /// is only delivered once the transition finishes, so its recipient, and the callbacks it
+    /// sends, see the value written after the `send`.
+    WriteAfterSend {
+        transition: String,
+        send: Step,
+        /// The field written by `write`.
+        field: String,
+        write: Step,
+    },
+    /// A transition receiving a callback of the messages sent by `transition` reads fields that
+    /// `transition` writes, i.e. state set in the middle of the chain of messages.
+    CallbackObservesState {
+        transition: String,
+        callback: String,
+        fields: Vec<String>,
+    },
+}
+
+impl Display for InteractionWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InteractionWarning::WriteAfterSend {
+                transition,
+                send,
+                field,
+                write,
+            } => write!(
+                f,
+                "`{transition}` writes `{field}` at line {} after sending a message at line {}",
+                write.span.line, send.span.line
+            ),
+            InteractionWarning::CallbackObservesState {
+                transition,
+                callback,
+                fields,
+            } => write!(
+                f,
+                "callback `{callback}` of `{transition}` reads intermediate state written by it: {}",
+                fields.join(", ")
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InteractionWarningList(pub Vec<InteractionWarning>);
+
+impl std::ops::Deref for InteractionWarningList {
+    type Target = Vec<InteractionWarning>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl InteractionWarningList {
+    /// The warnings about the transition `name`.
+    pub fn of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a InteractionWarning> {
+        self.iter().filter(move |w| match w {
+            InteractionWarning::WriteAfterSend { transition, .. }
+            | InteractionWarning::CallbackObservesState { transition, .. } => transition == name,
+        })
+    }
+}
+
+/// Follows the statements of a transition and of the procedures it calls.
+struct Paths<'a> {
+    contract: &'a ContractDef,
+    stack: Vec<&'a str>,
+    truncated: bool,
+}
+
+impl<'a> Paths<'a> {
+    fn stmts(
+        &mut self,
+        component: &'a str,
+        stmts: &'a [Stmt],
+        paths: Vec<Vec<Step>>,
+    ) -> Vec<Vec<Step>> {
+        let mut paths = paths;
+        for stmt in stmts {
+            if paths.is_empty() {
+                break;
+            }
+            let step = |kind| Step {
+                kind,
+                component: component.to_string(),
+                span: stmt.span,
+            };
+            let push = |paths: &mut Vec<Vec<Step>>, step: Step| {
+                paths.iter_mut().for_each(|path| path.push(step.clone()))
+            };
+            match &stmt.kind {
+                StmtKind::Store { field, .. }
+                | StmtKind::MapUpdate { map: field, .. }
+                | StmtKind::MapDelete { map: field, .. } => {
+                    push(&mut paths, step(StepKind::Write(field.clone())))
+                }
+                StmtKind::Send(_) => push(&mut paths, step(StepKind::Send)),
+                StmtKind::Event(_) => push(&mut paths, step(StepKind::Event)),
+                StmtKind::Throw(_) => paths.clear(),
+                StmtKind::Match { arms, .. } => {
+                    let mut after: Vec<Vec<Step>> = vec![];
+                    for (_, arm) in arms {
+                        for path in self.stmts(component, arm, paths.clone()) {
+                            if after.len() == MAX_PATHS {
+                                self.truncated = true;
+                                break;
+                            }
+                            if !after.contains(&path) {
+                                after.push(path);
+                            }
+                        }
+                    }
+                    paths = after;
+                }
+                StmtKind::CallProc { name, .. } => paths = self.call(name, paths),
+                StmtKind::Iterate { proc, .. } => {
+                    // The list may be empty. A single iteration is listed.
+                    let mut iterated = self.call(proc, paths.clone());
+                    iterated.retain(|path| !paths.contains(path));
+                    paths.extend(iterated);
+                    if paths.len() > MAX_PATHS {
+                        paths.truncate(MAX_PATHS);
+                        self.truncated = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        paths
+    }
+
+    fn call(&mut self, name: &'a str, paths: Vec<Vec<Step>>) -> Vec<Vec<Step>> {
+        match self.contract.component(name) {
+            Some(proc) if !self.stack.contains(&name) => {
+                self.stack.push(name);
+                let paths = self.stmts(&proc.name, &proc.body, paths);
+                self.stack.pop();
+                paths
+            }
+            _ => paths,
+        }
+    }
+}
+
+/// The sends of a path that does not throw, and the writes following them.
+#[derive(Clone, Default)]
+struct Flow {
+    sent: Vec<Step>,
+    /// The sends and the writes following them, with the written field.
+    writes_after_send: Vec<(Step, String, Step)>,
+}
+
+fn merge<T: PartialEq>(into: &mut Vec<T>, from: Vec<T>) {
+    for item in from {
+        if !into.contains(&item) {
+            into.push(item);
+        }
+    }
+}
+
+/// Like [`Paths`], but merging the paths at the end of each `match`, so that their number does
+/// not matter.
+struct SendFlow<'a> {
+    contract: &'a ContractDef,
+    stack: Vec<&'a str>,
+}
+
+impl<'a> SendFlow<'a> {
+    fn stmts(&mut self, component: &'a str, stmts: &'a [Stmt], flow: Flow) -> Option<Flow> {
+        let mut flow = flow;
+        for stmt in stmts {
+            let step = |kind| Step {
+                kind,
+                component: component.to_string(),
+                span: stmt.span,
+            };
+            match &stmt.kind {
+                StmtKind::Store { field, .. }
+                | StmtKind::MapUpdate { map: field, .. }
+                | StmtKind::MapDelete { map: field, .. } => {
+                    let write = step(StepKind::Write(field.clone()));
+                    let pairs = flow
+                        .sent
+                        .iter()
+                        .map(|send| (send.clone(), field.clone(), write.clone()));
+                    merge(&mut flow.writes_after_send, pairs.collect());
+                }
+                StmtKind::Send(_) => merge(&mut flow.sent, vec![step(StepKind::Send)]),
+                StmtKind::Throw(_) => return None,
+                StmtKind::Match { arms, .. } => {
+                    let mut after: Option<Flow> = None;
+                    for (_, arm) in arms {
+                        if let Some(arm) = self.stmts(component, arm, flow.clone()) {
+                            let merged = after.get_or_insert_with(Flow::default);
+                            merge(&mut merged.sent, arm.sent);
+                            merge(&mut merged.writes_after_send, arm.writes_after_send);
+                        }
+                    }
+                    flow = after?;
+                }
+                StmtKind::CallProc { name, .. } => flow = self.call(name, flow)?,
+                StmtKind::Iterate { proc, .. } => {
+                    // The list may be empty, and the procedure may run several times.
+                    if let Some(once) = self.call(proc, flow.clone()) {
+                        let twice = self.call(proc, once.clone()).unwrap_or(once);
+                        merge(&mut flow.sent, twice.sent);
+                        merge(&mut flow.writes_after_send, twice.writes_after_send);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Some(flow)
+    }
+
+    fn call(&mut self, name: &'a str, flow: Flow) -> Option<Flow> {
+        match self.contract.component(name) {
+            Some(proc) if !self.stack.contains(&name) => {
+                self.stack.push(name);
+                let flow = self.stmts(&proc.name, &proc.body, flow);
+                self.stack.pop();
+                flow
+            }
+            _ => Some(flow),
+        }
+    }
+}
+
+impl Contract {
+    /// The writes, sends and events of each transition along each control-flow path, following
+    /// the procedures it calls.
+    pub fn transition_orders(&self) -> Result<Vec<TransitionOrder>, Error> {
+        let module = self.syntax_tree()?;
+        let orders = module
+            .contract
+            .transitions()
+            .map(|transition| {
+                let mut paths = Paths {
+                    contract: &module.contract,
+                    stack: vec![],
+                    truncated: false,
+                };
+                let found = paths.stmts(&transition.name, &transition.body, vec![vec![]]);
+                TransitionOrder {
+                    transition: transition.name.clone(),
+                    paths: found,
+                    truncated: paths.truncated,
+                }
+            })
+            .collect();
+        Ok(orders)
+    }
+
+    /// Check the order of the interactions of each transition: fields written after sending a
+    /// message, and callbacks reading fields written by the transition whose messages they answer.
+    ///
+    /// A transition is a callback of the messages tagged `Tag` if it is named `Tag` and the
+    /// message is sent to `_this_address`, or if its name starts with `Tag` followed by a
+    /// `CallBack` suffix, e.g. `DelegateStakeSuccessCallBack`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Vault ()
+    ///     field pending : Uint128 = Uint128 0
+    ///     transition Withdraw (amount : Uint128)
+    ///       msg = {_tag : \"Withdraw\"; _recipient : _sender; _amount : amount};
+    ///       nil = Nil {Message};
+    ///       msgs = Cons {Message} msg nil;
+    ///       send msgs;
+    ///       pending := amount
+    ///     end
+    ///     transition WithdrawCallBack ()
+    ///       p <- pending
+    ///     end",
+    /// )
+    /// .unwrap();
+    /// let warnings: Vec<String> = contract
+    ///     .interaction_warnings()
+    ///     .unwrap()
+    ///     .iter()
+    ///     .map(ToString::to_string)
+    ///     .collect();
+    /// assert_eq!(
+    ///     warnings,
+    ///     vec![
+    ///         "`Withdraw` writes `pending` at line 9 after sending a message at line 8",
+    ///         "callback `WithdrawCallBack` of `Withdraw` reads intermediate state written by it: pending",
+    ///     ]
+    /// );
+    /// ```
+    pub fn interaction_warnings(&self) -> Result<InteractionWarningList, Error> {
+        let contract = &self.syntax_tree()?.contract;
+        let mut warnings = vec![];
+        let messages = self.messages()?;
+        let effects = self.effects()?;
+        for transition in contract.transitions() {
+            let mut flow = SendFlow {
+                contract,
+                stack: vec![],
+            };
+            let found = flow.stmts(&transition.name, &transition.body, Flow::default());
+            for (send, field, write) in found.map(|f| f.writes_after_send).unwrap_or_default() {
+                // Each write is reported once, with the first send before it.
+                if warnings.iter().any(|w| {
+                    matches!(w, InteractionWarning::WriteAfterSend { transition: t, write: w, .. }
+                        if *t == transition.name && w.span == write.span)
+                }) {
+                    continue;
+                }
+                warnings.push(InteractionWarning::WriteAfterSend {
+                    transition: transition.name.clone(),
+                    send,
+                    field,
+                    write,
+                });
+            }
+
+            let Some(written) = effects.of(&transition.name) else {
+                continue;
+            };
+            let mut callbacks: Vec<&str> = vec![];
+            for message in messages.sent_by(&transition.name) {
+                let Some(tag) = &message.tag else {
+                    continue;
+                };
+                for callback in contract.transitions() {
+                    let is_callback = match callback.name.strip_prefix(tag.as_str()) {
+                        Some("") => message.recipient == "_this_address",
+                        Some(suffix) => suffix.to_lowercase().contains("callback"),
+                        None => false,
+                    };
+                    if is_callback
+                        && callback.name != transition.name
+                        && !callbacks.contains(&callback.name.as_str())
+                    {
+                        callbacks.push(&callback.name);
+                    }
+                }
+            }
+            for callback in callbacks {
+                let fields: Vec<String> = effects
+                    .of(callback)
+                    .map(|e| e.reads.clone())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|field| written.modifies(field))
+                    .collect();
+                if !fields.is_empty() {
+                    warnings.push(InteractionWarning::CallbackObservesState {
+                        transition: transition.name.clone(),
+                        callback: callback.to_string(),
+                        fields,
+                    });
+                }
+            }
+        }
+        Ok(InteractionWarningList(warnings))
+    }
+}
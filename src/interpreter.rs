@@ -0,0 +1,1085 @@
+//! An interpreter of the pure expressions of Scilla: literals, `let`, functions, applications,
+//! `match`, constructors and builtins. It evaluates library values and applies library functions
+//! to [`Value`]s without a blockchain, e.g. to unit test them. Integer arithmetic is checked as in
+//! Scilla: an overflow or a division by zero is an error.
+
+use std::{cell::Cell, collections::HashMap, rc::Rc, sync::OnceLock};
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{Signed, ToPrimitive, Zero};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+use crate::{
+    ast::{Expr, ExprKind, LibEntry, Literal, Pattern},
+    infer::substitute,
+    parser::{parse_expr, parse_type},
+    value::{decode_hex, int_bounds},
+    Contract, Error, Type, Value,
+};
+
+/// `to_nat` fails above this number, since naturals are nested `Succ` constructors which are
+/// cloned and dropped recursively.
+const MAX_NAT: u32 = 1000;
+
+/// Applying a function fails when the applications it is nested in use more than this many bytes
+/// of the stack, e.g. when folding a long list with `list_foldk`, instead of overflowing it. Threads
+/// spawned by the standard library, including test threads, have 2 MiB of stack; the rest is left
+/// for cloning and dropping nested values such as naturals.
+const MAX_STACK: usize = 512 * 1024;
+
+thread_local! {
+    /// The address on the stack of the outermost function application being evaluated on this
+    /// thread.
+    static STACK_START: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// The standard library functions written in Scilla, on top of the recursion principles
+/// `list_foldl`, `list_foldr`, `list_foldk` and `nat_fold`. Each one may use the previous ones.
+const PRELUDE: &[(&str, &str)] = &[
+    (
+        "negb",
+        "fun (b : Bool) => match b with | True => False | False => True end",
+    ),
+    (
+        "andb",
+        "fun (b : Bool) => fun (c : Bool) => match b with | False => False | True => c end",
+    ),
+    (
+        "orb",
+        "fun (b : Bool) => fun (c : Bool) => match b with | True => True | False => c end",
+    ),
+    (
+        "xorb",
+        "fun (b : Bool) => fun (c : Bool) => match b with | True => negb c | False => c end",
+    ),
+    (
+        "bool_to_string",
+        "fun (b : Bool) => match b with | True => \"True\" | False => \"False\" end",
+    ),
+    (
+        "fst",
+        "tfun 'A => tfun 'B => fun (p : Pair 'A 'B) => match p with | Pair a b => a end",
+    ),
+    (
+        "snd",
+        "tfun 'A => tfun 'B => fun (p : Pair 'A 'B) => match p with | Pair a b => b end",
+    ),
+    (
+        "list_length",
+        "tfun 'A => fun (l : List 'A) =>
+          let f = fun (n : Uint32) => fun (h : 'A) => let one = Uint32 1 in builtin add n one in
+          let zero = Uint32 0 in
+          let fold = @list_foldl 'A Uint32 in
+          fold f zero l",
+    ),
+    (
+        "list_is_empty",
+        "tfun 'A => fun (l : List 'A) => match l with | Cons _ _ => False | Nil => True end",
+    ),
+    (
+        "list_head",
+        "tfun 'A => fun (l : List 'A) =>
+          match l with | Cons h _ => Some {'A} h | Nil => None {'A} end",
+    ),
+    (
+        "list_tail",
+        "tfun 'A => fun (l : List 'A) =>
+          match l with | Cons _ t => Some {(List 'A)} t | Nil => None {(List 'A)} end",
+    ),
+    (
+        "list_append",
+        "tfun 'A => fun (l1 : List 'A) => fun (l2 : List 'A) =>
+          let f = fun (h : 'A) => fun (acc : List 'A) => Cons {'A} h acc in
+          let fold = @list_foldr 'A (List 'A) in
+          fold f l2 l1",
+    ),
+    (
+        "list_reverse",
+        "tfun 'A => fun (l : List 'A) =>
+          let f = fun (acc : List 'A) => fun (h : 'A) => Cons {'A} h acc in
+          let nil = Nil {'A} in
+          let fold = @list_foldl 'A (List 'A) in
+          fold f nil l",
+    ),
+    (
+        "list_flatten",
+        "tfun 'A => fun (l : List (List 'A)) =>
+          let append = @list_append 'A in
+          let f = fun (h : List 'A) => fun (acc : List 'A) => append h acc in
+          let nil = Nil {'A} in
+          let fold = @list_foldr (List 'A) (List 'A) in
+          fold f nil l",
+    ),
+    (
+        "list_map",
+        "tfun 'A => tfun 'B => fun (g : 'A -> 'B) => fun (l : List 'A) =>
+          let f = fun (h : 'A) => fun (acc : List 'B) => let x = g h in Cons {'B} x acc in
+          let nil = Nil {'B} in
+          let fold = @list_foldr 'A (List 'B) in
+          fold f nil l",
+    ),
+    (
+        "list_filter",
+        "tfun 'A => fun (p : 'A -> Bool) => fun (l : List 'A) =>
+          let f = fun (h : 'A) => fun (acc : List 'A) =>
+            let keep = p h in
+            match keep with | True => Cons {'A} h acc | False => acc end in
+          let nil = Nil {'A} in
+          let fold = @list_foldr 'A (List 'A) in
+          fold f nil l",
+    ),
+    (
+        "list_exists",
+        "tfun 'A => fun (p : 'A -> Bool) => fun (l : List 'A) =>
+          let f = fun (acc : Bool) => fun (h : 'A) => let found = p h in orb acc found in
+          let init = False in
+          let fold = @list_foldl 'A Bool in
+          fold f init l",
+    ),
+    (
+        "list_forall",
+        "tfun 'A => fun (p : 'A -> Bool) => fun (l : List 'A) =>
+          let f = fun (acc : Bool) => fun (h : 'A) => let ok = p h in andb acc ok in
+          let init = True in
+          let fold = @list_foldl 'A Bool in
+          fold f init l",
+    ),
+    (
+        "list_find",
+        "tfun 'A => fun (p : 'A -> Bool) => fun (l : List 'A) =>
+          let f = fun (acc : Option 'A) => fun (h : 'A) => fun (k : Option 'A -> Option 'A) =>
+            let found = p h in
+            match found with | True => Some {'A} h | False => k acc end in
+          let init = None {'A} in
+          let fold = @list_foldk 'A (Option 'A) in
+          fold f init l",
+    ),
+    (
+        "list_mem",
+        "tfun 'A => fun (f : 'A -> 'A -> Bool) => fun (x : 'A) => fun (l : List 'A) =>
+          let p = fun (h : 'A) => f x h in
+          let exists_in = @list_exists 'A in
+          exists_in p l",
+    ),
+    (
+        "list_zip",
+        "tfun 'A => tfun 'B => fun (l1 : List 'A) => fun (l2 : List 'B) =>
+          let f = fun (acc : Pair (List 'B) (List (Pair 'A 'B))) => fun (h : 'A) =>
+            fun (k : Pair (List 'B) (List (Pair 'A 'B)) -> Pair (List 'B) (List (Pair 'A 'B))) =>
+            match acc with
+            | Pair (Cons h2 t2) zipped =>
+              let p = Pair {'A 'B} h h2 in
+              let zipped = Cons {(Pair 'A 'B)} p zipped in
+              let acc = Pair {(List 'B) (List (Pair 'A 'B))} t2 zipped in
+              k acc
+            | Pair Nil _ => acc
+            end in
+          let nil = Nil {(Pair 'A 'B)} in
+          let init = Pair {(List 'B) (List (Pair 'A 'B))} l2 nil in
+          let fold = @list_foldk 'A (Pair (List 'B) (List (Pair 'A 'B))) in
+          let result = fold f init l1 in
+          match result with
+          | Pair _ zipped => let reverse = @list_reverse (Pair 'A 'B) in reverse zipped
+          end",
+    ),
+    (
+        "list_unzip",
+        "tfun 'A => tfun 'B => fun (l : List (Pair 'A 'B)) =>
+          let f = fun (h : Pair 'A 'B) => fun (acc : Pair (List 'A) (List 'B)) =>
+            match h with
+            | Pair a b =>
+              match acc with
+              | Pair la lb =>
+                let la = Cons {'A} a la in
+                let lb = Cons {'B} b lb in
+                Pair {(List 'A) (List 'B)} la lb
+              end
+            end in
+          let nil_a = Nil {'A} in
+          let nil_b = Nil {'B} in
+          let init = Pair {(List 'A) (List 'B)} nil_a nil_b in
+          let fold = @list_foldr (Pair 'A 'B) (Pair (List 'A) (List 'B)) in
+          fold f init l",
+    ),
+    (
+        "list_nth",
+        "tfun 'A => fun (n : Uint32) => fun (l : List 'A) =>
+          let f = fun (acc : Pair Uint32 (Option 'A)) => fun (h : 'A) =>
+            fun (k : Pair Uint32 (Option 'A) -> Pair Uint32 (Option 'A)) =>
+            match acc with
+            | Pair i _ =>
+              let found = builtin eq i n in
+              match found with
+              | True => let h = Some {'A} h in Pair {Uint32 (Option 'A)} i h
+              | False =>
+                let one = Uint32 1 in
+                let i = builtin add i one in
+                let none = None {'A} in
+                let acc = Pair {Uint32 (Option 'A)} i none in
+                k acc
+              end
+            end in
+          let zero = Uint32 0 in
+          let none = None {'A} in
+          let init = Pair {Uint32 (Option 'A)} zero none in
+          let fold = @list_foldk 'A (Pair Uint32 (Option 'A)) in
+          let result = fold f init l in
+          match result with | Pair _ nth => nth end",
+    ),
+    (
+        "list_sort",
+        "tfun 'A => fun (lt : 'A -> 'A -> Bool) => fun (l : List 'A) =>
+          let filter = @list_filter 'A in
+          let append = @list_append 'A in
+          let insert = fun (sorted : List 'A) => fun (x : 'A) =>
+            let before = fun (h : 'A) => let after = lt x h in negb after in
+            let after = fun (h : 'A) => lt x h in
+            let l1 = filter before sorted in
+            let l2 = filter after sorted in
+            let l2 = Cons {'A} x l2 in
+            append l1 l2 in
+          let nil = Nil {'A} in
+          let fold = @list_foldl 'A (List 'A) in
+          fold insert nil l",
+    ),
+    (
+        "list_eq",
+        "tfun 'A => fun (f : 'A -> 'A -> Bool) => fun (l1 : List 'A) => fun (l2 : List 'A) =>
+          let g = fun (acc : Pair (List 'A) Bool) => fun (h : 'A) =>
+            fun (k : Pair (List 'A) Bool -> Pair (List 'A) Bool) =>
+            match acc with
+            | Pair (Cons h2 t2) _ =>
+              let same = f h h2 in
+              let acc = Pair {(List 'A) Bool} t2 same in
+              match same with | True => k acc | False => acc end
+            | Pair Nil _ => let no = False in Pair {(List 'A) Bool} l2 no
+            end in
+          let yes = True in
+          let init = Pair {(List 'A) Bool} l2 yes in
+          let fold = @list_foldk 'A (Pair (List 'A) Bool) in
+          let result = fold g init l1 in
+          match result with
+          | Pair Nil True => True
+          | Pair _ _ => False
+          end",
+    ),
+    (
+        "nat_prev",
+        "fun (n : Nat) => match n with | Succ m => Some {Nat} m | Zero => None {Nat} end",
+    ),
+    (
+        "nat_eq",
+        "fun (n : Nat) => fun (m : Nat) =>
+          let f = fun (acc : Option Nat) => fun (i : Nat) =>
+            match acc with | Some k => nat_prev k | None => None {Nat} end in
+          let init = Some {Nat} m in
+          let fold = @nat_fold (Option Nat) in
+          let result = fold f init n in
+          match result with | Some Zero => True | _ => False end",
+    ),
+    (
+        "nat_to_int",
+        "fun (n : Nat) =>
+          let f = fun (acc : Uint32) => fun (i : Nat) => let one = Uint32 1 in builtin add acc one in
+          let zero = Uint32 0 in
+          let fold = @nat_fold Uint32 in
+          fold f zero n",
+    ),
+];
+
+const INT_TYPES: [&str; 8] = [
+    "Int32", "Int64", "Int128", "Int256", "Uint32", "Uint64", "Uint128", "Uint256",
+];
+
+/// The parsed standard library, including the integer comparisons of `IntUtils` such as
+/// `uint128_le`.
+fn prelude() -> &'static [(String, Expr)] {
+    static PRELUDE_EXPRS: OnceLock<Vec<(String, Expr)>> = OnceLock::new();
+    PRELUDE_EXPRS.get_or_init(|| {
+        let comparisons = INT_TYPES.iter().flat_map(|ty| {
+            [
+                ("eq", "builtin eq a b"),
+                ("lt", "builtin lt a b"),
+                (
+                    "le",
+                    "let x = builtin lt a b in let y = builtin eq a b in orb x y",
+                ),
+                ("gt", "builtin lt b a"),
+                (
+                    "ge",
+                    "let x = builtin lt b a in let y = builtin eq a b in orb x y",
+                ),
+            ]
+            .map(|(op, body)| {
+                (
+                    format!("{}_{op}", ty.to_lowercase()),
+                    format!("fun (a : {ty}) => fun (b : {ty}) => {body}"),
+                )
+            })
+        });
+        PRELUDE
+            .iter()
+            .map(|(name, src)| (name.to_string(), src.to_string()))
+            .chain(comparisons)
+            .map(|(name, src)| {
+                let expr = parse_expr(&src).expect("the prelude is valid Scilla");
+                (name, expr)
+            })
+            .collect()
+    })
+}
+
+/// A value computed by the interpreter.
+#[derive(Debug, Clone)]
+pub(crate) enum Val<'a> {
+    Value(Value),
+    /// A `fun` or `tfun` expression and the scope it was defined in.
+    Closure(Rc<Closure<'a>>),
+    /// A recursion principle of the standard library and the arguments applied to it so far.
+    Recursion(Recursion, Vec<Val<'a>>),
+}
+
+#[derive(Debug)]
+pub(crate) struct Closure<'a> {
+    expr: &'a Expr,
+    scope: Env<'a>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Recursion {
+    ListFoldl,
+    ListFoldr,
+    ListFoldk,
+    /// The continuation passed to the function folded by `list_foldk`, applied to the rest of
+    /// the list.
+    ListFoldkRest,
+    NatFold,
+}
+
+/// The values and the instantiated type variables in scope.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Env<'a> {
+    pub values: HashMap<String, Val<'a>>,
+    types: Vec<(String, Type)>,
+}
+
+impl<'a> Env<'a> {
+    pub fn insert(&mut self, name: &str, value: Val<'a>) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    pub fn lookup(&self, name: &str) -> Result<Val<'a>, Error> {
+        self.values
+            .get(name)
+            .cloned()
+            .ok_or_else(|| runtime(format!("undefined variable `{name}`")))
+    }
+
+    /// The value of `name`, which must not be a function.
+    pub fn value(&self, name: &str) -> Result<Value, Error> {
+        match self.lookup(name)? {
+            Val::Value(value) => Ok(value),
+            _ => Err(runtime(format!("`{name}` is a function"))),
+        }
+    }
+
+    /// `ty` with the type variables in scope replaced by their instances.
+    pub fn instantiate(&self, ty: &Type) -> Type {
+        self.types
+            .iter()
+            .rev()
+            .fold(ty.clone(), |ty, (tvar, instance)| {
+                substitute(&ty, tvar, instance)
+            })
+    }
+}
+
+pub(crate) fn runtime(message: String) -> Error {
+    Error::RuntimeError(message)
+}
+
+/// Evaluates library values and applies library functions.
+///
+/// # Examples
+///
+/// ```
+/// use scilla_parser::{Contract, Interpreter, Type, Value};
+/// let contract = Contract::from_source(
+///     "scilla_version 0
+///     library Math
+///     let double = fun (x : Uint128) => builtin add x x
+///     contract Math ()",
+/// )
+/// .unwrap();
+/// let interpreter = Interpreter::new(&contract).unwrap();
+/// let two = Value::int(Type::Uint128, 2).unwrap();
+/// assert_eq!(interpreter.call("double", &[two]).unwrap().to_string(), "Uint128 4");
+///
+/// let max = Value::int(Type::Uint128, u128::MAX).unwrap();
+/// assert!(interpreter.call("double", &[max]).is_err());
+///
+/// let interpreter = interpreter.with_var("x", Value::int(Type::Uint128, 20).unwrap());
+/// assert_eq!(interpreter.eval("let y = double x in builtin lt x y").unwrap(), Value::bool(true));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Interpreter<'a> {
+    scope: Env<'a>,
+}
+
+impl Default for Interpreter<'_> {
+    /// An interpreter of the standard library only.
+    fn default() -> Self {
+        let mut scope = Env::default();
+        for (name, fold) in [
+            ("list_foldl", Recursion::ListFoldl),
+            ("list_foldr", Recursion::ListFoldr),
+            ("list_foldk", Recursion::ListFoldk),
+            ("nat_fold", Recursion::NatFold),
+        ] {
+            scope.insert(name, Val::Recursion(fold, vec![]));
+        }
+        for (name, expr) in prelude() {
+            let value = eval(expr, &scope).expect("the prelude evaluates to functions");
+            scope.insert(name, value);
+        }
+        Self { scope }
+    }
+}
+
+impl<'a> Interpreter<'a> {
+    /// An interpreter of the standard library and the library of `contract`, whose values are
+    /// evaluated in order. Fails if one of them fails to evaluate.
+    pub fn new(contract: &'a Contract) -> Result<Self, Error> {
+        let module = contract.syntax_tree()?;
+        let mut interpreter = Self::default();
+        for entry in module.library.iter().flat_map(|lib| &lib.entries) {
+            if let LibEntry::Let { name, expr, .. } = entry {
+                let value = eval(expr, &interpreter.scope)?;
+                interpreter.scope.insert(name, value);
+            }
+        }
+        Ok(interpreter)
+    }
+
+    /// Bind `name` to `value` in the expressions evaluated next.
+    pub fn with_var(mut self, name: &str, value: Value) -> Self {
+        self.scope.insert(name, Val::Value(value));
+        self
+    }
+
+    /// The value of the library value or variable `name`.
+    pub fn value(&self, name: &str) -> Result<Value, Error> {
+        self.scope.value(name)
+    }
+
+    /// Apply the library function `name` to `args`. Polymorphic functions are applied without
+    /// instantiating their type variables; use [`Interpreter::eval`] with `@f T` for that.
+    pub fn call(&self, name: &str, args: &[Value]) -> Result<Value, Error> {
+        let result = args
+            .iter()
+            .try_fold(self.scope.lookup(name)?, |func, arg| {
+                apply(func, Val::Value(arg.clone()))
+            })?;
+        into_value(result)
+    }
+
+    /// Evaluate the Scilla expression `src`, e.g. `builtin add x y`.
+    pub fn eval(&self, src: &str) -> Result<Value, Error> {
+        let expr = parse_expr(src)?;
+        into_value(eval(&expr, &self.scope)?)
+    }
+
+    pub(crate) fn into_env(self) -> Env<'a> {
+        self.scope
+    }
+}
+
+pub(crate) fn into_value(value: Val) -> Result<Value, Error> {
+    match value {
+        Val::Value(value) => Ok(value),
+        _ => Err(runtime("the result is a function".to_string())),
+    }
+}
+
+/// Evaluate `expr` in `scope`.
+pub(crate) fn eval<'a>(expr: &'a Expr, scope: &Env<'a>) -> Result<Val<'a>, Error> {
+    match &expr.kind {
+        ExprKind::Literal(literal) => Ok(Val::Value(eval_literal(literal, scope)?)),
+        ExprKind::Var(name) => scope.lookup(name),
+        ExprKind::Message(entries) => {
+            let entries = entries
+                .iter()
+                .map(|e| Ok((e.name.clone(), into_value(eval(&e.value, scope)?)?)))
+                .collect::<Result<_, Error>>()?;
+            Ok(Val::Value(Value::Message(entries)))
+        }
+        ExprKind::Let {
+            name, value, body, ..
+        } => {
+            let value = eval(value, scope)?;
+            let mut scope = scope.clone();
+            scope.insert(name, value);
+            eval(body, &scope)
+        }
+        ExprKind::Fun { .. } | ExprKind::TFun { .. } => Ok(Val::Closure(Rc::new(Closure {
+            expr,
+            scope: scope.clone(),
+        }))),
+        ExprKind::App { func, args } => args.iter().try_fold(scope.lookup(func)?, |func, arg| {
+            apply(func, scope.lookup(arg)?)
+        }),
+        ExprKind::Constructor {
+            name,
+            type_args,
+            args,
+        } => Ok(Val::Value(Value::Adt {
+            constructor: name.clone(),
+            type_args: type_args.iter().map(|ty| scope.instantiate(ty)).collect(),
+            args: args
+                .iter()
+                .map(|arg| scope.value(arg))
+                .collect::<Result<_, Error>>()?,
+        })),
+        ExprKind::Builtin { name, args } => {
+            let args = args
+                .iter()
+                .map(|arg| scope.value(arg))
+                .collect::<Result<Vec<_>, Error>>()?;
+            Ok(Val::Value(builtin(name, &args)?))
+        }
+        ExprKind::Match { scrutinee, arms } => {
+            let value = scope.lookup(scrutinee)?;
+            for (pattern, body) in arms {
+                let mut bindings = vec![];
+                if matches(pattern, &value, &mut bindings) {
+                    let mut scope = scope.clone();
+                    for (name, value) in bindings {
+                        scope.insert(&name, value);
+                    }
+                    return eval(body, &scope);
+                }
+            }
+            Err(no_arm(scrutinee, &value))
+        }
+        ExprKind::TApp { func, type_args } => {
+            let not_polymorphic = || runtime(format!("`{func}` is not polymorphic"));
+            type_args
+                .iter()
+                .try_fold(scope.lookup(func)?, |value, ty| match value {
+                    Val::Closure(closure) => match &closure.expr.kind {
+                        ExprKind::TFun { tvar, body } => {
+                            let mut inner = closure.scope.clone();
+                            inner.types.push((tvar.clone(), scope.instantiate(ty)));
+                            eval(body, &inner)
+                        }
+                        _ => Err(not_polymorphic()),
+                    },
+                    // The recursion principles do not depend on the types of their arguments.
+                    Val::Recursion(..) => Ok(value),
+                    Val::Value(_) => Err(not_polymorphic()),
+                })
+        }
+    }
+}
+
+pub(crate) fn no_arm(scrutinee: &str, value: &Val) -> Error {
+    match value {
+        Val::Value(value) => runtime(format!(
+            "no arm of the match on `{scrutinee}` matches {value}"
+        )),
+        _ => runtime(format!("no arm of the match on `{scrutinee}` matches")),
+    }
+}
+
+/// Whether `pattern` matches `value`, adding the values of its binders to `bindings`.
+pub(crate) fn matches<'a>(
+    pattern: &Pattern,
+    value: &Val<'a>,
+    bindings: &mut Vec<(String, Val<'a>)>,
+) -> bool {
+    match (pattern, value) {
+        (Pattern::Wildcard, _) => true,
+        (Pattern::Binder(name), _) => {
+            bindings.push((name.clone(), value.clone()));
+            true
+        }
+        (
+            Pattern::Constructor(name, patterns),
+            Val::Value(Value::Adt {
+                constructor, args, ..
+            }),
+        ) => {
+            name == constructor
+                && patterns.len() == args.len()
+                && patterns
+                    .iter()
+                    .zip(args)
+                    .all(|(p, arg)| matches(p, &Val::Value(arg.clone()), bindings))
+        }
+        _ => false,
+    }
+}
+
+/// Apply the function `func` to `arg`. Type abstractions are applied implicitly.
+pub(crate) fn apply<'a>(func: Val<'a>, arg: Val<'a>) -> Result<Val<'a>, Error> {
+    match func {
+        Val::Closure(closure) => match &closure.expr.kind {
+            ExprKind::Fun { param, body, .. } => {
+                let mut scope = closure.scope.clone();
+                scope.insert(param, arg);
+                nested(|| eval(body, &scope))
+            }
+            ExprKind::TFun { body, .. } => apply(eval(body, &closure.scope)?, arg),
+            _ => unreachable!("closures are built from functions"),
+        },
+        Val::Recursion(fold, mut args) => {
+            args.push(arg);
+            if args.len() < 3 {
+                return Ok(Val::Recursion(fold, args));
+            }
+            let [f, a, b] = <[Val; 3]>::try_from(args).unwrap_or_else(|_| unreachable!());
+            recurse(fold, f, a, b)
+        }
+        Val::Value(value) => Err(runtime(format!("{value} is not a function"))),
+    }
+}
+
+/// Run `f` as a function application, failing if the applications it is nested in use too much
+/// of the stack.
+fn nested<T>(f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    let marker = 0u8;
+    let here = std::ptr::addr_of!(marker) as usize;
+    match STACK_START.get() {
+        Some(start) if start.abs_diff(here) > MAX_STACK => {
+            Err(runtime("too many nested function applications".to_string()))
+        }
+        Some(_) => f(),
+        None => {
+            STACK_START.set(Some(here));
+            let result = f();
+            STACK_START.set(None);
+            result
+        }
+    }
+}
+
+fn apply2<'a>(func: &Val<'a>, a: Val<'a>, b: Val<'a>) -> Result<Val<'a>, Error> {
+    apply(apply(func.clone(), a)?, b)
+}
+
+fn list<'a>(value: &Val<'a>) -> Result<Vec<Val<'a>>, Error> {
+    match value {
+        Val::Value(value) => value
+            .as_list()
+            .map(|items| items.into_iter().cloned().map(Val::Value).collect())
+            .ok_or_else(|| runtime(format!("{value} is not a list"))),
+        _ => Err(runtime("a function is not a list".to_string())),
+    }
+}
+
+/// Apply the recursion principle `fold` to the function `f` and the arguments `a` and `b`.
+fn recurse<'a>(fold: Recursion, f: Val<'a>, a: Val<'a>, b: Val<'a>) -> Result<Val<'a>, Error> {
+    match fold {
+        // list_foldl f init l
+        Recursion::ListFoldl => list(&b)?
+            .into_iter()
+            .try_fold(a, |acc, item| apply2(&f, acc, item)),
+        // list_foldr f init l
+        Recursion::ListFoldr => list(&b)?
+            .into_iter()
+            .rev()
+            .try_fold(a, |acc, item| apply2(&f, item, acc)),
+        // list_foldk f init l, calling `f acc head (fun acc => list_foldk f acc tail)`
+        Recursion::ListFoldk => match b {
+            Val::Value(Value::Adt {
+                constructor, args, ..
+            }) if constructor == "Cons" && args.len() == 2 => {
+                let [head, tail] = <[Value; 2]>::try_from(args).unwrap_or_else(|_| unreachable!());
+                let rest =
+                    Val::Recursion(Recursion::ListFoldkRest, vec![f.clone(), Val::Value(tail)]);
+                apply(apply2(&f, a, Val::Value(head))?, rest)
+            }
+            _ if list(&b)?.is_empty() => Ok(a),
+            _ => Err(runtime("list_foldk expects a list".to_string())),
+        },
+        // The continuation of list_foldk, with `a` the rest of the list and `b` the accumulator.
+        Recursion::ListFoldkRest => recurse(Recursion::ListFoldk, f, b, a),
+        // nat_fold f init n, calling f on the accumulator and each natural below n
+        Recursion::NatFold => {
+            let mut acc = a;
+            let mut nat = Value::Adt {
+                constructor: "Zero".to_string(),
+                type_args: vec![],
+                args: vec![],
+            };
+            let mut current = b;
+            loop {
+                match current {
+                    Val::Value(Value::Adt {
+                        constructor,
+                        mut args,
+                        ..
+                    }) if constructor == "Succ" && args.len() == 1 => {
+                        acc = apply2(&f, acc, Val::Value(nat.clone()))?;
+                        nat = Value::Adt {
+                            constructor: "Succ".to_string(),
+                            type_args: vec![],
+                            args: vec![nat],
+                        };
+                        current = Val::Value(args.remove(0));
+                    }
+                    Val::Value(Value::Adt { constructor, .. }) if constructor == "Zero" => {
+                        return Ok(acc)
+                    }
+                    _ => return Err(runtime("nat_fold expects a Nat".to_string())),
+                }
+            }
+        }
+    }
+}
+
+fn eval_literal(literal: &Literal, scope: &Env) -> Result<Value, Error> {
+    let invalid = || Error::InvalidValue(format!("invalid literal {literal:?}"));
+    Ok(match literal {
+        Literal::Int(ty, n) => Value::int(ty.clone(), n.parse::<BigInt>().map_err(|_| invalid())?)?,
+        Literal::String(s) => Value::String(s.clone()),
+        Literal::ByStr(hex) => Value::ByStrX(decode_hex(hex).ok_or_else(invalid)?),
+        Literal::BNum(n) => Value::BNum(n.parse().map_err(|_| invalid())?),
+        Literal::EmptyMap(key_type, value_type) => Value::Map {
+            key_type: scope.instantiate(key_type),
+            value_type: scope.instantiate(value_type),
+            entries: vec![],
+        },
+    })
+}
+
+fn int_arg<'v>(name: &str, value: &'v Value) -> Result<(&'v Type, &'v BigInt), Error> {
+    match value {
+        Value::Int(ty, n) => Ok((ty, n)),
+        _ => Err(runtime(format!(
+            "`{name}` expects an integer, found {value}"
+        ))),
+    }
+}
+
+/// The integers `a` and `b`, which must be of the same type.
+fn int_args<'v>(
+    name: &str,
+    a: &'v Value,
+    b: &'v Value,
+) -> Result<(&'v Type, &'v BigInt, &'v BigInt), Error> {
+    let ((ta, x), (tb, y)) = (int_arg(name, a)?, int_arg(name, b)?);
+    if ta != tb {
+        return Err(runtime(format!(
+            "`{name}` expects integers of the same type, found {ta} and {tb}"
+        )));
+    }
+    Ok((ta, x, y))
+}
+
+/// `n` as a value of type `ty`, failing if it does not fit.
+fn checked(name: &str, ty: &Type, n: BigInt) -> Result<Value, Error> {
+    Value::int(ty.clone(), n).map_err(|_| runtime(format!("integer overflow in `{name}` of {ty}")))
+}
+
+fn int_type(name: &str) -> Option<Type> {
+    INT_TYPES
+        .iter()
+        .find(|ty| ty.to_lowercase() == name)
+        .and_then(|ty| parse_type(ty).ok())
+}
+
+fn bytes<'v>(name: &str, value: &'v Value) -> Result<&'v [u8], Error> {
+    match value {
+        Value::String(s) => Ok(s.as_bytes()),
+        Value::ByStr(bytes) | Value::ByStrX(bytes) => Ok(bytes),
+        _ => Err(runtime(format!("`{name}` expects a string, found {value}"))),
+    }
+}
+
+fn uint32(value: usize) -> Value {
+    Value::Int(Type::Uint32, value.into())
+}
+
+/// The bytes hashed by the hash builtins: the bytes of strings and byte strings, and the big
+/// endian representation of integers.
+fn hashed_bytes(name: &str, value: &Value) -> Result<Vec<u8>, Error> {
+    match value {
+        Value::Int(ty, n) => {
+            let bits = int_bounds(ty).map_or(0, |(_, max)| max.bits() as usize + 1);
+            let width = bits.div_ceil(8);
+            let mut bytes = n.to_signed_bytes_be();
+            let fill = if n.is_negative() { 0xff } else { 0 };
+            while bytes.len() < width {
+                bytes.insert(0, fill);
+            }
+            Ok(bytes)
+        }
+        _ => Ok(bytes(name, value)?.to_vec()),
+    }
+}
+
+/// The key type, the value type and the entries of a map.
+type MapParts<'v> = (&'v Type, &'v Type, &'v [(Value, Value)]);
+
+fn map_parts<'v>(name: &str, value: &'v Value) -> Result<MapParts<'v>, Error> {
+    match value {
+        Value::Map {
+            key_type,
+            value_type,
+            entries,
+        } => Ok((key_type, value_type, entries)),
+        _ => Err(runtime(format!("`{name}` expects a map, found {value}"))),
+    }
+}
+
+/// Apply the builtin `name` to `args`.
+pub(crate) fn builtin(name: &str, args: &[Value]) -> Result<Value, Error> {
+    let arity_error = || {
+        runtime(format!(
+            "`{name}` cannot be applied to {} arguments",
+            args.len()
+        ))
+    };
+    let unsupported = || runtime(format!("builtin `{name}` is not supported"));
+    match (name, args) {
+        ("eq", [a, b]) => Ok(Value::bool(a == b)),
+        ("add" | "sub" | "mul" | "div" | "rem", [a, b]) => {
+            let (ty, x, y) = int_args(name, a, b)?;
+            if matches!(name, "div" | "rem") && y.is_zero() {
+                return Err(runtime(format!("division by zero in `{name}`")));
+            }
+            let n = match name {
+                "add" => x + y,
+                "sub" => x - y,
+                "mul" => x * y,
+                "div" => x / y,
+                _ => x % y,
+            };
+            checked(name, ty, n)
+        }
+        ("pow", [a, b]) => {
+            let (ty, x) = int_arg(name, a)?;
+            let exponent = int_arg(name, b)?.1.to_u32().unwrap_or(u32::MAX);
+            // Only -1, 0 and 1 have powers of large exponents that fit into 256 bits.
+            if exponent > 256 && x.magnitude() > &BigUint::from(1u8) {
+                return Err(runtime(format!("integer overflow in `{name}` of {ty}")));
+            }
+            checked(name, ty, x.pow(exponent))
+        }
+        ("isqrt", [a]) => {
+            let (ty, x) = int_arg(name, a)?;
+            if x.is_negative() {
+                return Err(runtime(format!("`isqrt` of negative {x}")));
+            }
+            checked(name, ty, x.sqrt())
+        }
+        ("lt", [a, b]) => {
+            let (_, x, y) = int_args(name, a, b)?;
+            Ok(Value::bool(x < y))
+        }
+        ("to_nat", [a]) => {
+            let n = int_arg(name, a)?.1.to_u32().filter(|n| *n <= MAX_NAT);
+            let n = n.ok_or_else(|| runtime(format!("`to_nat` of {a} is too large")))?;
+            let zero = Value::Adt {
+                constructor: "Zero".to_string(),
+                type_args: vec![],
+                args: vec![],
+            };
+            Ok((0..n).fold(zero, |nat, _| Value::Adt {
+                constructor: "Succ".to_string(),
+                type_args: vec![],
+                args: vec![nat],
+            }))
+        }
+        ("to_string", [a]) => Ok(Value::String(match a {
+            Value::Int(_, n) => n.to_string(),
+            Value::String(s) => s.clone(),
+            Value::ByStr(_) | Value::ByStrX(_) | Value::BNum(_) => a.to_string(),
+            _ => return Err(runtime(format!("`to_string` of {a}"))),
+        })),
+        ("concat", [a, b]) => match (a, b) {
+            (Value::String(x), Value::String(y)) => Ok(Value::String(format!("{x}{y}"))),
+            (Value::ByStrX(x), Value::ByStrX(y)) => Ok(Value::ByStrX([&x[..], y].concat())),
+            (Value::ByStr(x), Value::ByStr(y)) => Ok(Value::ByStr([&x[..], y].concat())),
+            _ => Err(runtime(format!("`concat` of {a} and {b}"))),
+        },
+        ("substr", [s, start, len]) => {
+            let start = int_arg(name, start)?.1.to_usize().unwrap_or(usize::MAX);
+            let len = int_arg(name, len)?.1.to_usize().unwrap_or(usize::MAX);
+            let range = start..start.saturating_add(len);
+            let out_of_bounds = || runtime(format!("`substr` out of the bounds of {s}"));
+            match s {
+                Value::String(s) => Ok(Value::String(
+                    s.get(range).ok_or_else(out_of_bounds)?.to_string(),
+                )),
+                Value::ByStr(bytes) => Ok(Value::ByStr(
+                    bytes.get(range).ok_or_else(out_of_bounds)?.to_vec(),
+                )),
+                _ => Err(runtime(format!("`substr` of {s}"))),
+            }
+        }
+        ("strlen", [s]) => Ok(uint32(bytes(name, s)?.len())),
+        ("strrev", [Value::String(s)]) => Ok(Value::String(s.chars().rev().collect())),
+        ("to_bystr", [Value::ByStrX(bytes)]) => Ok(Value::ByStr(bytes.clone())),
+        ("to_ascii", [s]) => {
+            let bytes = bytes(name, s)?;
+            match std::str::from_utf8(bytes) {
+                Ok(s) if s.is_ascii() => Ok(Value::String(s.to_string())),
+                _ => Err(runtime(format!("{s} is not ASCII"))),
+            }
+        }
+        ("sha256hash", [a]) => Ok(Value::ByStrX(
+            Sha256::digest(hashed_bytes(name, a)?).to_vec(),
+        )),
+        ("keccak256hash", [a]) => Ok(Value::ByStrX(
+            Keccak256::digest(hashed_bytes(name, a)?).to_vec(),
+        )),
+        ("ripemd160hash", [a]) => Ok(Value::ByStrX(
+            Ripemd160::digest(hashed_bytes(name, a)?).to_vec(),
+        )),
+        ("put", [map, key, value]) => {
+            let (key_type, value_type, entries) = map_parts(name, map)?;
+            let mut entries = entries.to_vec();
+            match entries.iter_mut().find(|(k, _)| k == key) {
+                Some(entry) => entry.1 = value.clone(),
+                None => entries.push((key.clone(), value.clone())),
+            }
+            Ok(Value::Map {
+                key_type: key_type.clone(),
+                value_type: value_type.clone(),
+                entries,
+            })
+        }
+        ("get", [map, key]) => {
+            let (_, value_type, entries) = map_parts(name, map)?;
+            let value = entries
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.clone());
+            Ok(Value::option(value_type.clone(), value))
+        }
+        ("contains", [map, key]) => {
+            let (_, _, entries) = map_parts(name, map)?;
+            Ok(Value::bool(entries.iter().any(|(k, _)| k == key)))
+        }
+        ("remove", [map, key]) => {
+            let (key_type, value_type, entries) = map_parts(name, map)?;
+            Ok(Value::Map {
+                key_type: key_type.clone(),
+                value_type: value_type.clone(),
+                entries: entries.iter().filter(|(k, _)| k != key).cloned().collect(),
+            })
+        }
+        ("to_list", [map]) => {
+            let (key_type, value_type, entries) = map_parts(name, map)?;
+            let pairs = entries
+                .iter()
+                .map(|(k, v)| {
+                    Value::pair(key_type.clone(), value_type.clone(), k.clone(), v.clone())
+                })
+                .collect();
+            Ok(Value::list(
+                Type::Pair(Box::new(key_type.clone()), Box::new(value_type.clone())),
+                pairs,
+            ))
+        }
+        ("size", [map]) => Ok(uint32(map_parts(name, map)?.2.len())),
+        ("blt", [Value::BNum(a), Value::BNum(b)]) => Ok(Value::bool(a < b)),
+        ("badd", [Value::BNum(a), b]) => {
+            let (_, n) = int_arg(name, b)?;
+            let n = n
+                .to_biguint()
+                .ok_or_else(|| runtime(format!("`badd` of negative {n}")))?;
+            Ok(Value::BNum(a + n))
+        }
+        ("bsub", [Value::BNum(a), Value::BNum(b)]) => {
+            let difference = BigInt::from_biguint(Sign::Plus, a.clone())
+                - BigInt::from_biguint(Sign::Plus, b.clone());
+            checked(name, &Type::Int256, difference)
+        }
+        _ => {
+            if let Some(ty) = name.strip_prefix("to_").and_then(int_type) {
+                return match args {
+                    [Value::Int(_, n)] => {
+                        Ok(Value::option(ty.clone(), Value::int(ty, n.clone()).ok()))
+                    }
+                    [Value::String(s)] => {
+                        let value = s
+                            .parse::<BigInt>()
+                            .ok()
+                            .and_then(|n| Value::int(ty.clone(), n).ok());
+                        Ok(Value::option(ty, value))
+                    }
+                    // Byte strings are read as big endian unsigned integers.
+                    [Value::ByStrX(bytes)] => {
+                        checked(name, &ty, BigUint::from_bytes_be(bytes).into())
+                    }
+                    [a] => Err(runtime(format!("`{name}` of {a}"))),
+                    _ => Err(arity_error()),
+                };
+            }
+            if let Some(width) = name
+                .strip_prefix("to_bystr")
+                .and_then(|width| width.parse::<usize>().ok())
+            {
+                return match args {
+                    [Value::ByStr(bytes)] => Ok(Value::option(
+                        Type::ByStr(width),
+                        (bytes.len() == width).then(|| Value::ByStrX(bytes.clone())),
+                    )),
+                    [Value::Int(ty, n)] if int_bounds(ty).is_some() && !n.is_negative() => {
+                        let mut bytes = n.to_biguint().unwrap_or_default().to_bytes_be();
+                        if bytes.len() > width {
+                            return Err(runtime(format!("{n} does not fit into ByStr{width}")));
+                        }
+                        while bytes.len() < width {
+                            bytes.insert(0, 0);
+                        }
+                        Ok(Value::ByStrX(bytes))
+                    }
+                    [a] => Err(runtime(format!("`{name}` of {a}"))),
+                    _ => Err(arity_error()),
+                };
+            }
+            let known = [
+                "eq",
+                "add",
+                "sub",
+                "mul",
+                "div",
+                "rem",
+                "pow",
+                "isqrt",
+                "lt",
+                "to_nat",
+                "to_string",
+                "concat",
+                "substr",
+                "strlen",
+                "strrev",
+                "to_bystr",
+                "to_ascii",
+                "sha256hash",
+                "keccak256hash",
+                "ripemd160hash",
+                "put",
+                "get",
+                "contains",
+                "remove",
+                "to_list",
+                "size",
+                "blt",
+                "badd",
+                "bsub",
+            ];
+            if known.contains(&name) {
+                Err(arity_error())
+            } else {
+                Err(unsupported())
+            }
+        }
+    }
+}
@@ -0,0 +1,386 @@
+use crate::{ast::Span, Error};
+
+/// All tokens of the Scilla language.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    /// An identifier starting with a lowercase letter or an underscore, e.g. `amount` or `_sender`.
+    Ident(String),
+    /// An identifier starting with an uppercase letter, e.g. `Uint128`, `Cons` or `ZRC2`.
+    CapIdent(String),
+    /// A type variable, e.g. `'A`.
+    TypeVar(String),
+    /// An integer literal, possibly negative.
+    Number(String),
+    /// A string literal without the surrounding quotes.
+    Str(String),
+    /// A hexadecimal byte string literal including the `0x` prefix.
+    Hex(String),
+
+    ScillaVersion,
+    Import,
+    As,
+    Library,
+    Let,
+    In,
+    Fun,
+    Tfun,
+    Type,
+    Of,
+    Contract,
+    With,
+    Field,
+    Procedure,
+    Transition,
+    End,
+    Match,
+    Builtin,
+    Accept,
+    Send,
+    Event,
+    Throw,
+    Delete,
+    Exists,
+    Forall,
+
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Colon,
+    Semicolon,
+    Comma,
+    Dot,
+    Equal,
+    Assign,
+    LeftArrow,
+    Arrow,
+    DoubleArrow,
+    Bar,
+    At,
+    Ampersand,
+    Underscore,
+
+    Eof,
+}
+
+impl Token {
+    fn keyword(s: &str) -> Option<Token> {
+        let token = match s {
+            "scilla_version" => Token::ScillaVersion,
+            "import" => Token::Import,
+            "as" => Token::As,
+            "library" => Token::Library,
+            "let" => Token::Let,
+            "in" => Token::In,
+            "fun" => Token::Fun,
+            "tfun" => Token::Tfun,
+            "type" => Token::Type,
+            "of" => Token::Of,
+            "contract" => Token::Contract,
+            "with" => Token::With,
+            "field" => Token::Field,
+            "procedure" => Token::Procedure,
+            "transition" => Token::Transition,
+            "end" => Token::End,
+            "match" => Token::Match,
+            "builtin" => Token::Builtin,
+            "accept" => Token::Accept,
+            "send" => Token::Send,
+            "event" => Token::Event,
+            "throw" => Token::Throw,
+            "delete" => Token::Delete,
+            "exists" => Token::Exists,
+            "forall" => Token::Forall,
+            _ => return None,
+        };
+        Some(token)
+    }
+}
+
+/// Tokens together with their location in the source.
+pub type Tokens = Vec<(Token, Span)>;
+
+/// A comment found in the source, including its delimiters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub text: String,
+    pub span: Span,
+}
+
+/// Split a Scilla source into tokens. Comments are collected separately so that they can be
+/// attached to the declarations they document.
+pub struct Lexer<'a> {
+    src: &'a str,
+    pos: usize,
+    line: usize,
+    column: usize,
+    comments: Vec<Comment>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            pos: 0,
+            line: 1,
+            column: 1,
+            comments: vec![],
+        }
+    }
+
+    /// Tokenize the whole input. The last token is always `Token::Eof`.
+    pub fn tokenize(mut self) -> Result<(Tokens, Vec<Comment>), Error> {
+        let mut tokens = vec![];
+        loop {
+            self.skip_trivia()?;
+            let start = self.mark();
+            let token = self.next_token()?;
+            let span = self.span_from(start);
+            let eof = token == Token::Eof;
+            tokens.push((token, span));
+            if eof {
+                break;
+            }
+        }
+        Ok((tokens, self.comments))
+    }
+
+    fn mark(&self) -> (usize, usize, usize) {
+        (self.pos, self.line, self.column)
+    }
+
+    fn span_from(&self, (start, line, column): (usize, usize, usize)) -> Span {
+        Span {
+            start,
+            end: self.pos,
+            line,
+            column,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.src[self.pos..].chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::SyntaxError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), Error> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('(') if self.peek_nth(1) == Some('*') => self.comment()?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Comments in Scilla can be nested.
+    fn comment(&mut self) -> Result<(), Error> {
+        let start = self.mark();
+        let mut depth = 0;
+        loop {
+            match (self.peek(), self.peek_nth(1)) {
+                (Some('('), Some('*')) => {
+                    self.bump();
+                    self.bump();
+                    depth += 1;
+                }
+                (Some('*'), Some(')')) => {
+                    self.bump();
+                    self.bump();
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                (Some(_), _) => {
+                    self.bump();
+                }
+                (None, _) => return Err(self.error("Unterminated comment")),
+            }
+        }
+        let span = self.span_from(start);
+        self.comments.push(Comment {
+            text: self.src[span.start..span.end].to_string(),
+            span,
+        });
+        Ok(())
+    }
+
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(&pred) {
+            self.bump();
+        }
+        &self.src[start..self.pos]
+    }
+
+    fn next_token(&mut self) -> Result<Token, Error> {
+        let Some(c) = self.peek() else {
+            return Ok(Token::Eof);
+        };
+
+        let is_ident_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let token = match c {
+            'a'..='z' | '_' => {
+                let word = self.take_while(is_ident_char);
+                if word == "_" {
+                    Token::Underscore
+                } else {
+                    Token::keyword(word).unwrap_or_else(|| Token::Ident(word.to_string()))
+                }
+            }
+            'A'..='Z' => Token::CapIdent(self.take_while(is_ident_char).to_string()),
+            '\'' => {
+                self.bump();
+                let name = self.take_while(is_ident_char);
+                if name.is_empty() {
+                    return Err(self.error("Expected a type variable name after '"));
+                }
+                Token::TypeVar(format!("'{name}"))
+            }
+            '0' if matches!(self.peek_nth(1), Some('x') | Some('X')) => {
+                self.bump();
+                self.bump();
+                let digits = self.take_while(|c| c.is_ascii_hexdigit());
+                Token::Hex(format!("0x{digits}"))
+            }
+            '0'..='9' => Token::Number(self.take_while(|c| c.is_ascii_digit()).to_string()),
+            '-' if self.peek_nth(1).is_some_and(|c| c.is_ascii_digit()) => {
+                self.bump();
+                let digits = self.take_while(|c| c.is_ascii_digit());
+                Token::Number(format!("-{digits}"))
+            }
+            '"' => self.string()?,
+            _ => self.punctuation(c)?,
+        };
+        Ok(token)
+    }
+
+    fn string(&mut self) -> Result<Token, Error> {
+        self.bump();
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(Token::Str(s)),
+                Some('\\') => match self.bump() {
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    Some('r') => s.push('\r'),
+                    Some(c) => s.push(c),
+                    None => return Err(self.error("Unterminated string literal")),
+                },
+                Some(c) => s.push(c),
+                None => return Err(self.error("Unterminated string literal")),
+            }
+        }
+    }
+
+    fn punctuation(&mut self, c: char) -> Result<Token, Error> {
+        let two = |second| self.peek_nth(1) == Some(second);
+        let (token, len) = match c {
+            ':' if two('=') => (Token::Assign, 2),
+            '<' if two('-') => (Token::LeftArrow, 2),
+            '-' if two('>') => (Token::Arrow, 2),
+            '=' if two('>') => (Token::DoubleArrow, 2),
+            '(' => (Token::LParen, 1),
+            ')' => (Token::RParen, 1),
+            '{' => (Token::LBrace, 1),
+            '}' => (Token::RBrace, 1),
+            '[' => (Token::LBracket, 1),
+            ']' => (Token::RBracket, 1),
+            ':' => (Token::Colon, 1),
+            ';' => (Token::Semicolon, 1),
+            ',' => (Token::Comma, 1),
+            '.' => (Token::Dot, 1),
+            '=' => (Token::Equal, 1),
+            '|' => (Token::Bar, 1),
+            '@' => (Token::At, 1),
+            '&' => (Token::Ampersand, 1),
+            _ => return Err(self.error(format!("Unexpected character '{c}'"))),
+        };
+        for _ in 0..len {
+            self.bump();
+        }
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(src: &str) -> Vec<Token> {
+        let (tokens, _) = Lexer::new(src).tokenize().unwrap();
+        tokens.into_iter().map(|(t, _)| t).collect()
+    }
+
+    #[test]
+    fn test_statement_tokens() {
+        assert_eq!(
+            tokens("bal <- balances[_sender];"),
+            vec![
+                Token::Ident("bal".to_string()),
+                Token::LeftArrow,
+                Token::Ident("balances".to_string()),
+                Token::LBracket,
+                Token::Ident("_sender".to_string()),
+                Token::RBracket,
+                Token::Semicolon,
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_literal_tokens() {
+        assert_eq!(
+            tokens(r#"Int32 -1 "a\"b" 0xAbC0 'A"#),
+            vec![
+                Token::CapIdent("Int32".to_string()),
+                Token::Number("-1".to_string()),
+                Token::Str("a\"b".to_string()),
+                Token::Hex("0xAbC0".to_string()),
+                Token::TypeVar("'A".to_string()),
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_comments() {
+        let (tokens, comments) = Lexer::new("(* outer (* inner *) *) accept")
+            .tokenize()
+            .unwrap();
+        assert_eq!(tokens[0].0, Token::Accept);
+        assert_eq!(tokens[0].1.column, 25);
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].text, "(* outer (* inner *) *)");
+    }
+}
@@ -1,14 +1,63 @@
+pub mod access_control;
+pub mod ast;
+pub mod bindings;
+pub mod call_graph;
+#[cfg(feature = "codegen")]
+pub mod codegen;
+pub mod conformance;
 pub mod contract;
+pub mod contract_info;
+pub mod diff;
+pub mod doc_page;
+pub mod docs;
+pub mod effect;
 pub mod error;
+pub mod error_code;
+pub mod event;
+pub mod exhaustiveness;
 pub mod field;
+pub mod gas;
+mod infer;
+pub mod interactions;
+pub mod interpreter;
+pub mod lexer;
+pub mod lint;
+pub mod message;
+pub mod migration;
+pub mod parser;
+pub mod printer;
+pub mod receipt;
+pub mod simulator;
 pub mod transition;
 pub mod r#type;
+pub mod type_check;
+pub mod typescript;
+pub mod value;
 
+pub use access_control::*;
+pub use call_graph::*;
+pub use conformance::*;
 pub use contract::*;
+pub use diff::*;
+pub use docs::*;
+pub use effect::*;
 pub use error::Error;
+pub use error_code::*;
+pub use event::*;
+pub use exhaustiveness::*;
 pub use field::*;
+pub use gas::*;
+pub use interactions::*;
+pub use interpreter::*;
+pub use lint::*;
+pub use message::*;
+pub use migration::*;
 pub use r#type::*;
+pub use receipt::*;
+pub use simulator::*;
 pub use transition::*;
+pub use type_check::*;
+pub use value::*;
 
 use std::{path::Path, process::Command};
 
@@ -0,0 +1,680 @@
+//! Configurable checks of common mistakes in Scilla contracts.
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    ast::{Component, ContractDef, Expr, ExprKind, LibEntry, Literal, Span, Stmt, StmtKind},
+    call_graph::stmts_refs,
+    printer::print_pattern,
+    Contract, Error, MatchIssue, Type,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Note => write!(f, "note"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A built-in lint rule, named in kebab case, e.g. `unused-parameter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// A transition accepts funds but never uses `_amount`, so the amount is not recorded.
+    AcceptWithoutRecord,
+    /// A message is sent by a procedure called with `forall`, once per element of a list.
+    SendInLoop,
+    /// A parameter of a transition or procedure is never used. Components with an empty body,
+    /// such as callbacks that only acknowledge a message, are not reported.
+    UnusedParameter,
+    /// A library function or value is never used.
+    UnusedLibraryValue,
+    /// A local binding hides a library entry, a parameter or an earlier binding.
+    ShadowedBinding,
+    /// A field is never read by the contract.
+    FieldNeverRead,
+    /// A `Uint128` literal other than 0 and 1 in a transition or procedure, instead of a named
+    /// library value.
+    MagicNumber,
+    /// A message to `_sender` is sent before a field is changed.
+    SendBeforeStateUpdate,
+    /// An arm of a match can never be used because the arms before it match all of its values.
+    UnreachableArm,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 9] = [
+        Rule::AcceptWithoutRecord,
+        Rule::SendInLoop,
+        Rule::UnusedParameter,
+        Rule::UnusedLibraryValue,
+        Rule::ShadowedBinding,
+        Rule::FieldNeverRead,
+        Rule::MagicNumber,
+        Rule::SendBeforeStateUpdate,
+        Rule::UnreachableArm,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::AcceptWithoutRecord => "accept-without-record",
+            Rule::SendInLoop => "send-in-loop",
+            Rule::UnusedParameter => "unused-parameter",
+            Rule::UnusedLibraryValue => "unused-library-value",
+            Rule::ShadowedBinding => "shadowed-binding",
+            Rule::FieldNeverRead => "field-never-read",
+            Rule::MagicNumber => "magic-number",
+            Rule::SendBeforeStateUpdate => "send-before-state-update",
+            Rule::UnreachableArm => "unreachable-arm",
+        }
+    }
+
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            Rule::MagicNumber | Rule::ShadowedBinding => Severity::Note,
+            _ => Severity::Warning,
+        }
+    }
+
+    /// The span and message of each problem the rule finds in the contract.
+    fn check(&self, contract: &Contract, def: &ContractDef) -> Result<Vec<(Span, String)>, Error> {
+        let mut found = vec![];
+        match self {
+            Rule::AcceptWithoutRecord => accept_without_record(def, &mut found),
+            Rule::SendInLoop => send_in_loop(def, &mut found),
+            Rule::UnusedParameter => unused_parameter(def, &mut found),
+            Rule::UnusedLibraryValue => unused_library_value(contract, &mut found)?,
+            Rule::ShadowedBinding => shadowed_binding(contract, def, &mut found),
+            Rule::FieldNeverRead => field_never_read(def, &mut found),
+            Rule::MagicNumber => magic_number(def, &mut found),
+            Rule::SendBeforeStateUpdate => send_before_state_update(def, &mut found),
+            Rule::UnreachableArm => unreachable_arm(contract, &mut found)?,
+        }
+        Ok(found)
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    /// Parse the kebab case name of a rule.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rule::ALL
+            .into_iter()
+            .find(|rule| rule.name() == s)
+            .ok_or_else(|| Error::InvalidValue(format!("unknown lint rule `{s}`")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+}
+
+impl Display for Diagnostic {
+    /// `12:5: warning[unused-parameter]: parameter `to` of `Mint` is never used`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}[{}]: {}",
+            self.span.line, self.span.column, self.severity, self.rule, self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DiagnosticList(pub Vec<Diagnostic>);
+
+impl std::ops::Deref for DiagnosticList {
+    type Target = Vec<Diagnostic>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DiagnosticList {
+    /// The diagnostics reported by `rule`.
+    pub fn of(&self, rule: Rule) -> impl Iterator<Item = &Diagnostic> {
+        self.iter().filter(move |d| d.rule == rule)
+    }
+
+    /// The highest severity of the diagnostics, if any.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.iter().map(|d| d.severity).max()
+    }
+}
+
+/// Runs a set of rules over the syntax tree of a contract. By default, every rule runs with its
+/// default severity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Linter {
+    rules: Vec<(Rule, Severity)>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self {
+            rules: Rule::ALL
+                .into_iter()
+                .map(|rule| (rule, rule.default_severity()))
+                .collect(),
+        }
+    }
+}
+
+impl Linter {
+    /// A linter running no rule, to enable rules one by one.
+    pub fn empty() -> Self {
+        Self { rules: vec![] }
+    }
+
+    pub fn enable(self, rule: Rule) -> Self {
+        if self.is_enabled(rule) {
+            self
+        } else {
+            self.with_severity(rule, rule.default_severity())
+        }
+    }
+
+    pub fn disable(mut self, rule: Rule) -> Self {
+        self.rules.retain(|(r, _)| *r != rule);
+        self
+    }
+
+    /// Enable `rule` and report its diagnostics with `severity`.
+    pub fn with_severity(mut self, rule: Rule, severity: Severity) -> Self {
+        match self.rules.iter_mut().find(|(r, _)| *r == rule) {
+            Some((_, s)) => *s = severity,
+            None => self.rules.push((rule, severity)),
+        }
+        self
+    }
+
+    pub fn is_enabled(&self, rule: Rule) -> bool {
+        self.rules.iter().any(|(r, _)| *r == rule)
+    }
+
+    /// Run the enabled rules, returning their diagnostics in source order.
+    pub fn lint(&self, contract: &Contract) -> Result<DiagnosticList, Error> {
+        let module = contract.syntax_tree()?;
+        let mut diagnostics: Vec<Diagnostic> = vec![];
+        for (rule, severity) in &self.rules {
+            for (span, message) in rule.check(contract, &module.contract)? {
+                // Problems in procedures may be found once per transition calling them.
+                if !diagnostics
+                    .iter()
+                    .any(|d| d.rule == *rule && d.span == span)
+                {
+                    diagnostics.push(Diagnostic {
+                        rule: *rule,
+                        severity: *severity,
+                        message,
+                        span,
+                    });
+                }
+            }
+        }
+        diagnostics.sort_by_key(|d| d.span.start);
+        Ok(DiagnosticList(diagnostics))
+    }
+}
+
+/// The free names used by a component, including its parameters.
+fn component_refs(component: &Component) -> Vec<String> {
+    let mut refs = vec![];
+    stmts_refs(&component.body, &mut vec![], &mut refs);
+    refs
+}
+
+fn accept_without_record(contract: &ContractDef, found: &mut Vec<(Span, String)>) {
+    for transition in contract.transitions() {
+        let reached = contract.reachable_from(transition);
+        let mut accepts = vec![];
+        for component in &reached {
+            for stmt in &component.body {
+                stmt.walk(&mut |stmt| {
+                    if let StmtKind::Accept = stmt.kind {
+                        accepts.push(stmt.span);
+                    }
+                });
+            }
+        }
+        if !reached
+            .iter()
+            .any(|c| component_refs(c).iter().any(|name| name == "_amount"))
+        {
+            for span in accepts {
+                found.push((
+                    span,
+                    format!(
+                        "`{}` accepts funds but never uses `_amount`",
+                        transition.name
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn send_in_loop(contract: &ContractDef, found: &mut Vec<(Span, String)>) {
+    for component in &contract.components {
+        for stmt in &component.body {
+            stmt.walk(&mut |stmt| {
+                let StmtKind::Iterate { list, proc } = &stmt.kind else {
+                    return;
+                };
+                let Some(proc) = contract.component(proc) else {
+                    return;
+                };
+                for reached in contract.reachable_from(proc) {
+                    for stmt in &reached.body {
+                        stmt.walk(&mut |stmt| {
+                            if let StmtKind::Send(_) = stmt.kind {
+                                found.push((
+                                    stmt.span,
+                                    format!(
+                                        "message sent for each element of `{list}` by `forall` in `{}`",
+                                        component.name
+                                    ),
+                                ));
+                            }
+                        });
+                    }
+                }
+            });
+        }
+    }
+}
+
+fn unused_parameter(contract: &ContractDef, found: &mut Vec<(Span, String)>) {
+    for component in &contract.components {
+        if component.body.is_empty() {
+            continue;
+        }
+        let refs = component_refs(component);
+        for param in &component.params {
+            if !refs.contains(&param.name) {
+                found.push((
+                    param.span,
+                    format!(
+                        "parameter `{}` of `{}` is never used",
+                        param.name, component.name
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn unused_library_value(contract: &Contract, found: &mut Vec<(Span, String)>) -> Result<(), Error> {
+    let graph = contract.call_graph()?;
+    for entry in contract
+        .module
+        .iter()
+        .flat_map(|m| &m.library)
+        .flat_map(|l| &l.entries)
+    {
+        if let LibEntry::Let { name, span, .. } = entry {
+            if graph.callers(name).next().is_none() {
+                found.push((*span, format!("library value `{name}` is never used")));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn unreachable_arm(contract: &Contract, found: &mut Vec<(Span, String)>) -> Result<(), Error> {
+    // The arms of a match share its span, so they are reported together.
+    let mut matches: Vec<(Span, &str, Vec<String>)> = vec![];
+    let issues = contract.match_issues()?;
+    for issue in issues.unreachable_arms() {
+        let MatchIssue::UnreachableArm {
+            scrutinee,
+            arm,
+            span,
+        } = issue
+        else {
+            continue;
+        };
+        let arm = format!("`{}`", print_pattern(arm));
+        match matches.iter_mut().find(|(s, _, _)| s == span) {
+            Some((_, _, arms)) => arms.push(arm),
+            None => matches.push((*span, scrutinee, vec![arm])),
+        }
+    }
+    for (span, scrutinee, arms) in matches {
+        let message = match arms.as_slice() {
+            [arm] => format!("arm {arm} of the match on `{scrutinee}` is unreachable"),
+            _ => format!(
+                "arms {} of the match on `{scrutinee}` are unreachable",
+                arms.join(", ")
+            ),
+        };
+        found.push((span, message));
+    }
+    Ok(())
+}
+
+/// The names in scope while looking for shadowed bindings, with what they are.
+type Scope<'a> = Vec<(&'a str, String)>;
+
+fn shadow<'a>(
+    name: &'a str,
+    what: String,
+    span: Span,
+    scope: &mut Scope<'a>,
+    found: &mut Vec<(Span, String)>,
+) {
+    if let Some((_, shadowed)) = scope.iter().rev().find(|(n, _)| *n == name) {
+        found.push((span, format!("`{name}` shadows {shadowed}")));
+    }
+    scope.push((name, what));
+}
+
+fn expr_shadows<'a>(expr: &'a Expr, scope: &Scope<'a>, found: &mut Vec<(Span, String)>) {
+    let local = || "a local binding".to_string();
+    match &expr.kind {
+        ExprKind::Let {
+            name, value, body, ..
+        } => {
+            expr_shadows(value, scope, found);
+            let mut scope = scope.clone();
+            shadow(name, local(), expr.span, &mut scope, found);
+            expr_shadows(body, &scope, found);
+        }
+        ExprKind::Fun { param, body, .. } => {
+            let mut scope = scope.clone();
+            shadow(param, local(), expr.span, &mut scope, found);
+            expr_shadows(body, &scope, found);
+        }
+        ExprKind::Match { arms, .. } => {
+            for (pattern, arm) in arms {
+                let mut scope = scope.clone();
+                for binder in pattern.binders() {
+                    shadow(binder, local(), arm.span, &mut scope, found);
+                }
+                expr_shadows(arm, &scope, found);
+            }
+        }
+        ExprKind::TFun { body, .. } => expr_shadows(body, scope, found),
+        ExprKind::Message(_)
+        | ExprKind::Literal(_)
+        | ExprKind::Var(_)
+        | ExprKind::App { .. }
+        | ExprKind::Constructor { .. }
+        | ExprKind::Builtin { .. }
+        | ExprKind::TApp { .. } => {}
+    }
+}
+
+fn stmts_shadows<'a>(stmts: &'a [Stmt], scope: &mut Scope<'a>, found: &mut Vec<(Span, String)>) {
+    let local = || "a local binding".to_string();
+    for stmt in stmts {
+        match &stmt.kind {
+            StmtKind::Bind { lhs, expr } => {
+                expr_shadows(expr, scope, found);
+                shadow(lhs, local(), stmt.span, scope, found);
+            }
+            StmtKind::Load { lhs, .. }
+            | StmtKind::MapGet { lhs, .. }
+            | StmtKind::RemoteLoad { lhs, .. }
+            | StmtKind::RemoteMapGet { lhs, .. }
+            | StmtKind::TypeCast { lhs, .. }
+            | StmtKind::ReadFromBC { lhs, .. } => shadow(lhs, local(), stmt.span, scope, found),
+            StmtKind::Match { arms, .. } => {
+                for (pattern, arm) in arms {
+                    let mut scope = scope.clone();
+                    for binder in pattern.binders() {
+                        shadow(binder, local(), stmt.span, &mut scope, found);
+                    }
+                    stmts_shadows(arm, &mut scope, found);
+                }
+            }
+            StmtKind::Store { .. }
+            | StmtKind::MapUpdate { .. }
+            | StmtKind::MapDelete { .. }
+            | StmtKind::Accept
+            | StmtKind::Send(_)
+            | StmtKind::Event(_)
+            | StmtKind::Throw(_)
+            | StmtKind::CallProc { .. }
+            | StmtKind::Iterate { .. } => {}
+        }
+    }
+}
+
+fn shadowed_binding(contract: &Contract, def: &ContractDef, found: &mut Vec<(Span, String)>) {
+    let mut scope: Scope = vec![];
+    for entry in contract
+        .module
+        .iter()
+        .flat_map(|m| &m.library)
+        .flat_map(|l| &l.entries)
+    {
+        if let LibEntry::Let { name, expr, .. } = entry {
+            expr_shadows(expr, &scope, found);
+            scope.push((name, "a library entry".to_string()));
+        }
+    }
+    for param in &def.params {
+        scope.push((&param.name, "an immutable parameter".to_string()));
+    }
+    for component in &def.components {
+        let mut scope = scope.clone();
+        for param in &component.params {
+            let what = format!("a parameter of `{}`", component.name);
+            shadow(&param.name, what, param.span, &mut scope, found);
+        }
+        stmts_shadows(&component.body, &mut scope, found);
+    }
+}
+
+fn field_never_read(contract: &ContractDef, found: &mut Vec<(Span, String)>) {
+    let mut read = vec![];
+    for component in &contract.components {
+        for stmt in &component.body {
+            stmt.walk(&mut |stmt| match &stmt.kind {
+                StmtKind::Load { field, .. } | StmtKind::MapGet { map: field, .. } => {
+                    read.push(field.as_str())
+                }
+                _ => {}
+            });
+        }
+    }
+    for field in &contract.fields {
+        if !read.contains(&field.name.as_str()) {
+            found.push((field.span, format!("field `{}` is never read", field.name)));
+        }
+    }
+}
+
+fn magic_number(contract: &ContractDef, found: &mut Vec<(Span, String)>) {
+    for component in &contract.components {
+        for stmt in &component.body {
+            stmt.walk(&mut |stmt| {
+                let StmtKind::Bind { expr, .. } = &stmt.kind else {
+                    return;
+                };
+                expr.walk(&mut |expr| {
+                    if let ExprKind::Literal(Literal::Int(Type::Uint128, value)) = &expr.kind {
+                        if value != "0" && value != "1" {
+                            found.push((
+                                expr.span,
+                                format!("magic number `Uint128 {value}`, consider a named library value"),
+                            ));
+                        }
+                    }
+                });
+            });
+        }
+    }
+}
+
+/// Follows the statements of a transition and the procedures it calls in order, remembering
+/// the messages to `_sender` sent so far.
+struct SendFlow<'a> {
+    contract: &'a ContractDef,
+    stack: Vec<&'a str>,
+}
+
+impl<'a> SendFlow<'a> {
+    fn to_sender(env: &[(&str, bool)], name: &str) -> bool {
+        env.iter()
+            .rev()
+            .find(|(n, _)| *n == name)
+            .is_some_and(|(_, to_sender)| *to_sender)
+    }
+
+    fn stmts(
+        &mut self,
+        stmts: &'a [Stmt],
+        env: &mut Vec<(&'a str, bool)>,
+        sent: &mut Vec<Span>,
+        found: &mut Vec<(Span, String)>,
+    ) {
+        for stmt in stmts {
+            match &stmt.kind {
+                StmtKind::Bind { lhs, expr } => {
+                    // Messages to `_sender`, and the lists containing them.
+                    let to_sender = match &expr.kind {
+                        ExprKind::Message(entries) => entries.iter().any(|entry| {
+                            entry.name == "_recipient"
+                                && matches!(&entry.value.kind, ExprKind::Var(v) if v == "_sender")
+                        }),
+                        ExprKind::Var(name) => Self::to_sender(env, name),
+                        ExprKind::App { args, .. } | ExprKind::Constructor { args, .. } => {
+                            args.iter().any(|arg| Self::to_sender(env, arg))
+                        }
+                        _ => false,
+                    };
+                    env.push((lhs, to_sender));
+                }
+                StmtKind::Send(msgs) if Self::to_sender(env, msgs) => sent.push(stmt.span),
+                StmtKind::Store { field, .. }
+                | StmtKind::MapUpdate { map: field, .. }
+                | StmtKind::MapDelete { map: field, .. } => {
+                    for span in sent.drain(..) {
+                        found.push((
+                            span,
+                            format!("message to `_sender` sent before `{field}` is changed"),
+                        ));
+                    }
+                }
+                StmtKind::Match { arms, .. } => {
+                    let mut after = vec![];
+                    for (pattern, arm) in arms {
+                        let mut env = env.clone();
+                        env.extend(pattern.binders().into_iter().map(|b| (b, false)));
+                        let mut arm_sent = sent.clone();
+                        self.stmts(arm, &mut env, &mut arm_sent, found);
+                        for span in arm_sent {
+                            if !after.contains(&span) {
+                                after.push(span);
+                            }
+                        }
+                    }
+                    *sent = after;
+                }
+                StmtKind::CallProc { name, args } => {
+                    let args: Vec<bool> = args.iter().map(|a| Self::to_sender(env, a)).collect();
+                    self.call(name, &args, sent, found);
+                }
+                StmtKind::Iterate { proc, .. } => self.call(proc, &[], sent, found),
+                _ => {}
+            }
+        }
+    }
+
+    fn call(
+        &mut self,
+        name: &str,
+        args: &[bool],
+        sent: &mut Vec<Span>,
+        found: &mut Vec<(Span, String)>,
+    ) {
+        let Some(proc) = self.contract.component(name) else {
+            return;
+        };
+        if self.stack.contains(&proc.name.as_str()) {
+            return;
+        }
+        self.stack.push(&proc.name);
+        let mut env: Vec<(&str, bool)> = proc
+            .params
+            .iter()
+            .map(|p| p.name.as_str())
+            .zip(args.iter().copied().chain(std::iter::repeat(false)))
+            .collect();
+        self.stmts(&proc.body, &mut env, sent, found);
+        self.stack.pop();
+    }
+}
+
+fn send_before_state_update(contract: &ContractDef, found: &mut Vec<(Span, String)>) {
+    for transition in contract.transitions() {
+        let mut flow = SendFlow {
+            contract,
+            stack: vec![],
+        };
+        flow.stmts(&transition.body, &mut vec![], &mut vec![], found);
+    }
+}
+
+impl Contract {
+    /// Run every lint rule over the contract with its default severity, see [`Linter`] to
+    /// choose the rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::{Contract, Rule};
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Vault ()
+    ///     field deposits : Map ByStr20 Uint128 = Emp ByStr20 Uint128
+    ///     transition Deposit (memo : String)
+    ///       accept;
+    ///       fee = Uint128 42;
+    ///       deposits[_sender] := fee
+    ///     end",
+    /// )
+    /// .unwrap();
+    /// let diagnostics = contract.lint().unwrap();
+    /// let rules: Vec<Rule> = diagnostics.iter().map(|d| d.rule).collect();
+    /// assert_eq!(
+    ///     rules,
+    ///     vec![
+    ///         Rule::FieldNeverRead,
+    ///         Rule::UnusedParameter,
+    ///         Rule::AcceptWithoutRecord,
+    ///         Rule::MagicNumber
+    ///     ]
+    /// );
+    /// assert_eq!(
+    ///     diagnostics[1].to_string(),
+    ///     "4:25: warning[unused-parameter]: parameter `memo` of `Deposit` is never used"
+    /// );
+    /// ```
+    pub fn lint(&self) -> Result<DiagnosticList, Error> {
+        Linter::default().lint(self)
+    }
+}
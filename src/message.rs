@@ -0,0 +1,144 @@
+use crate::{
+    ast::{Expr, ExprKind, Literal},
+    conformance::satisfies,
+    infer::Context,
+    Contract, Error, InferredField,
+};
+
+/// A message a contract can send, e.g.
+/// `{_tag : "RecipientAcceptTransfer"; _recipient : to; _amount : zero; amount : amount}`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    /// The `_tag` of the message, i.e. the transition invoked on the recipient. `None` if the tag
+    /// is not a string literal.
+    pub tag: Option<String>,
+    /// The `_recipient` expression, e.g. `_sender` or `to`.
+    pub recipient: String,
+    /// The `_amount` expression, e.g. `zero` or `Uint128 0`.
+    pub amount: String,
+    pub params: Vec<InferredField>,
+    /// Transitions and procedures that can send the message, either directly or by calling a
+    /// procedure that sends it.
+    pub sent_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MessageList(pub Vec<Message>);
+
+impl std::ops::Deref for MessageList {
+    type Target = Vec<Message>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl MessageList {
+    /// Messages that can be sent by the transition or procedure `name`.
+    pub fn sent_by<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Message> {
+        self.iter()
+            .filter(move |m| m.sent_by.iter().any(|s| s == name))
+    }
+}
+
+impl Contract {
+    /// Collect the messages constructed by the transitions and procedures of the contract,
+    /// including those built by library functions.
+    ///
+    /// Messages are literals with a `_tag` entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    /// let messages = contract.messages().unwrap();
+    /// let tags: Vec<_> = messages
+    ///     .sent_by("Transfer")
+    ///     .filter_map(|m| m.tag.as_deref())
+    ///     .collect();
+    /// assert_eq!(tags, vec!["RecipientAcceptTransfer", "TransferSuccessCallBack"]);
+    /// ```
+    pub fn messages(&self) -> Result<MessageList, Error> {
+        let module = self.syntax_tree()?;
+        let context = Context::new(module);
+
+        let mut messages: Vec<Message> = vec![];
+        for construction in context.constructions() {
+            if construction.entry("_tag").is_none() {
+                continue;
+            }
+            let expression = |name| {
+                construction
+                    .entry(name)
+                    .map(|entry| expression_text(&entry.value))
+                    .unwrap_or_default()
+            };
+            let message = Message {
+                tag: construction.string_entry("_tag").map(ToString::to_string),
+                recipient: expression("_recipient"),
+                amount: expression("_amount"),
+                params: construction.params(&["_tag", "_recipient", "_amount"]),
+                sent_by: vec![],
+            };
+
+            let sender = construction.component.name.clone();
+            match messages.iter_mut().find(|m| {
+                m.tag == message.tag
+                    && m.recipient == message.recipient
+                    && m.amount == message.amount
+                    && m.params == message.params
+            }) {
+                Some(known) => known.sent_by.push(sender),
+                None => messages.push(Message {
+                    sent_by: vec![sender],
+                    ..message
+                }),
+            }
+        }
+
+        // Components calling a sending procedure can send the message as well.
+        for message in &mut messages {
+            message.sent_by = context.reaching(&message.sent_by);
+        }
+        Ok(MessageList(messages))
+    }
+
+    /// Returns true if the contract has a transition that can receive `message`: its name is the
+    /// tag of the message and its parameters have the names and compatible types of the message
+    /// parameters: an address type such as `ByStr20 with end` is compatible with `ByStr20`.
+    /// Parameters whose type could not be inferred match any type.
+    ///
+    /// Messages whose tag is not known are never accepted.
+    pub fn accepts(&self, message: &Message) -> bool {
+        let Some(tag) = &message.tag else {
+            return false;
+        };
+        self.transitions.iter().any(|transition| {
+            transition.name == *tag
+                && transition.params.len() == message.params.len()
+                && message.params.iter().all(|param| {
+                    transition.params.iter().any(|p| {
+                        p.name == param.name
+                            && param.r#type.as_ref().is_none_or(|ty| {
+                                satisfies(ty, &p.r#type) || satisfies(&p.r#type, ty)
+                            })
+                    })
+                })
+        })
+    }
+}
+
+/// Text of a message entry value, which is a variable or a literal.
+fn expression_text(expr: &Expr) -> String {
+    match &expr.kind {
+        ExprKind::Var(name) => name.clone(),
+        ExprKind::Literal(Literal::Int(ty, value)) => format!("{ty} {value}"),
+        ExprKind::Literal(Literal::String(s)) => format!("{s:?}"),
+        ExprKind::Literal(Literal::ByStr(hex)) => hex.clone(),
+        ExprKind::Literal(Literal::BNum(n)) => format!("BNum {n}"),
+        ExprKind::Literal(Literal::EmptyMap(key, value)) => format!("Emp {key} {value}"),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,289 @@
+//! Planning the migration of the state of a contract to a new version.
+//!
+//! Contracts are immutable, so upgrading one means deploying the new version and copying the
+//! fields of the old one into it.
+
+use std::fmt::Display;
+
+use crate::{
+    ast::{CtrDef, LibEntry},
+    printer::print_type,
+    Contract, Type,
+};
+
+/// What to do with a field when migrating the state of a contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Migration {
+    /// The field has the same type in both versions and can be copied verbatim.
+    Identical,
+    /// Every value of the old type is a value of the new one with the same representation, e.g.
+    /// `Uint32` to `Uint128`, so the field can be copied verbatim too.
+    Widened,
+    /// The values of the field have to be converted or checked before being copied.
+    Transform,
+    /// The field no longer exists.
+    Dropped,
+    /// The field is new and starts with its initial value.
+    Added,
+}
+
+/// The migration of a field. `from` is `None` for added fields and `to` for dropped ones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMigration {
+    pub name: String,
+    pub migration: Migration,
+    pub from: Option<Type>,
+    pub to: Option<Type>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MigrationPlan(pub Vec<FieldMigration>);
+
+impl std::ops::Deref for MigrationPlan {
+    type Target = Vec<FieldMigration>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl MigrationPlan {
+    /// The fields to migrate with `migration`.
+    pub fn fields(&self, migration: Migration) -> impl Iterator<Item = &FieldMigration> {
+        self.iter().filter(move |f| f.migration == migration)
+    }
+
+    /// Whether some fields cannot be copied verbatim to the new version.
+    pub fn needs_transformation(&self) -> bool {
+        self.fields(Migration::Transform).next().is_some()
+    }
+}
+
+impl Display for MigrationPlan {
+    /// The fields grouped by migration, e.g. `Widened:\n  - count : Uint32 -> Uint128\n`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (migration, title) in [
+            (Migration::Identical, "Identical"),
+            (Migration::Widened, "Widened"),
+            (Migration::Transform, "Needs transformation"),
+            (Migration::Dropped, "Dropped"),
+            (Migration::Added, "Added"),
+        ] {
+            let fields: Vec<_> = self.fields(migration).collect();
+            if fields.is_empty() {
+                continue;
+            }
+            writeln!(f, "{title}:")?;
+            for field in fields {
+                let types: Vec<String> = [&field.from, &field.to]
+                    .into_iter()
+                    .flatten()
+                    .map(print_type)
+                    .collect();
+                let types = match migration {
+                    Migration::Identical => &types[..1],
+                    _ => &types[..],
+                };
+                writeln!(f, "  - {} : {}", field.name, types.join(" -> "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The ADTs declared in the library of each version, by name.
+struct Adts<'a> {
+    old: Vec<(&'a str, &'a [CtrDef])>,
+    new: Vec<(&'a str, &'a [CtrDef])>,
+}
+
+impl<'a> Adts<'a> {
+    fn of(contract: &'a Contract) -> Vec<(&'a str, &'a [CtrDef])> {
+        contract
+            .module
+            .iter()
+            .flat_map(|m| &m.library)
+            .flat_map(|l| &l.entries)
+            .filter_map(|entry| match entry {
+                LibEntry::Type {
+                    name, constructors, ..
+                } => Some((name.as_str(), constructors.as_slice())),
+                LibEntry::Let { .. } => None,
+            })
+            .collect()
+    }
+
+    fn find(adts: &[(&'a str, &'a [CtrDef])], name: &str) -> Option<&'a [CtrDef]> {
+        adts.iter().find(|(n, _)| *n == name).map(|(_, c)| *c)
+    }
+}
+
+/// Width of an integer type and whether it is signed.
+fn integer(ty: &Type) -> Option<(u32, bool)> {
+    match ty {
+        Type::Int32 => Some((32, true)),
+        Type::Int64 => Some((64, true)),
+        Type::Int128 => Some((128, true)),
+        Type::Int256 => Some((256, true)),
+        Type::Uint32 => Some((32, false)),
+        Type::Uint64 => Some((64, false)),
+        Type::Uint128 => Some((128, false)),
+        Type::Uint256 => Some((256, false)),
+        _ => None,
+    }
+}
+
+/// How values of type `from` migrate to type `to`: `Identical`, `Widened` or `Transform`.
+fn compare(from: &Type, to: &Type, adts: &Adts) -> Migration {
+    let all = |pairs: &[(&Type, &Type)]| {
+        pairs
+            .iter()
+            .map(|(from, to)| compare(from, to, adts))
+            .max()
+            .unwrap_or(Migration::Identical)
+    };
+    match (from, to) {
+        (Type::Map(k1, v1), Type::Map(k2, v2)) | (Type::Pair(k1, v1), Type::Pair(k2, v2)) => {
+            all(&[(k1, k2), (v1, v2)])
+        }
+        (Type::Option(a), Type::Option(b)) | (Type::List(a), Type::List(b)) => all(&[(a, b)]),
+        (Type::Other(a), Type::Other(b)) if a == b => {
+            match (Adts::find(&adts.old, a), Adts::find(&adts.new, b)) {
+                (Some(old), Some(new)) if old != new => {
+                    // Values of the old ADT remain valid if its constructors are kept as they are.
+                    if old.iter().all(|ctr| new.contains(ctr)) {
+                        Migration::Widened
+                    } else {
+                        Migration::Transform
+                    }
+                }
+                _ => Migration::Identical,
+            }
+        }
+        _ if from == to => Migration::Identical,
+        (Type::Address(_), Type::ByStr(20)) | (Type::Address(Some(_)), Type::Address(None)) => {
+            Migration::Widened
+        }
+        (Type::Address(Some(from)), Type::Address(Some(to)))
+            if to.iter().all(|field| from.contains(field)) =>
+        {
+            Migration::Widened
+        }
+        _ => match (integer(from), integer(to)) {
+            // A signed integer never fits in an unsigned one.
+            (Some((from, from_signed)), Some((to, to_signed)))
+                if from < to && (to_signed || !from_signed) =>
+            {
+                Migration::Widened
+            }
+            _ => Migration::Transform,
+        },
+    }
+}
+
+impl Type {
+    /// Whether every value of this type is a value of `other` with the same representation, e.g.
+    /// `Uint32` widens to `Uint64` and `Int64`, and an address type widens to `ByStr20`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Type;
+    /// let map = |v| Type::Map(Box::new(Type::ByStr(20)), Box::new(v));
+    /// assert!(map(Type::Uint32).widens_to(&map(Type::Uint128)));
+    /// assert!(!map(Type::Uint128).widens_to(&map(Type::Uint32)));
+    /// ```
+    pub fn widens_to(&self, other: &Type) -> bool {
+        let adts = Adts {
+            old: vec![],
+            new: vec![],
+        };
+        compare(self, other, &adts) != Migration::Transform
+    }
+}
+
+impl Contract {
+    /// Plan the migration of the fields of the contract to its new version `new`: fields are
+    /// matched by name and compared by type. When both versions are parsed from their Scilla
+    /// source, the library ADTs used by the fields are compared too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::{Contract, Migration};
+    /// let old = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Counter ()
+    ///     field count : Uint32 = Uint32 0
+    ///     field owner : ByStr20 = 0x0000000000000000000000000000000000000000",
+    /// )
+    /// .unwrap();
+    /// let new = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Counter ()
+    ///     field count : Uint128 = Uint128 0",
+    /// )
+    /// .unwrap();
+    /// let plan = old.migration_plan(&new);
+    /// assert_eq!(plan[0].migration, Migration::Widened);
+    /// assert_eq!(plan[1].migration, Migration::Dropped);
+    /// assert_eq!(
+    ///     plan.to_string(),
+    ///     "Widened:\n  - count : Uint32 -> Uint128\nDropped:\n  - owner : ByStr20\n"
+    /// );
+    /// ```
+    pub fn migration_plan(&self, new: &Contract) -> MigrationPlan {
+        let adts = Adts {
+            old: Adts::of(self),
+            new: Adts::of(new),
+        };
+        let mut plan = vec![];
+        for field in self.fields.iter() {
+            let to = new.fields.iter().find(|f| f.name == field.name);
+            plan.push(FieldMigration {
+                name: field.name.clone(),
+                migration: match to {
+                    Some(to) => compare(&field.r#type, &to.r#type, &adts),
+                    None => Migration::Dropped,
+                },
+                from: Some(field.r#type.clone()),
+                to: to.map(|to| to.r#type.clone()),
+            });
+        }
+        for field in new.fields.iter() {
+            if !self.fields.iter().any(|f| f.name == field.name) {
+                plan.push(FieldMigration {
+                    name: field.name.clone(),
+                    migration: Migration::Added,
+                    from: None,
+                    to: Some(field.r#type.clone()),
+                });
+            }
+        }
+        MigrationPlan(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Field, FieldList};
+
+    #[test]
+    fn test_widens_to() {
+        assert!(Type::Uint32.widens_to(&Type::Int64));
+        assert!(!Type::Uint64.widens_to(&Type::Int64));
+        assert!(!Type::Int32.widens_to(&Type::Uint256));
+        assert!(!Type::Uint32.widens_to(&Type::String));
+
+        let owner = FieldList(vec![Field::new("owner", Type::ByStr(20))]);
+        let token = FieldList(vec![
+            Field::new("owner", Type::ByStr(20)),
+            Field::new("paused", Type::Bool),
+        ]);
+        assert!(Type::Address(Some(token.clone())).widens_to(&Type::Address(Some(owner.clone()))));
+        assert!(!Type::Address(Some(owner)).widens_to(&Type::Address(Some(token))));
+        assert!(Type::Address(None).widens_to(&Type::ByStr(20)));
+        assert!(!Type::ByStr(20).widens_to(&Type::Address(None)));
+    }
+}
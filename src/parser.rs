@@ -0,0 +1,1022 @@
+//! A native parser for Scilla source files. It produces the [syntax tree](crate::ast) without
+//! requiring `scilla-fmt`.
+
+use crate::{
+    ast::*,
+    lexer::{Comment, Lexer, Token, Tokens},
+    Docs, Error, Field, FieldList, Type,
+};
+
+/// Parse a whole `.scilla` source into a [`Module`].
+pub fn parse_module(src: &str) -> Result<Module, Error> {
+    let mut parser = Parser::new(src)?;
+    let module = parser.module()?;
+    parser.expect(Token::Eof)?;
+    Ok(module)
+}
+
+/// Parse a type written in Scilla syntax, e.g. `Map ByStr20 (List Uint128)`.
+pub fn parse_type(src: &str) -> Result<Type, Error> {
+    let mut parser = Parser::new(src)?;
+    let ty = parser.ty()?;
+    parser.expect(Token::Eof)?;
+    Ok(ty)
+}
+
+/// Parse a single Scilla expression.
+pub fn parse_expr(src: &str) -> Result<Expr, Error> {
+    let mut parser = Parser::new(src)?;
+    let expr = parser.expr()?;
+    parser.expect(Token::Eof)?;
+    Ok(expr)
+}
+
+/// Types, expressions, patterns and statements nested deeper than this fail to parse instead of
+/// overflowing the stack, here or in the analyses of the syntax tree.
+const MAX_DEPTH: usize = 100;
+
+struct Parser {
+    tokens: Tokens,
+    comments: Vec<Comment>,
+    pos: usize,
+    /// The number of types, expressions, patterns and statements being parsed.
+    depth: usize,
+}
+
+impl Parser {
+    fn new(src: &str) -> Result<Self, Error> {
+        let (tokens, comments) = Lexer::new(src).tokenize()?;
+        Ok(Self {
+            tokens,
+            comments,
+            pos: 0,
+            depth: 0,
+        })
+    }
+
+    /// Run `parse` one level deeper, failing if the nesting is too deep.
+    fn nested<T>(&mut self, parse: fn(&mut Self) -> Result<T, Error>) -> Result<T, Error> {
+        if self.depth == MAX_DEPTH {
+            let span = self.span();
+            return Err(Error::SyntaxError {
+                line: span.line,
+                column: span.column,
+                message: format!("Nesting deeper than {MAX_DEPTH} levels"),
+            });
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].0
+    }
+
+    fn peek_nth(&self, n: usize) -> &Token {
+        let index = (self.pos + n).min(self.tokens.len() - 1);
+        &self.tokens[index].0
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.pos].1
+    }
+
+    /// Span from `start` up to the end of the last consumed token.
+    fn span_from(&self, start: Span) -> Span {
+        let last = self.tokens[self.pos.saturating_sub(1)].1;
+        start.to(last)
+    }
+
+    /// The block of comments right before the current token, each starting its own line and
+    /// without blank lines in between.
+    fn docs(&self) -> Docs {
+        let (start, mut line) = (self.span().start, self.span().line);
+        let previous = self.pos.checked_sub(1).map(|i| self.tokens[i].1);
+        let mut comments = vec![];
+        for comment in self.comments.iter().rev() {
+            if comment.span.start >= start {
+                continue;
+            }
+            let end_line = comment.span.line + comment.text.matches('\n').count();
+            let after_previous =
+                previous.is_none_or(|p| comment.span.start >= p.end && comment.span.line > p.line);
+            if end_line + 1 != line || !after_previous {
+                break;
+            }
+            comments.push(comment.text.clone());
+            line = comment.span.line;
+        }
+        comments.reverse();
+        Docs::from_comments(comments)
+    }
+
+    fn bump(&mut self) -> Token {
+        let token = self.tokens[self.pos].0.clone();
+        if token != Token::Eof {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, token: Token) -> bool {
+        if *self.peek() == token {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, expected: &str) -> Error {
+        let span = self.span();
+        Error::SyntaxError {
+            line: span.line,
+            column: span.column,
+            message: format!("Expected {expected}, found {:?}", self.peek()),
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), Error> {
+        if self.eat(token.clone()) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("{token:?}")))
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, Error> {
+        match self.peek() {
+            Token::Ident(_) => match self.bump() {
+                Token::Ident(name) => Ok(name),
+                _ => unreachable!(),
+            },
+            _ => Err(self.error("an identifier")),
+        }
+    }
+
+    fn cap_ident(&mut self) -> Result<String, Error> {
+        match self.peek() {
+            Token::CapIdent(_) => match self.bump() {
+                Token::CapIdent(name) => Ok(name),
+                _ => unreachable!(),
+            },
+            _ => Err(self.error("a capitalized identifier")),
+        }
+    }
+
+    /// Transitions, procedures and contracts may be named with either case.
+    fn any_ident(&mut self) -> Result<String, Error> {
+        match self.peek() {
+            Token::CapIdent(_) => self.cap_ident(),
+            _ => self.ident(),
+        }
+    }
+
+    fn idents(&mut self) -> Vec<String> {
+        let mut idents = vec![];
+        while let Token::Ident(name) = self.peek() {
+            idents.push(name.clone());
+            self.bump();
+        }
+        idents
+    }
+
+    fn module(&mut self) -> Result<Module, Error> {
+        self.expect(Token::ScillaVersion)?;
+        let scilla_version = match self.bump() {
+            Token::Number(n) => n.parse().map_err(|_| self.error("a version number"))?,
+            _ => return Err(self.error("a version number")),
+        };
+
+        let mut imports = vec![];
+        if self.eat(Token::Import) {
+            while let Token::CapIdent(_) = self.peek() {
+                let start = self.span();
+                let name = self.cap_ident()?;
+                let alias = if self.eat(Token::As) {
+                    Some(self.cap_ident()?)
+                } else {
+                    None
+                };
+                imports.push(Import {
+                    name,
+                    alias,
+                    span: self.span_from(start),
+                });
+            }
+        }
+
+        let library = if *self.peek() == Token::Library {
+            Some(self.library()?)
+        } else {
+            None
+        };
+
+        Ok(Module {
+            scilla_version,
+            imports,
+            library,
+            contract: self.contract()?,
+        })
+    }
+
+    fn library(&mut self) -> Result<Library, Error> {
+        let start = self.span();
+        self.expect(Token::Library)?;
+        let name = self.cap_ident()?;
+        let mut entries = vec![];
+        loop {
+            let entry_start = self.span();
+            let docs = self.docs();
+            match self.peek() {
+                Token::Let => {
+                    self.bump();
+                    let name = self.ident()?;
+                    let ty = if self.eat(Token::Colon) {
+                        Some(self.ty()?)
+                    } else {
+                        None
+                    };
+                    self.expect(Token::Equal)?;
+                    let expr = self.expr()?;
+                    entries.push(LibEntry::Let {
+                        name,
+                        ty,
+                        expr,
+                        docs,
+                        span: self.span_from(entry_start),
+                    });
+                }
+                Token::Type => {
+                    self.bump();
+                    let name = self.cap_ident()?;
+                    self.expect(Token::Equal)?;
+                    let mut constructors = vec![];
+                    let mut first = true;
+                    while self.eat(Token::Bar)
+                        || (first && matches!(self.peek(), Token::CapIdent(_)))
+                    {
+                        first = false;
+                        let name = self.cap_ident()?;
+                        let args = if self.eat(Token::Of) {
+                            self.atypes()?
+                        } else {
+                            vec![]
+                        };
+                        constructors.push(CtrDef { name, args });
+                    }
+                    entries.push(LibEntry::Type {
+                        name,
+                        constructors,
+                        docs,
+                        span: self.span_from(entry_start),
+                    });
+                }
+                _ => break,
+            }
+        }
+        Ok(Library {
+            name,
+            entries,
+            span: self.span_from(start),
+        })
+    }
+
+    fn contract(&mut self) -> Result<ContractDef, Error> {
+        let start = self.span();
+        let docs = self.docs();
+        self.expect(Token::Contract)?;
+        let name = self.any_ident()?;
+        let params = self.params()?;
+        let constraint = if self.eat(Token::With) {
+            let expr = self.expr()?;
+            self.expect(Token::DoubleArrow)?;
+            Some(expr)
+        } else {
+            None
+        };
+
+        let mut fields = vec![];
+        while *self.peek() == Token::Field {
+            let start = self.span();
+            let docs = self.docs();
+            self.bump();
+            let name = self.ident()?;
+            self.expect(Token::Colon)?;
+            let ty = self.ty()?;
+            self.expect(Token::Equal)?;
+            let init = self.expr()?;
+            fields.push(FieldDef {
+                name,
+                ty,
+                init,
+                docs,
+                span: self.span_from(start),
+            });
+        }
+
+        let mut components = vec![];
+        loop {
+            let start = self.span();
+            let docs = self.docs();
+            let kind = match self.peek() {
+                Token::Transition => ComponentKind::Transition,
+                Token::Procedure => ComponentKind::Procedure,
+                _ => break,
+            };
+            self.bump();
+            let name = self.any_ident()?;
+            let params = self.params()?;
+            let body = self.stmts()?;
+            self.expect(Token::End)?;
+            components.push(Component {
+                kind,
+                name,
+                params,
+                body,
+                docs,
+                span: self.span_from(start),
+            });
+        }
+
+        Ok(ContractDef {
+            name,
+            params,
+            constraint,
+            fields,
+            components,
+            docs,
+            span: self.span_from(start),
+        })
+    }
+
+    fn params(&mut self) -> Result<Vec<Param>, Error> {
+        self.expect(Token::LParen)?;
+        let mut params = vec![];
+        while *self.peek() != Token::RParen {
+            if !params.is_empty() {
+                self.expect(Token::Comma)?;
+            }
+            let start = self.span();
+            let name = self.ident()?;
+            self.expect(Token::Colon)?;
+            let ty = self.ty()?;
+            params.push(Param {
+                name,
+                ty,
+                span: self.span_from(start),
+            });
+        }
+        self.expect(Token::RParen)?;
+        Ok(params)
+    }
+
+    fn ty(&mut self) -> Result<Type, Error> {
+        self.nested(Self::ty_at_depth)
+    }
+
+    fn ty_at_depth(&mut self) -> Result<Type, Error> {
+        let ty = match self.peek() {
+            Token::Forall => {
+                self.bump();
+                let tvar = match self.bump() {
+                    Token::TypeVar(name) => name,
+                    _ => return Err(self.error("a type variable")),
+                };
+                self.expect(Token::Dot)?;
+                return Ok(Type::PolyFun(tvar, Box::new(self.ty()?)));
+            }
+            Token::CapIdent(_) => {
+                let name = self.cap_ident()?;
+                if name == "ByStr20" && *self.peek() == Token::With {
+                    self.address_type()?
+                } else {
+                    let args = self.atypes()?;
+                    named_type(name, args)
+                }
+            }
+            _ => self.atype()?,
+        };
+
+        if self.eat(Token::Arrow) {
+            Ok(Type::Function(Box::new(ty), Box::new(self.ty()?)))
+        } else {
+            Ok(ty)
+        }
+    }
+
+    fn address_type(&mut self) -> Result<Type, Error> {
+        self.expect(Token::With)?;
+        if self.eat(Token::End) {
+            return Ok(Type::Address(None));
+        }
+        self.expect(Token::Contract)?;
+        let mut fields = vec![];
+        while *self.peek() != Token::End {
+            if !fields.is_empty() {
+                self.expect(Token::Comma)?;
+            }
+            self.expect(Token::Field)?;
+            let name = self.ident()?;
+            self.expect(Token::Colon)?;
+            fields.push(Field::new(&name, self.ty()?));
+        }
+        self.expect(Token::End)?;
+        Ok(Type::Address(Some(FieldList(fields))))
+    }
+
+    fn is_atype_start(&self) -> bool {
+        matches!(
+            self.peek(),
+            Token::CapIdent(_) | Token::TypeVar(_) | Token::LParen
+        )
+    }
+
+    fn atypes(&mut self) -> Result<Vec<Type>, Error> {
+        let mut types = vec![];
+        while self.is_atype_start() {
+            types.push(self.atype()?);
+        }
+        Ok(types)
+    }
+
+    fn atype(&mut self) -> Result<Type, Error> {
+        if !self.is_atype_start() {
+            return Err(self.error("a type"));
+        }
+        match self.bump() {
+            Token::CapIdent(name) => Ok(named_type(name, vec![])),
+            Token::TypeVar(name) => Ok(Type::TypeVar(name)),
+            _ => {
+                let ty = self.ty()?;
+                self.expect(Token::RParen)?;
+                Ok(ty)
+            }
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        self.nested(Self::expr_at_depth)
+    }
+
+    fn expr_at_depth(&mut self) -> Result<Expr, Error> {
+        let start = self.span();
+        let kind = match self.peek().clone() {
+            Token::Let => {
+                self.bump();
+                let name = self.ident()?;
+                let ty = if self.eat(Token::Colon) {
+                    Some(self.ty()?)
+                } else {
+                    None
+                };
+                self.expect(Token::Equal)?;
+                let value = Box::new(self.expr()?);
+                self.expect(Token::In)?;
+                let body = Box::new(self.expr()?);
+                ExprKind::Let {
+                    name,
+                    ty,
+                    value,
+                    body,
+                }
+            }
+            Token::Fun => {
+                self.bump();
+                self.expect(Token::LParen)?;
+                let param = self.ident()?;
+                self.expect(Token::Colon)?;
+                let ty = self.ty()?;
+                self.expect(Token::RParen)?;
+                self.expect(Token::DoubleArrow)?;
+                ExprKind::Fun {
+                    param,
+                    ty,
+                    body: Box::new(self.expr()?),
+                }
+            }
+            Token::Tfun => {
+                self.bump();
+                let tvar = match self.bump() {
+                    Token::TypeVar(name) => name,
+                    _ => return Err(self.error("a type variable")),
+                };
+                self.expect(Token::DoubleArrow)?;
+                ExprKind::TFun {
+                    tvar,
+                    body: Box::new(self.expr()?),
+                }
+            }
+            Token::Match => {
+                self.bump();
+                let scrutinee = self.ident()?;
+                self.expect(Token::With)?;
+                let mut arms = vec![];
+                while self.eat(Token::Bar) {
+                    let pattern = self.pattern()?;
+                    self.expect(Token::DoubleArrow)?;
+                    arms.push((pattern, self.expr()?));
+                }
+                self.expect(Token::End)?;
+                ExprKind::Match { scrutinee, arms }
+            }
+            Token::Builtin => {
+                self.bump();
+                let name = self.ident()?;
+                ExprKind::Builtin {
+                    name,
+                    args: self.idents(),
+                }
+            }
+            Token::At => {
+                self.bump();
+                let func = self.ident()?;
+                ExprKind::TApp {
+                    func,
+                    type_args: self.atypes()?,
+                }
+            }
+            Token::LBrace => self.message()?,
+            Token::Ident(name) => {
+                self.bump();
+                let args = self.idents();
+                if args.is_empty() {
+                    ExprKind::Var(name)
+                } else {
+                    ExprKind::App { func: name, args }
+                }
+            }
+            Token::CapIdent(name) if name != "Emp" && !self.is_literal_start() => {
+                self.bump();
+                let type_args = if self.eat(Token::LBrace) {
+                    let types = self.atypes()?;
+                    self.expect(Token::RBrace)?;
+                    types
+                } else {
+                    vec![]
+                };
+                ExprKind::Constructor {
+                    name,
+                    type_args,
+                    args: self.idents(),
+                }
+            }
+            _ => ExprKind::Literal(self.literal()?),
+        };
+        Ok(Expr {
+            kind,
+            span: self.span_from(start),
+        })
+    }
+
+    fn is_literal_start(&self) -> bool {
+        match (self.peek(), self.peek_nth(1)) {
+            (Token::Str(_) | Token::Hex(_), _) => true,
+            (Token::CapIdent(name), Token::Number(_)) => is_int_type(name) || name == "BNum",
+            (Token::CapIdent(name), _) => name == "Emp",
+            _ => false,
+        }
+    }
+
+    fn literal(&mut self) -> Result<Literal, Error> {
+        if !self.is_literal_start() {
+            return Err(self.error("a literal"));
+        }
+        let literal = match self.bump() {
+            Token::Str(s) => Literal::String(s),
+            Token::Hex(s) => Literal::ByStr(s),
+            Token::CapIdent(name) if name == "Emp" => {
+                let key = self.atype()?;
+                let value = self.atype()?;
+                Literal::EmptyMap(key, value)
+            }
+            Token::CapIdent(name) => {
+                let Token::Number(n) = self.bump() else {
+                    unreachable!()
+                };
+                if name == "BNum" {
+                    Literal::BNum(n)
+                } else {
+                    Literal::Int(named_type(name, vec![]), n)
+                }
+            }
+            _ => unreachable!(),
+        };
+        Ok(literal)
+    }
+
+    fn message(&mut self) -> Result<ExprKind, Error> {
+        self.expect(Token::LBrace)?;
+        let mut entries = vec![];
+        while *self.peek() != Token::RBrace {
+            if !entries.is_empty() {
+                self.expect(Token::Semicolon)?;
+                if *self.peek() == Token::RBrace {
+                    break;
+                }
+            }
+            let name = self.ident()?;
+            self.expect(Token::Colon)?;
+            let start = self.span();
+            let kind = match self.peek().clone() {
+                Token::Ident(var) => {
+                    self.bump();
+                    ExprKind::Var(var)
+                }
+                _ => ExprKind::Literal(self.literal()?),
+            };
+            entries.push(MessageEntry {
+                name,
+                value: Expr {
+                    kind,
+                    span: self.span_from(start),
+                },
+            });
+        }
+        self.expect(Token::RBrace)?;
+        Ok(ExprKind::Message(entries))
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, Error> {
+        self.nested(Self::pattern_at_depth)
+    }
+
+    fn pattern_at_depth(&mut self) -> Result<Pattern, Error> {
+        match self.peek() {
+            Token::CapIdent(_) => {
+                let name = self.cap_ident()?;
+                let mut args = vec![];
+                while matches!(
+                    self.peek(),
+                    Token::CapIdent(_) | Token::Ident(_) | Token::Underscore | Token::LParen
+                ) {
+                    args.push(self.arg_pattern()?);
+                }
+                Ok(Pattern::Constructor(name, args))
+            }
+            _ => self.arg_pattern(),
+        }
+    }
+
+    fn arg_pattern(&mut self) -> Result<Pattern, Error> {
+        let pattern = match self.peek().clone() {
+            Token::Underscore => Pattern::Wildcard,
+            Token::Ident(name) => Pattern::Binder(name),
+            Token::CapIdent(name) => Pattern::Constructor(name, vec![]),
+            Token::LParen => {
+                self.bump();
+                let pattern = self.pattern()?;
+                self.expect(Token::RParen)?;
+                return Ok(pattern);
+            }
+            _ => return Err(self.error("a pattern")),
+        };
+        self.bump();
+        Ok(pattern)
+    }
+
+    /// Parse a `;` separated list of statements, stopping before `end` or `|`.
+    fn stmts(&mut self) -> Result<Vec<Stmt>, Error> {
+        let mut stmts = vec![];
+        while !matches!(self.peek(), Token::End | Token::Bar | Token::Eof) {
+            stmts.push(self.stmt()?);
+            if !self.eat(Token::Semicolon) {
+                break;
+            }
+        }
+        Ok(stmts)
+    }
+
+    fn keys(&mut self) -> Result<Vec<String>, Error> {
+        let mut keys = vec![];
+        while self.eat(Token::LBracket) {
+            keys.push(self.ident()?);
+            self.expect(Token::RBracket)?;
+        }
+        Ok(keys)
+    }
+
+    fn stmt(&mut self) -> Result<Stmt, Error> {
+        self.nested(Self::stmt_at_depth)
+    }
+
+    fn stmt_at_depth(&mut self) -> Result<Stmt, Error> {
+        let start = self.span();
+        if !matches!(
+            self.peek(),
+            Token::Accept
+                | Token::Send
+                | Token::Event
+                | Token::Throw
+                | Token::Delete
+                | Token::Forall
+                | Token::Match
+                | Token::CapIdent(_)
+                | Token::Ident(_)
+        ) {
+            return Err(self.error("a statement"));
+        }
+        let kind = match self.bump() {
+            Token::Accept => StmtKind::Accept,
+            Token::Send => StmtKind::Send(self.ident()?),
+            Token::Event => StmtKind::Event(self.ident()?),
+            Token::Throw => match self.peek() {
+                Token::Ident(_) => StmtKind::Throw(Some(self.ident()?)),
+                _ => StmtKind::Throw(None),
+            },
+            Token::Delete => {
+                let map = self.ident()?;
+                let keys = self.keys()?;
+                StmtKind::MapDelete { map, keys }
+            }
+            Token::Forall => {
+                let list = self.ident()?;
+                let proc = self.any_ident()?;
+                StmtKind::Iterate { list, proc }
+            }
+            Token::Match => {
+                let scrutinee = self.ident()?;
+                self.expect(Token::With)?;
+                let mut arms = vec![];
+                while self.eat(Token::Bar) {
+                    let pattern = self.pattern()?;
+                    self.expect(Token::DoubleArrow)?;
+                    arms.push((pattern, self.stmts()?));
+                }
+                self.expect(Token::End)?;
+                StmtKind::Match { scrutinee, arms }
+            }
+            Token::CapIdent(name) => StmtKind::CallProc {
+                name,
+                args: self.idents(),
+            },
+            Token::Ident(lhs) => match self.peek() {
+                Token::LeftArrow => {
+                    self.bump();
+                    self.fetch(lhs)?
+                }
+                Token::Assign => {
+                    self.bump();
+                    StmtKind::Store {
+                        field: lhs,
+                        rhs: self.ident()?,
+                    }
+                }
+                Token::Equal => {
+                    self.bump();
+                    StmtKind::Bind {
+                        lhs,
+                        expr: self.expr()?,
+                    }
+                }
+                Token::LBracket => {
+                    let keys = self.keys()?;
+                    self.expect(Token::Assign)?;
+                    StmtKind::MapUpdate {
+                        map: lhs,
+                        keys,
+                        value: self.ident()?,
+                    }
+                }
+                _ => StmtKind::CallProc {
+                    name: lhs,
+                    args: self.idents(),
+                },
+            },
+            _ => unreachable!(),
+        };
+        Ok(Stmt {
+            kind,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse the right-hand side of `lhs <- ...`.
+    fn fetch(&mut self, lhs: String) -> Result<StmtKind, Error> {
+        if self.eat(Token::Ampersand) {
+            return self.remote_fetch(lhs);
+        }
+        if self.eat(Token::Exists) {
+            let map = self.ident()?;
+            let keys = self.keys()?;
+            return Ok(StmtKind::MapGet {
+                lhs,
+                map,
+                keys,
+                exists: true,
+            });
+        }
+        let field = self.ident()?;
+        if *self.peek() == Token::LBracket {
+            let keys = self.keys()?;
+            Ok(StmtKind::MapGet {
+                lhs,
+                map: field,
+                keys,
+                exists: false,
+            })
+        } else {
+            Ok(StmtKind::Load { lhs, field })
+        }
+    }
+
+    fn remote_fetch(&mut self, lhs: String) -> Result<StmtKind, Error> {
+        if let Token::CapIdent(_) = self.peek() {
+            let query = self.cap_ident()?;
+            let mut args = vec![];
+            if self.eat(Token::LParen) {
+                while *self.peek() != Token::RParen {
+                    if !args.is_empty() {
+                        self.expect(Token::Comma)?;
+                    }
+                    args.push(self.ident()?);
+                }
+                self.expect(Token::RParen)?;
+            }
+            return Ok(StmtKind::ReadFromBC { lhs, query, args });
+        }
+
+        let exists = self.eat(Token::Exists);
+        let address = self.ident()?;
+        if !exists && self.eat(Token::As) {
+            return Ok(StmtKind::TypeCast {
+                lhs,
+                address,
+                ty: self.ty()?,
+            });
+        }
+        self.expect(Token::Dot)?;
+        let field = self.ident()?;
+        if exists || *self.peek() == Token::LBracket {
+            let keys = self.keys()?;
+            Ok(StmtKind::RemoteMapGet {
+                lhs,
+                address,
+                map: field,
+                keys,
+                exists,
+            })
+        } else {
+            Ok(StmtKind::RemoteLoad {
+                lhs,
+                address,
+                field,
+            })
+        }
+    }
+}
+
+fn is_int_type(name: &str) -> bool {
+    matches!(
+        name,
+        "Int32" | "Int64" | "Int128" | "Int256" | "Uint32" | "Uint64" | "Uint128" | "Uint256"
+    )
+}
+
+/// Build a [`Type`] out of a type name and its arguments.
+fn named_type(name: String, mut args: Vec<Type>) -> Type {
+    let arg = |args: &mut Vec<Type>| Box::new(args.remove(0));
+    match (name.as_str(), args.len()) {
+        ("Int32", 0) => Type::Int32,
+        ("Int64", 0) => Type::Int64,
+        ("Int128", 0) => Type::Int128,
+        ("Int256", 0) => Type::Int256,
+        ("Uint32", 0) => Type::Uint32,
+        ("Uint64", 0) => Type::Uint64,
+        ("Uint128", 0) => Type::Uint128,
+        ("Uint256", 0) => Type::Uint256,
+        ("String", 0) => Type::String,
+        ("BNum", 0) => Type::BNum,
+        ("Bool", 0) => Type::Bool,
+        ("Map", 2) => Type::Map(arg(&mut args), arg(&mut args)),
+        ("Option", 1) => Type::Option(arg(&mut args)),
+        ("List", 1) => Type::List(arg(&mut args)),
+        ("Pair", 2) => Type::Pair(arg(&mut args), arg(&mut args)),
+        (_, 0) => match name.strip_prefix("ByStr").map(str::parse) {
+            Some(Ok(n)) => Type::ByStr(n),
+            _ => Type::Other(name),
+        },
+        _ => {
+            let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+            Type::Other(format!("({} {})", name, args.join(" ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_types() {
+        assert_eq!(
+            parse_type("Map ByStr20 (Map ByStr20 Uint128)").unwrap(),
+            Type::Map(
+                Box::new(Type::ByStr(20)),
+                Box::new(Type::Map(
+                    Box::new(Type::ByStr(20)),
+                    Box::new(Type::Uint128)
+                ))
+            )
+        );
+        assert_eq!(
+            parse_type("forall 'A. List 'A -> Uint32").unwrap(),
+            Type::PolyFun(
+                "'A".to_string(),
+                Box::new(Type::Function(
+                    Box::new(Type::List(Box::new(Type::TypeVar("'A".to_string())))),
+                    Box::new(Type::Uint32)
+                ))
+            )
+        );
+        assert_eq!(
+            parse_type("ByStr20 with contract field f : Uint32 end").unwrap(),
+            Type::Address(Some(FieldList(vec![Field::new("f", Type::Uint32)])))
+        );
+    }
+
+    #[test]
+    fn test_parse_expr() {
+        let expr = parse_expr("let nil = Nil {Message} in Cons {Message} msg nil").unwrap();
+        let ExprKind::Let {
+            name, value, body, ..
+        } = expr.kind
+        else {
+            panic!("Expected a let expression");
+        };
+        assert_eq!(name, "nil");
+        assert_eq!(
+            value.kind,
+            ExprKind::Constructor {
+                name: "Nil".to_string(),
+                type_args: vec![Type::Other("Message".to_string())],
+                args: vec![]
+            }
+        );
+        assert_eq!(
+            body.kind,
+            ExprKind::Constructor {
+                name: "Cons".to_string(),
+                type_args: vec![Type::Other("Message".to_string())],
+                args: vec!["msg".to_string(), "nil".to_string()]
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_nesting_depth() {
+        let nested_type = |depth| "(".repeat(depth) + "Uint32" + &")".repeat(depth);
+        assert_eq!(
+            parse_type(&nested_type(MAX_DEPTH - 1)).unwrap(),
+            Type::Uint32
+        );
+        assert!(matches!(
+            parse_type(&nested_type(MAX_DEPTH)),
+            Err(Error::SyntaxError { message, .. }) if message == "Nesting deeper than 100 levels"
+        ));
+        let nested_lets = |depth| "let x = Uint32 0 in ".repeat(depth) + "x";
+        assert!(parse_expr(&nested_lets(MAX_DEPTH - 1)).is_ok());
+        assert!(matches!(
+            parse_expr(&nested_lets(MAX_DEPTH)),
+            Err(Error::SyntaxError { message, .. }) if message == "Nesting deeper than 100 levels"
+        ));
+    }
+
+    #[test]
+    fn test_parse_statements() {
+        let module = parse_module(
+            r#"scilla_version 0
+            contract Test ()
+            transition T (to : ByStr20)
+              bal <- balances[to];
+              match bal with
+              | Some b => e = {_eventname : "Found"; balance : b}; event e
+              | None => delete balances[to]
+              end
+            end"#,
+        )
+        .unwrap();
+        let body = &module.contract.components[0].body;
+        assert_eq!(body.len(), 2);
+        assert_eq!(
+            body[0].kind,
+            StmtKind::MapGet {
+                lhs: "bal".to_string(),
+                map: "balances".to_string(),
+                keys: vec!["to".to_string()],
+                exists: false
+            }
+        );
+        let StmtKind::Match { arms, .. } = &body[1].kind else {
+            panic!("Expected a match statement");
+        };
+        assert_eq!(
+            arms[0].0,
+            Pattern::Constructor("Some".to_string(), vec![Pattern::Binder("b".to_string())])
+        );
+        assert_eq!(arms[0].1.len(), 2);
+        assert_eq!(body[1].span.line, 5);
+    }
+}
@@ -0,0 +1,516 @@
+//! Pretty-printing of the [syntax tree](crate::ast) back to Scilla source.
+//!
+//! The output parses to the same tree, up to spans, so that tools can modify a contract
+//! programmatically and write it out again. The [`Docs`](crate::Docs) comments of declarations are
+//! printed back, other comments are not part of the tree and are lost.
+//!
+//! ```
+//! use scilla_parser::{parser::parse_module, printer::PrettyPrinter};
+//! let module = parse_module("scilla_version 0 contract Counter () field n : Uint32 = Uint32 0").unwrap();
+//! let source = PrettyPrinter::default().with_indent(4).print_module(&module);
+//! assert_eq!(source, "scilla_version 0\n\ncontract Counter ()\n\nfield n : Uint32 = Uint32 0\n");
+//! assert_eq!(parse_module(&source).unwrap().contract.fields[0].name, "n");
+//! ```
+
+use std::fmt::{Display, Write};
+
+use crate::{
+    ast::{
+        Component, ComponentKind, ContractDef, Expr, ExprKind, LibEntry, Library, Literal, Module,
+        Param, Pattern, Stmt, StmtKind,
+    },
+    Docs, Type,
+};
+
+/// Prints syntax trees as Scilla source with a configurable indentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyPrinter {
+    indent: usize,
+}
+
+impl Default for PrettyPrinter {
+    fn default() -> Self {
+        Self { indent: 2 }
+    }
+}
+
+impl PrettyPrinter {
+    /// Use `indent` spaces per nesting level instead of 2.
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    fn pad(&self, level: usize) -> String {
+        " ".repeat(self.indent * level)
+    }
+
+    /// Print a whole module.
+    pub fn print_module(&self, module: &Module) -> String {
+        let mut out = format!("scilla_version {}\n", module.scilla_version);
+        if !module.imports.is_empty() {
+            let imports: Vec<String> = module
+                .imports
+                .iter()
+                .map(|import| match &import.alias {
+                    Some(alias) => format!("{} as {}", import.name, alias),
+                    None => import.name.clone(),
+                })
+                .collect();
+            let _ = write!(out, "\nimport {}\n", imports.join(" "));
+        }
+        if let Some(library) = &module.library {
+            out.push('\n');
+            out.push_str(&self.print_library(library));
+        }
+        out.push('\n');
+        out.push_str(&self.print_contract(&module.contract));
+        out
+    }
+
+    /// Print a library with a blank line between its entries.
+    pub fn print_library(&self, library: &Library) -> String {
+        let mut out = format!("library {}\n", library.name);
+        for entry in &library.entries {
+            out.push('\n');
+            out.push_str(&print_docs(entry.docs()));
+            out.push_str(&self.print_lib_entry(entry));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Print a `let` or `type` library entry.
+    pub fn print_lib_entry(&self, entry: &LibEntry) -> String {
+        match entry {
+            LibEntry::Let { name, ty, expr, .. } => {
+                let ty = ty
+                    .as_ref()
+                    .map(|ty| format!(" : {}", print_type(ty)))
+                    .unwrap_or_default();
+                format!("let {name}{ty} ={}", self.rhs(expr, 0))
+            }
+            LibEntry::Type {
+                name, constructors, ..
+            } => {
+                let mut out = format!("type {name} =");
+                for ctr in constructors {
+                    let _ = write!(out, "\n| {}", ctr.name);
+                    if !ctr.args.is_empty() {
+                        let args: Vec<String> = ctr.args.iter().map(print_atype).collect();
+                        let _ = write!(out, " of {}", args.join(" "));
+                    }
+                }
+                out
+            }
+        }
+    }
+
+    fn print_contract(&self, contract: &ContractDef) -> String {
+        let mut out = print_docs(&contract.docs);
+        let _ = write!(out, "contract {}", contract.name);
+        if contract.params.is_empty() {
+            out.push_str(" ()\n");
+        } else {
+            out.push_str("\n(\n");
+            let params: Vec<String> = contract
+                .params
+                .iter()
+                .map(|p| format!("{}{} : {}", self.pad(1), p.name, print_type(&p.ty)))
+                .collect();
+            out.push_str(&params.join(",\n"));
+            out.push_str("\n)\n");
+        }
+        if let Some(constraint) = &contract.constraint {
+            if is_simple(constraint) {
+                let _ = writeln!(out, "with {} =>", self.expr(constraint, 0));
+            } else {
+                let _ = writeln!(out, "with\n{}{}\n=>", self.pad(1), self.expr(constraint, 1));
+            }
+        }
+        if !contract.fields.is_empty() {
+            out.push('\n');
+        }
+        for field in &contract.fields {
+            out.push_str(&print_docs(&field.docs));
+            let _ = writeln!(
+                out,
+                "field {} : {} ={}",
+                field.name,
+                print_type(&field.ty),
+                self.rhs(&field.init, 0)
+            );
+        }
+        for component in &contract.components {
+            out.push('\n');
+            out.push_str(&self.print_component(component));
+        }
+        out
+    }
+
+    /// Print a transition or a procedure.
+    pub fn print_component(&self, component: &Component) -> String {
+        let keyword = match component.kind {
+            ComponentKind::Transition => "transition",
+            ComponentKind::Procedure => "procedure",
+        };
+        let mut out = print_docs(&component.docs);
+        let _ = writeln!(
+            out,
+            "{keyword} {} {}",
+            component.name,
+            print_params(&component.params)
+        );
+        out.push_str(&self.stmts(&component.body, 1));
+        out.push_str("end\n");
+        out
+    }
+
+    /// Print a sequence of statements, one per line.
+    pub fn print_stmts(&self, stmts: &[Stmt]) -> String {
+        self.stmts(stmts, 0)
+    }
+
+    /// Print an expression.
+    pub fn print_expr(&self, expr: &Expr) -> String {
+        self.expr(expr, 0)
+    }
+
+    /// The right-hand side of `=`, on the same line if it is short and indented on the next
+    /// lines otherwise.
+    fn rhs(&self, expr: &Expr, level: usize) -> String {
+        if is_simple(expr) {
+            format!(" {}", self.expr(expr, level))
+        } else {
+            format!("\n{}{}", self.pad(level + 1), self.expr(expr, level + 1))
+        }
+    }
+
+    /// Statements at the given level, each on its own line and separated by `;`.
+    fn stmts(&self, stmts: &[Stmt], level: usize) -> String {
+        let mut out = String::new();
+        for (i, stmt) in stmts.iter().enumerate() {
+            out.push_str(&self.pad(level));
+            out.push_str(&self.stmt(stmt, level));
+            if i + 1 < stmts.len() {
+                out.push(';');
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// A statement starting at the current position of a line indented by `level`.
+    fn stmt(&self, stmt: &Stmt, level: usize) -> String {
+        let keys = |keys: &[String]| -> String { keys.iter().map(|k| format!("[{k}]")).collect() };
+        let exists = |exists: bool| if exists { "exists " } else { "" };
+        match &stmt.kind {
+            StmtKind::Load { lhs, field } => format!("{lhs} <- {field}"),
+            StmtKind::Store { field, rhs } => format!("{field} := {rhs}"),
+            StmtKind::Bind { lhs, expr } => format!("{lhs} ={}", self.rhs(expr, level)),
+            StmtKind::MapUpdate {
+                map,
+                keys: k,
+                value,
+            } => format!("{map}{} := {value}", keys(k)),
+            StmtKind::MapDelete { map, keys: k } => format!("delete {map}{}", keys(k)),
+            StmtKind::MapGet {
+                lhs,
+                map,
+                keys: k,
+                exists: e,
+            } => format!("{lhs} <- {}{map}{}", exists(*e), keys(k)),
+            StmtKind::RemoteLoad {
+                lhs,
+                address,
+                field,
+            } => format!("{lhs} <- & {address}.{field}"),
+            StmtKind::RemoteMapGet {
+                lhs,
+                address,
+                map,
+                keys: k,
+                exists: e,
+            } => format!("{lhs} <- & {}{address}.{map}{}", exists(*e), keys(k)),
+            StmtKind::TypeCast { lhs, address, ty } => {
+                format!("{lhs} <- & {address} as {}", print_type(ty))
+            }
+            StmtKind::ReadFromBC { lhs, query, args } if args.is_empty() => {
+                format!("{lhs} <- & {query}")
+            }
+            StmtKind::ReadFromBC { lhs, query, args } => {
+                format!("{lhs} <- & {query}({})", args.join(", "))
+            }
+            StmtKind::Accept => "accept".to_string(),
+            StmtKind::Send(msgs) => format!("send {msgs}"),
+            StmtKind::Event(event) => format!("event {event}"),
+            StmtKind::Throw(Some(error)) => format!("throw {error}"),
+            StmtKind::Throw(None) => "throw".to_string(),
+            StmtKind::Match { scrutinee, arms } => {
+                let mut out = format!("match {scrutinee} with\n");
+                for (pattern, stmts) in arms {
+                    let _ = writeln!(out, "{}| {} =>", self.pad(level), print_pattern(pattern));
+                    out.push_str(&self.stmts(stmts, level + 1));
+                }
+                out.push_str(&self.pad(level));
+                out.push_str("end");
+                out
+            }
+            StmtKind::CallProc { name, args } => apply(name, args),
+            StmtKind::Iterate { list, proc } => format!("forall {list} {proc}"),
+        }
+    }
+
+    /// An expression starting at the current position of a line indented by `level`.
+    fn expr(&self, expr: &Expr, level: usize) -> String {
+        match &expr.kind {
+            ExprKind::Literal(literal) => print_literal(literal),
+            ExprKind::Var(name) => name.clone(),
+            ExprKind::Message(entries) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|e| format!("{} : {}", e.name, self.expr(&e.value, level)))
+                    .collect();
+                format!("{{{}}}", entries.join("; "))
+            }
+            ExprKind::Let {
+                name,
+                ty,
+                value,
+                body,
+            } => {
+                let ty = ty
+                    .as_ref()
+                    .map(|ty| format!(" : {}", print_type(ty)))
+                    .unwrap_or_default();
+                let pad = self.pad(level);
+                if is_simple(value) {
+                    format!(
+                        "let {name}{ty} = {} in\n{pad}{}",
+                        self.expr(value, level),
+                        self.expr(body, level)
+                    )
+                } else {
+                    format!(
+                        "let {name}{ty} ={}\n{pad}in\n{pad}{}",
+                        self.rhs(value, level),
+                        self.expr(body, level)
+                    )
+                }
+            }
+            ExprKind::Fun { param, ty, body } => format!(
+                "fun ({param} : {}) =>\n{}{}",
+                print_type(ty),
+                self.pad(level + 1),
+                self.expr(body, level + 1)
+            ),
+            ExprKind::TFun { tvar, body } => format!(
+                "tfun {tvar} =>\n{}{}",
+                self.pad(level + 1),
+                self.expr(body, level + 1)
+            ),
+            ExprKind::App { func, args } => apply(func, args),
+            ExprKind::Constructor {
+                name,
+                type_args,
+                args,
+            } => {
+                let mut out = name.clone();
+                if !type_args.is_empty() {
+                    let types: Vec<String> = type_args.iter().map(print_atype).collect();
+                    let _ = write!(out, " {{{}}}", types.join(" "));
+                }
+                for arg in args {
+                    let _ = write!(out, " {arg}");
+                }
+                out
+            }
+            ExprKind::Builtin { name, args } => format!("builtin {}", apply(name, args)),
+            ExprKind::Match { scrutinee, arms } => {
+                let pad = self.pad(level);
+                let mut out = format!("match {scrutinee} with");
+                for (pattern, expr) in arms {
+                    let _ = write!(out, "\n{pad}| {} =>", print_pattern(pattern));
+                    out.push_str(&self.rhs(expr, level));
+                }
+                let _ = write!(out, "\n{pad}end");
+                out
+            }
+            ExprKind::TApp { func, type_args } => {
+                let types: Vec<String> = type_args.iter().map(print_atype).collect();
+                format!("@{func} {}", types.join(" "))
+            }
+        }
+    }
+}
+
+/// Whether an expression is printed on a single line.
+fn is_simple(expr: &Expr) -> bool {
+    !matches!(
+        expr.kind,
+        ExprKind::Let { .. }
+            | ExprKind::Fun { .. }
+            | ExprKind::TFun { .. }
+            | ExprKind::Match { .. }
+    )
+}
+
+fn apply(func: &str, args: &[String]) -> String {
+    std::iter::once(func)
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The comments of a declaration, each on its own line.
+fn print_docs(docs: &Docs) -> String {
+    docs.comments.iter().map(|c| format!("{c}\n")).collect()
+}
+
+fn print_params(params: &[Param]) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|p| format!("{} : {}", p.name, print_type(&p.ty)))
+        .collect();
+    format!("({})", params.join(", "))
+}
+
+/// Print a type in source syntax, e.g. `Map ByStr20 (Option Uint128)`.
+pub fn print_type(ty: &Type) -> String {
+    match ty {
+        Type::Map(k, v) => format!("Map {} {}", print_atype(k), print_atype(v)),
+        Type::Option(t) => format!("Option {}", print_atype(t)),
+        Type::List(t) => format!("List {}", print_atype(t)),
+        Type::Pair(a, b) => format!("Pair {} {}", print_atype(a), print_atype(b)),
+        Type::Address(Some(fields)) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|f| format!("field {} : {}", f.name, print_type(&f.r#type)))
+                .collect();
+            if fields.is_empty() {
+                "ByStr20 with contract end".to_string()
+            } else {
+                format!("ByStr20 with contract {} end", fields.join(", "))
+            }
+        }
+        Type::Function(a, b) => match **a {
+            Type::Function(..) | Type::PolyFun(..) => {
+                format!("({}) -> {}", print_type(a), print_type(b))
+            }
+            _ => format!("{} -> {}", print_type(a), print_type(b)),
+        },
+        Type::PolyFun(tvar, t) => format!("forall {tvar}. {}", print_type(t)),
+        _ => ty.to_string(),
+    }
+}
+
+/// Print a type, in parentheses unless it is a single name.
+fn print_atype(ty: &Type) -> String {
+    match ty {
+        Type::Map(..)
+        | Type::Option(_)
+        | Type::List(_)
+        | Type::Pair(..)
+        | Type::Address(_)
+        | Type::Function(..)
+        | Type::PolyFun(..) => format!("({})", print_type(ty)),
+        Type::Other(name) if name.contains(' ') && !name.starts_with('(') => format!("({name})"),
+        _ => print_type(ty),
+    }
+}
+
+fn print_literal(literal: &Literal) -> String {
+    match literal {
+        Literal::Int(ty, n) => format!("{ty} {n}"),
+        Literal::String(s) => {
+            let mut out = String::from('"');
+            for c in s.chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    '\n' => out.push_str("\\n"),
+                    '\t' => out.push_str("\\t"),
+                    '\r' => out.push_str("\\r"),
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+            out
+        }
+        Literal::ByStr(hex) => hex.clone(),
+        Literal::BNum(n) => format!("BNum {n}"),
+        Literal::EmptyMap(k, v) => format!("Emp {} {}", print_atype(k), print_atype(v)),
+    }
+}
+
+/// Print a pattern, e.g. `Some (Pair a _)`.
+pub fn print_pattern(pattern: &Pattern) -> String {
+    match pattern {
+        Pattern::Wildcard => "_".to_string(),
+        Pattern::Binder(name) => name.clone(),
+        Pattern::Constructor(name, args) => {
+            let mut out = name.clone();
+            for arg in args {
+                match arg {
+                    Pattern::Constructor(_, nested) if !nested.is_empty() => {
+                        let _ = write!(out, " ({})", print_pattern(arg));
+                    }
+                    _ => {
+                        let _ = write!(out, " {}", print_pattern(arg));
+                    }
+                }
+            }
+            out
+        }
+    }
+}
+
+impl Display for Module {
+    /// Print the module with the default [`PrettyPrinter`].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&PrettyPrinter::default().print_module(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_expr, parse_type};
+
+    #[test]
+    fn test_print_type() {
+        for source in [
+            "Map ByStr20 (Map ByStr20 Uint128)",
+            "forall 'A. List 'A -> Uint32",
+            "(Uint32 -> Bool) -> List Uint32 -> Bool",
+            "ByStr20 with contract field f : Map ByStr20 Uint32, field g : ByStr20 with end end",
+            "Option (Pair ByStr20 BNum)",
+        ] {
+            assert_eq!(print_type(&parse_type(source).unwrap()), source);
+        }
+    }
+
+    #[test]
+    fn test_print_expr() {
+        let printer = PrettyPrinter::default().with_indent(4);
+        let expr = parse_expr(
+            "fun (a : Option Uint32) => let zero = Uint32 0 in match a with | Some x => x | None => zero end",
+        )
+        .unwrap();
+        assert_eq!(
+            printer.print_expr(&expr),
+            "fun (a : Option Uint32) =>
+    let zero = Uint32 0 in
+    match a with
+    | Some x => x
+    | None => zero
+    end"
+        );
+
+        let expr = parse_expr(r#"{_eventname : "Quoted \"name\""; amount : Uint128 1}"#).unwrap();
+        assert_eq!(
+            printer.print_expr(&expr),
+            r#"{_eventname : "Quoted \"name\""; amount : Uint128 1}"#
+        );
+    }
+}
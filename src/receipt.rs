@@ -0,0 +1,305 @@
+//! Decoding of transaction receipts against a parsed [`Contract`].
+
+use std::str::FromStr;
+
+use serde_json::Value as JsonValue;
+
+use crate::{
+    ast::{Component, Library, Module},
+    infer::{Construction, Context},
+    parser::parse_type,
+    Contract, Error, Value,
+};
+
+/// A parameter of an event or a message as it appears in a receipt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceiptParam {
+    pub name: String,
+    /// The type of the parameter in Scilla syntax, e.g. `Uint128`.
+    pub r#type: String,
+    pub value: JsonValue,
+}
+
+/// An entry of the `event_logs` of a receipt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventLog {
+    /// Address of the contract emitting the event.
+    pub address: String,
+    pub name: String,
+    pub params: Vec<ReceiptParam>,
+}
+
+/// An entry of the `transitions` of a receipt, i.e. a message sent by a contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageLog {
+    /// Address of the contract sending the message.
+    pub address: String,
+    pub depth: u64,
+    pub tag: String,
+    pub recipient: String,
+    pub amount: String,
+    pub params: Vec<ReceiptParam>,
+}
+
+/// The receipt of a transaction, as returned by the `GetTransaction` API.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Receipt {
+    pub success: bool,
+    pub event_logs: Vec<EventLog>,
+    pub transitions: Vec<MessageLog>,
+}
+
+impl FromStr for Receipt {
+    type Err = Error;
+
+    /// Parse a receipt from JSON. Both the whole transaction and its `receipt` object are accepted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json: JsonValue = serde_json::from_str(s)?;
+        let receipt = json.get("receipt").unwrap_or(&json);
+        let string = |v: &JsonValue| v.as_str().unwrap_or_default().to_string();
+        let params = |v: &JsonValue| -> Vec<ReceiptParam> {
+            v.as_array()
+                .map(|params| {
+                    params
+                        .iter()
+                        .map(|p| ReceiptParam {
+                            name: string(&p["vname"]),
+                            r#type: string(&p["type"]),
+                            value: p["value"].clone(),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let entries = |key: &str| receipt[key].as_array().cloned().unwrap_or_default();
+
+        Ok(Receipt {
+            success: receipt["success"].as_bool().unwrap_or_default(),
+            event_logs: entries("event_logs")
+                .iter()
+                .map(|e| EventLog {
+                    address: string(&e["address"]),
+                    name: string(&e["_eventname"]),
+                    params: params(&e["params"]),
+                })
+                .collect(),
+            transitions: entries("transitions")
+                .iter()
+                .map(|t| MessageLog {
+                    address: string(&t["addr"]),
+                    depth: t["depth"].as_u64().unwrap_or_default(),
+                    tag: string(&t["msg"]["_tag"]),
+                    recipient: string(&t["msg"]["_recipient"]),
+                    amount: string(&t["msg"]["_amount"]),
+                    params: params(&t["msg"]["params"]),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// A parameter of a receipt decoded into a [`Value`], or left as it is in the receipt.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodedValue {
+    Value(Value),
+    /// The type or the value could not be decoded, e.g. a value of an ADT defined by another
+    /// contract in the call chain.
+    Raw {
+        r#type: String,
+        value: JsonValue,
+    },
+}
+
+impl DecodedValue {
+    /// Decode `value` of the type `ty` written in Scilla syntax. The address qualifiers of ADTs,
+    /// as in `0x1234….Error`, are dropped and the ADTs looked up in `library`.
+    fn decode(ty: &str, value: &JsonValue, library: Option<&Library>) -> Self {
+        let decoded = parse_type(&unqualified(ty))
+            .and_then(|parsed| Value::from_json(value, &parsed, library));
+        match decoded {
+            Ok(value) => DecodedValue::Value(value),
+            Err(_) => DecodedValue::Raw {
+                r#type: ty.to_string(),
+                value: value.clone(),
+            },
+        }
+    }
+
+    /// The decoded value, `None` if it was left raw.
+    pub fn value(&self) -> Option<&Value> {
+        match self {
+            DecodedValue::Value(value) => Some(value),
+            DecodedValue::Raw { .. } => None,
+        }
+    }
+}
+
+/// `ty` without the `0x….` address qualifiers of its ADTs.
+fn unqualified(ty: &str) -> String {
+    let mut result = String::new();
+    let mut rest = ty;
+    while let Some(start) = rest.find("0x") {
+        let (before, after) = rest.split_at(start);
+        result.push_str(before);
+        let digits = after[2..]
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(after.len() - 2);
+        match after[2 + digits..].strip_prefix('.') {
+            Some(tail) if digits == 40 => rest = tail,
+            _ => {
+                result.push_str(&after[..2 + digits]);
+                rest = &after[2 + digits..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// An event of a receipt with its parameters decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedEvent {
+    pub address: String,
+    pub name: String,
+    pub params: Vec<(String, DecodedValue)>,
+    /// Transitions of the contract that can emit this event. Empty if the contract cannot emit it.
+    pub emitted_by: Vec<String>,
+}
+
+impl DecodedEvent {
+    /// Returns false if the contract has no transition that can emit this event.
+    pub fn is_known(&self) -> bool {
+        !self.emitted_by.is_empty()
+    }
+}
+
+/// A message of a receipt with its parameters decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedMessage {
+    pub sender: String,
+    pub tag: String,
+    pub recipient: String,
+    pub amount: DecodedValue,
+    pub params: Vec<(String, DecodedValue)>,
+    /// Transitions of the contract that can send this message. Empty if the contract cannot send
+    /// it.
+    pub sent_by: Vec<String>,
+}
+
+impl DecodedMessage {
+    /// Returns false if the contract has no transition that can send this message.
+    pub fn is_known(&self) -> bool {
+        !self.sent_by.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DecodedReceipt {
+    pub events: Vec<DecodedEvent>,
+    pub messages: Vec<DecodedMessage>,
+}
+
+impl DecodedReceipt {
+    /// Events the contract cannot emit.
+    pub fn unknown_events(&self) -> impl Iterator<Item = &DecodedEvent> {
+        self.events.iter().filter(|e| !e.is_known())
+    }
+}
+
+impl Contract {
+    /// Decode the events and messages of a receipt and match them to the transitions of this
+    /// contract that can produce them.
+    ///
+    /// The receipt may contain entries produced by other contracts in the call chain. When the
+    /// `address` this contract is deployed at is given, the entries of other addresses are
+    /// reported as unknown; otherwise entries are matched by name only. Parameters whose type or
+    /// value cannot be decoded, e.g. values of ADTs defined by other contracts, are kept as
+    /// [`DecodedValue::Raw`].
+    ///
+    /// Fails with [`Error::NoSource`] if the contract was not parsed from its Scilla source.
+    pub fn decode_receipt(
+        &self,
+        receipt: &Receipt,
+        address: Option<&str>,
+    ) -> Result<DecodedReceipt, Error> {
+        let module = self.syntax_tree()?;
+        let library = module.library.as_ref();
+        let constructions = Context::new(module).constructions();
+        let decode_params = |params: &[ReceiptParam]| {
+            params
+                .iter()
+                .map(|p| {
+                    let value = DecodedValue::decode(&p.r#type, &p.value, library);
+                    (p.name.clone(), value)
+                })
+                .collect()
+        };
+        let sources = |sender: &str, key: &str, name: &str| {
+            if address.is_some_and(|address| !same_address(address, sender)) {
+                return vec![];
+            }
+            transitions_constructing(module, &constructions, key, name)
+        };
+
+        let events = receipt
+            .event_logs
+            .iter()
+            .map(|e| DecodedEvent {
+                address: e.address.clone(),
+                name: e.name.clone(),
+                params: decode_params(&e.params),
+                emitted_by: sources(&e.address, "_eventname", &e.name),
+            })
+            .collect();
+
+        let messages = receipt
+            .transitions
+            .iter()
+            .map(|t| DecodedMessage {
+                sender: t.address.clone(),
+                tag: t.tag.clone(),
+                recipient: t.recipient.clone(),
+                amount: DecodedValue::decode(
+                    "Uint128",
+                    &JsonValue::String(t.amount.clone()),
+                    library,
+                ),
+                params: decode_params(&t.params),
+                sent_by: sources(&t.address, "_tag", &t.tag),
+            })
+            .collect();
+
+        Ok(DecodedReceipt { events, messages })
+    }
+}
+
+/// Returns true if `a` and `b` are the same address, ignoring the case and the `0x` prefix.
+fn same_address(a: &str, b: &str) -> bool {
+    let digits = |address: &str| address.trim_start_matches("0x").to_ascii_lowercase();
+    digits(a) == digits(b)
+}
+
+/// Names of the transitions that construct, among the message literals of `constructions`, one
+/// whose `key` entry (`_eventname` or `_tag`) may be `name`, directly, through the procedures they
+/// call or through library functions. Entries that are not string literals may hold any name.
+fn transitions_constructing(
+    module: &Module,
+    constructions: &[Construction],
+    key: &str,
+    name: &str,
+) -> Vec<String> {
+    let constructs = |component: &Component| {
+        constructions.iter().any(|c| {
+            c.component.name == component.name
+                && c.entry(key).is_some()
+                && c.string_entry(key).is_none_or(|n| n == name)
+        })
+    };
+
+    let contract = &module.contract;
+    contract
+        .transitions()
+        .filter(|t| contract.reachable_from(t).into_iter().any(constructs))
+        .map(|t| t.name.clone())
+        .collect()
+}
@@ -0,0 +1,656 @@
+//! A simulator of transitions without a blockchain: the contract is deployed into an in-memory
+//! state initialised from its field initializers, and transitions are executed against it with a
+//! given blockchain context. Expressions are evaluated by the [`Interpreter`]. A transition that
+//! throws or fails leaves the state unchanged, like a failed transaction.
+
+use std::collections::HashMap;
+
+use num_bigint::BigInt;
+use serde_json::Value as JsonValue;
+
+use crate::{
+    ast::{Component, Module, Stmt, StmtKind},
+    gas::field_type,
+    interpreter::{eval, into_value, matches, no_arm, runtime, Env, Val},
+    Contract, Error, Interpreter, Type, Value,
+};
+
+/// The address of the simulated contract, `_this_address`, unless set with
+/// [`Simulator::with_address`].
+const DEFAULT_ADDRESS: [u8; 20] = [0xc0; 20];
+
+/// The blockchain context of a transition call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallContext {
+    /// `_sender`, the address calling the transition.
+    pub sender: [u8; 20],
+    /// `_origin`, the address that signed the transaction.
+    pub origin: [u8; 20],
+    /// `_amount`, the amount of ZIL sent with the call, in Qa.
+    pub amount: u128,
+    /// `BLOCKNUMBER`, the number of the current block.
+    pub block_number: u64,
+    /// `CHAINID`
+    pub chain_id: u32,
+    /// The timestamp of the current block in microseconds, read with `TIMESTAMP`.
+    pub timestamp: u64,
+}
+
+impl CallContext {
+    /// A call by `sender`, which is also its origin, without ZIL at block 1 of chain 1.
+    pub fn new(sender: [u8; 20]) -> Self {
+        Self {
+            sender,
+            origin: sender,
+            amount: 0,
+            block_number: 1,
+            chain_id: 1,
+            timestamp: 0,
+        }
+    }
+
+    pub fn with_origin(mut self, origin: [u8; 20]) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn with_amount(mut self, amount: u128) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    pub fn with_block_number(mut self, block_number: u64) -> Self {
+        self.block_number = block_number;
+        self
+    }
+
+    pub fn with_chain_id(mut self, chain_id: u32) -> Self {
+        self.chain_id = chain_id;
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+}
+
+/// The result of a transition that did not throw.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Execution {
+    /// The fields after the call, `_balance` first and then in the order they are declared.
+    pub state: Vec<(String, Value)>,
+    /// The events emitted, in order.
+    pub events: Vec<Value>,
+    /// The messages sent, in order.
+    pub messages: Vec<Value>,
+    /// The amount of ZIL accepted, zero if the transition did not `accept`.
+    pub accepted: u128,
+}
+
+/// Executes the transitions of a contract against an in-memory state.
+///
+/// # Examples
+///
+/// ```
+/// use scilla_parser::{CallContext, Contract, Error, Simulator, Type, Value};
+/// let contract = Contract::from_source(
+///     "scilla_version 0
+///     contract Counter (max : Uint32)
+///     field count : Uint32 = Uint32 0
+///     transition Increment ()
+///       c <- count;
+///       one = Uint32 1;
+///       c = builtin add c one;
+///       too_large = builtin lt max c;
+///       match too_large with
+///       | True =>
+///         e = { _exception : \"TooLarge\" };
+///         throw e
+///       | False =>
+///         count := c;
+///         e = { _eventname : \"Incremented\"; count : c };
+///         event e
+///       end
+///     end",
+/// )
+/// .unwrap();
+/// let one = Value::int(Type::Uint32, 1).unwrap();
+/// let mut simulator = Simulator::new(&contract, &[("max", one.clone())]).unwrap();
+/// let context = CallContext::new([1; 20]);
+/// let execution = simulator.call("Increment", &[], &context).unwrap();
+/// assert_eq!(
+///     execution.events[0].to_string(),
+///     "{_eventname : \"Incremented\"; count : Uint32 1}"
+/// );
+/// assert_eq!(simulator.field("count"), Some(&one));
+///
+/// let result = simulator.call("Increment", &[], &context);
+/// assert!(matches!(result, Err(Error::Exception(Some(_)))));
+/// assert_eq!(simulator.field("count"), Some(&one));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Simulator<'a> {
+    module: &'a Module,
+    /// The library, the contract parameters and `_this_address`.
+    scope: Env<'a>,
+    state: HashMap<String, Value>,
+    /// The fields of other contracts, by address and name.
+    remote: HashMap<(Vec<u8>, String), Value>,
+    address: [u8; 20],
+}
+
+impl<'a> Simulator<'a> {
+    /// Deploy `contract` with the contract parameters `params` at block 0: check the contract
+    /// constraint and evaluate the field initializers. The balance of the contract is zero.
+    /// Fails if a parameter is missing, the constraint does not hold or an initializer fails.
+    pub fn new(contract: &'a Contract, params: &[(&str, Value)]) -> Result<Self, Error> {
+        let module = contract.syntax_tree()?;
+        let mut interpreter = Interpreter::new(contract)?
+            .with_var("_this_address", Value::ByStrX(DEFAULT_ADDRESS.to_vec()))
+            .with_var("_creation_block", Value::BNum(0u8.into()))
+            .with_var("_scilla_version", Value::Int(Type::Uint32, 0.into()));
+        for param in &module.contract.params {
+            let (_, value) = params
+                .iter()
+                .find(|(name, _)| *name == param.name)
+                .ok_or_else(|| {
+                    Error::InvalidValue(format!("missing contract parameter `{}`", param.name))
+                })?;
+            interpreter = interpreter.with_var(&param.name, value.clone());
+        }
+        let scope = interpreter.into_env();
+        if let Some(constraint) = &module.contract.constraint {
+            if into_value(eval(constraint, &scope)?)? != Value::bool(true) {
+                return Err(runtime("the contract constraint does not hold".to_string()));
+            }
+        }
+        let mut state = HashMap::from([("_balance".to_string(), uint128(0))]);
+        for field in &module.contract.fields {
+            state.insert(field.name.clone(), into_value(eval(&field.init, &scope)?)?);
+        }
+        Ok(Self {
+            module,
+            scope,
+            state,
+            remote: HashMap::new(),
+            address: DEFAULT_ADDRESS,
+        })
+    }
+
+    /// Simulate the contract at `address`, the value of `_this_address` in the transitions
+    /// executed next. The field initializers already evaluated are not affected.
+    pub fn with_address(mut self, address: [u8; 20]) -> Self {
+        self.address = address;
+        self.scope
+            .insert("_this_address", Val::Value(Value::ByStrX(address.to_vec())));
+        self
+    }
+
+    /// Set the field `name`, including `_balance`.
+    pub fn with_field(mut self, name: &str, value: Value) -> Self {
+        self.state.insert(name.to_string(), value);
+        self
+    }
+
+    /// Set the fields in `state`, a contract state as returned by the Zilliqa API. Entries that
+    /// are not fields of the contract are ignored.
+    pub fn with_state(mut self, state: &JsonValue) -> Result<Self, Error> {
+        let object = state
+            .as_object()
+            .ok_or_else(|| Error::InvalidValue(format!("{state} is not a contract state")))?;
+        for (name, json) in object {
+            if let Some(ty) = field_type(self.module, name) {
+                let value = Value::from_json(json, &ty, self.module.library.as_ref())?;
+                self.state.insert(name.clone(), value);
+            }
+        }
+        Ok(self)
+    }
+
+    /// Set the field `name` of the contract at `address`, read by remote reads and address type
+    /// casts. A contract is only known at the addresses with fields set.
+    pub fn with_remote_field(mut self, address: [u8; 20], name: &str, value: Value) -> Self {
+        self.remote
+            .insert((address.to_vec(), name.to_string()), value);
+        self
+    }
+
+    pub fn address(&self) -> [u8; 20] {
+        self.address
+    }
+
+    /// The current value of the field `name`, including `_balance`.
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        self.state.get(name)
+    }
+
+    /// The current values of the fields, `_balance` first and then in the order they are
+    /// declared.
+    pub fn state(&self) -> Vec<(String, Value)> {
+        std::iter::once("_balance")
+            .chain(self.module.contract.fields.iter().map(|f| f.name.as_str()))
+            .filter_map(|name| Some((name.to_string(), self.state.get(name)?.clone())))
+            .collect()
+    }
+
+    /// Execute `transition` with `params` in `context`, committing its changes to the state.
+    /// Fails with [`Error::Exception`] if the transition throws and with a runtime error if it
+    /// fails otherwise, e.g. on an integer overflow or when sending more ZIL than the balance;
+    /// the state is unchanged in both cases.
+    pub fn call(
+        &mut self,
+        transition: &str,
+        params: &[(&str, Value)],
+        context: &CallContext,
+    ) -> Result<Execution, Error> {
+        let component = self
+            .module
+            .contract
+            .transitions()
+            .find(|t| t.name == transition)
+            .ok_or_else(|| Error::InvalidValue(format!("unknown transition `{transition}`")))?;
+        let mut args = vec![];
+        for param in &component.params {
+            let (_, value) = params
+                .iter()
+                .find(|(name, _)| *name == param.name)
+                .ok_or_else(|| {
+                    Error::InvalidValue(format!(
+                        "missing parameter `{}` of `{transition}`",
+                        param.name
+                    ))
+                })?;
+            args.push(Val::Value(value.clone()));
+        }
+        let mut run = Run::new(self, context);
+        run.component(component, args)?;
+        let Run {
+            state,
+            events,
+            messages,
+            accepted,
+            ..
+        } = run;
+        self.state = state;
+        Ok(Execution {
+            state: self.state(),
+            events,
+            messages,
+            accepted: if accepted { context.amount } else { 0 },
+        })
+    }
+}
+
+fn uint128(n: impl Into<BigInt>) -> Value {
+    Value::Int(Type::Uint128, n.into())
+}
+
+fn address_arg(name: &str, scope: &Env) -> Result<Vec<u8>, Error> {
+    match scope.value(name)? {
+        Value::ByStrX(bytes) if bytes.len() == 20 => Ok(bytes),
+        value => Err(runtime(format!("{value} is not an address"))),
+    }
+}
+
+/// The value at `keys` in `map`, and its type.
+fn map_get(map: &Value, keys: &[Value]) -> Result<(Option<Value>, Type), Error> {
+    let (
+        Value::Map {
+            value_type,
+            entries,
+            ..
+        },
+        Some((key, rest)),
+    ) = (map, keys.split_first())
+    else {
+        return Err(runtime(format!("{map} is not a map with a key")));
+    };
+    let found = entries.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+    match (found, rest.is_empty()) {
+        (found, true) => Ok((found.cloned(), value_type.clone())),
+        (Some(nested), false) => map_get(nested, rest),
+        (None, false) => {
+            let ty = rest.iter().try_fold(value_type.clone(), |ty, _| match ty {
+                Type::Map(_, value_type) => Ok(*value_type),
+                ty => Err(runtime(format!("{ty} is not a map type"))),
+            })?;
+            Ok((None, ty))
+        }
+    }
+}
+
+/// Set the value at `keys` in `map` to `value`, adding the nested maps that are missing.
+fn map_update(map: &mut Value, keys: &[Value], value: Value) -> Result<(), Error> {
+    let (
+        Value::Map {
+            value_type,
+            entries,
+            ..
+        },
+        Some((key, rest)),
+    ) = (&mut *map, keys.split_first())
+    else {
+        return Err(runtime(format!("{map} is not a map with a key")));
+    };
+    let index = match entries.iter().position(|(k, _)| k == key) {
+        Some(index) => index,
+        None if rest.is_empty() => {
+            entries.push((key.clone(), value));
+            return Ok(());
+        }
+        None => {
+            let Type::Map(key_type, nested_type) = value_type else {
+                return Err(runtime(format!("{value_type} is not a map type")));
+            };
+            let empty = Value::Map {
+                key_type: (**key_type).clone(),
+                value_type: (**nested_type).clone(),
+                entries: vec![],
+            };
+            entries.push((key.clone(), empty));
+            entries.len() - 1
+        }
+    };
+    if rest.is_empty() {
+        entries[index].1 = value;
+        Ok(())
+    } else {
+        map_update(&mut entries[index].1, rest, value)
+    }
+}
+
+/// Remove the value at `keys` in `map`, if any.
+fn map_delete(map: &mut Value, keys: &[Value]) -> Result<(), Error> {
+    let (Value::Map { entries, .. }, Some((key, rest))) = (&mut *map, keys.split_first()) else {
+        return Err(runtime(format!("{map} is not a map with a key")));
+    };
+    match entries.iter().position(|(k, _)| k == key) {
+        Some(index) if rest.is_empty() => {
+            entries.remove(index);
+            Ok(())
+        }
+        Some(index) => map_delete(&mut entries[index].1, rest),
+        None => Ok(()),
+    }
+}
+
+/// A transition call in progress, with its own copy of the state.
+struct Run<'s, 'a> {
+    simulator: &'s Simulator<'a>,
+    context: &'s CallContext,
+    /// The scope of the simulator with the implicit parameters of the call.
+    scope: Env<'a>,
+    state: HashMap<String, Value>,
+    events: Vec<Value>,
+    messages: Vec<Value>,
+    accepted: bool,
+}
+
+impl<'s, 'a> Run<'s, 'a> {
+    fn new(simulator: &'s Simulator<'a>, context: &'s CallContext) -> Self {
+        let mut scope = simulator.scope.clone();
+        scope.insert(
+            "_sender",
+            Val::Value(Value::ByStrX(context.sender.to_vec())),
+        );
+        scope.insert(
+            "_origin",
+            Val::Value(Value::ByStrX(context.origin.to_vec())),
+        );
+        scope.insert("_amount", Val::Value(uint128(context.amount)));
+        Self {
+            simulator,
+            context,
+            scope,
+            state: simulator.state.clone(),
+            events: vec![],
+            messages: vec![],
+            accepted: false,
+        }
+    }
+
+    fn component(&mut self, component: &'a Component, args: Vec<Val<'a>>) -> Result<(), Error> {
+        if component.params.len() != args.len() {
+            return Err(runtime(format!(
+                "`{}` expects {} arguments, found {}",
+                component.name,
+                component.params.len(),
+                args.len()
+            )));
+        }
+        let mut scope = self.scope.clone();
+        for (param, arg) in component.params.iter().zip(args) {
+            scope.insert(&param.name, arg);
+        }
+        self.stmts(&component.body, &mut scope)
+    }
+
+    fn procedure(&mut self, name: &str, args: Vec<Val<'a>>) -> Result<(), Error> {
+        let module = self.simulator.module;
+        let procedure = module
+            .contract
+            .procedures()
+            .find(|p| p.name == name)
+            .ok_or_else(|| runtime(format!("unknown procedure `{name}`")))?;
+        self.component(procedure, args)
+    }
+
+    fn stmts(&mut self, stmts: &'a [Stmt], scope: &mut Env<'a>) -> Result<(), Error> {
+        stmts.iter().try_for_each(|stmt| self.stmt(stmt, scope))
+    }
+
+    fn field(&self, name: &str) -> Result<&Value, Error> {
+        self.state
+            .get(name)
+            .ok_or_else(|| runtime(format!("unknown field `{name}`")))
+    }
+
+    fn field_mut(&mut self, name: &str) -> Result<&mut Value, Error> {
+        self.state
+            .get_mut(name)
+            .ok_or_else(|| runtime(format!("unknown field `{name}`")))
+    }
+
+    /// The field `name` of the contract at `address`.
+    fn remote_field(&self, address: &[u8], name: &str) -> Result<Value, Error> {
+        let value = if address == self.simulator.address {
+            self.state.get(name)
+        } else {
+            self.simulator
+                .remote
+                .get(&(address.to_vec(), name.to_string()))
+        };
+        value.cloned().ok_or_else(|| {
+            runtime(format!(
+                "the field `{name}` of {} is not known",
+                Value::ByStrX(address.to_vec())
+            ))
+        })
+    }
+
+    /// Whether the address `address` has the type `ty`.
+    fn has_type(&self, address: &[u8], ty: &Type) -> bool {
+        let Type::Address(Some(fields)) = ty else {
+            return true;
+        };
+        let is_contract = address == self.simulator.address
+            || self.simulator.remote.keys().any(|(a, _)| a == address);
+        is_contract
+            && fields
+                .iter()
+                .all(|field| self.remote_field(address, &field.name).is_ok())
+    }
+
+    fn balance(&self) -> BigInt {
+        match self.state.get("_balance") {
+            Some(Value::Int(_, n)) => n.clone(),
+            _ => BigInt::default(),
+        }
+    }
+
+    fn stmt(&mut self, stmt: &'a Stmt, scope: &mut Env<'a>) -> Result<(), Error> {
+        let values = |names: &[String], scope: &Env<'a>| {
+            names
+                .iter()
+                .map(|name| scope.value(name))
+                .collect::<Result<Vec<_>, Error>>()
+        };
+        match &stmt.kind {
+            StmtKind::Load { lhs, field } => {
+                let value = self.field(field)?.clone();
+                scope.insert(lhs, Val::Value(value));
+            }
+            StmtKind::Store { field, rhs } => {
+                let value = scope.value(rhs)?;
+                *self.field_mut(field)? = value;
+            }
+            StmtKind::Bind { lhs, expr } => {
+                let value = eval(expr, scope)?;
+                scope.insert(lhs, value);
+            }
+            StmtKind::MapUpdate { map, keys, value } => {
+                let (keys, value) = (values(keys, scope)?, scope.value(value)?);
+                map_update(self.field_mut(map)?, &keys, value)?;
+            }
+            StmtKind::MapDelete { map, keys } => {
+                let keys = values(keys, scope)?;
+                map_delete(self.field_mut(map)?, &keys)?;
+            }
+            StmtKind::MapGet {
+                lhs,
+                map,
+                keys,
+                exists,
+            } => {
+                let keys = values(keys, scope)?;
+                let (found, ty) = map_get(self.field(map)?, &keys)?;
+                let value = if *exists {
+                    Value::bool(found.is_some())
+                } else {
+                    Value::option(ty, found)
+                };
+                scope.insert(lhs, Val::Value(value));
+            }
+            StmtKind::RemoteLoad {
+                lhs,
+                address,
+                field,
+            } => {
+                let value = self.remote_field(&address_arg(address, scope)?, field)?;
+                scope.insert(lhs, Val::Value(value));
+            }
+            StmtKind::RemoteMapGet {
+                lhs,
+                address,
+                map,
+                keys,
+                exists,
+            } => {
+                let map = self.remote_field(&address_arg(address, scope)?, map)?;
+                let (found, ty) = map_get(&map, &values(keys, scope)?)?;
+                let value = if *exists {
+                    Value::bool(found.is_some())
+                } else {
+                    Value::option(ty, found)
+                };
+                scope.insert(lhs, Val::Value(value));
+            }
+            StmtKind::TypeCast { lhs, address, ty } => {
+                let bytes = address_arg(address, scope)?;
+                let value = self
+                    .has_type(&bytes, ty)
+                    .then(|| Value::ByStrX(bytes.clone()));
+                scope.insert(lhs, Val::Value(Value::option(ty.clone(), value)));
+            }
+            StmtKind::ReadFromBC { lhs, query, args } => {
+                let value = match (query.as_str(), &values(args, scope)?[..]) {
+                    ("BLOCKNUMBER", []) => Value::BNum(self.context.block_number.into()),
+                    ("CHAINID", []) => Value::Int(Type::Uint32, self.context.chain_id.into()),
+                    ("TIMESTAMP", [Value::BNum(block)]) => {
+                        let current = self.context.block_number.into();
+                        if *block > current {
+                            Value::option(Type::Uint64, None)
+                        } else if *block == current {
+                            let timestamp = Value::Int(Type::Uint64, self.context.timestamp.into());
+                            Value::option(Type::Uint64, Some(timestamp))
+                        } else {
+                            return Err(runtime(format!(
+                                "the timestamp of block {block} is not known"
+                            )));
+                        }
+                    }
+                    _ => return Err(runtime(format!("unsupported blockchain query `{query}`"))),
+                };
+                scope.insert(lhs, Val::Value(value));
+            }
+            StmtKind::Accept => {
+                if !self.accepted {
+                    self.accepted = true;
+                    let balance = self.balance() + self.context.amount;
+                    self.state.insert("_balance".to_string(), uint128(balance));
+                }
+            }
+            StmtKind::Send(name) => {
+                let msgs = scope.value(name)?;
+                let msgs = msgs
+                    .as_list()
+                    .ok_or_else(|| runtime(format!("{msgs} is not a list of messages")))?;
+                for msg in msgs {
+                    let Value::Message(entries) = msg else {
+                        return Err(runtime(format!("{msg} is not a message")));
+                    };
+                    let amount = match entries.iter().find(|(name, _)| name == "_amount") {
+                        Some((_, Value::Int(_, amount))) => amount.clone(),
+                        _ => BigInt::default(),
+                    };
+                    let balance = self.balance();
+                    if amount > balance {
+                        return Err(runtime(format!(
+                            "cannot send {amount} with a balance of {balance}"
+                        )));
+                    }
+                    self.state
+                        .insert("_balance".to_string(), uint128(balance - amount));
+                    self.messages.push(msg.clone());
+                }
+            }
+            StmtKind::Event(name) => self.events.push(scope.value(name)?),
+            StmtKind::Throw(exception) => {
+                let exception = exception.as_ref().map(|e| scope.value(e)).transpose()?;
+                return Err(Error::Exception(exception));
+            }
+            StmtKind::Match { scrutinee, arms } => {
+                let value = scope.lookup(scrutinee)?;
+                for (pattern, body) in arms {
+                    let mut bindings = vec![];
+                    if matches(pattern, &value, &mut bindings) {
+                        let mut scope = scope.clone();
+                        for (name, value) in bindings {
+                            scope.insert(&name, value);
+                        }
+                        return self.stmts(body, &mut scope);
+                    }
+                }
+                return Err(no_arm(scrutinee, &value));
+            }
+            StmtKind::CallProc { name, args } => {
+                let args = args
+                    .iter()
+                    .map(|arg| scope.lookup(arg))
+                    .collect::<Result<_, Error>>()?;
+                self.procedure(name, args)?;
+            }
+            StmtKind::Iterate { list, proc } => {
+                let list = scope.value(list)?;
+                let items = list
+                    .as_list()
+                    .ok_or_else(|| runtime(format!("{list} is not a list")))?;
+                for item in items {
+                    self.procedure(proc, vec![Val::Value(item.clone())])?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
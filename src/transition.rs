@@ -1,6 +1,6 @@
 use lexpr::Value;
 
-use crate::{Error, FieldList};
+use crate::{ast::Component, Error, FieldList};
 
 #[derive(Debug, PartialEq)]
 pub struct Transition {
@@ -24,6 +24,12 @@ impl Transition {
     }
 }
 
+impl From<&Component> for Transition {
+    fn from(component: &Component) -> Self {
+        Transition::new(&component.name, component.params.iter().collect())
+    }
+}
+
 impl TryFrom<&Value> for Transition {
     type Error = Error;
 
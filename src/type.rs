@@ -1,9 +1,9 @@
 use std::{fmt::Display, str::FromStr};
 
-use crate::Error;
+use crate::{Error, FieldList};
 
 /// Represents all different scilla types.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     Int32,
     Int64,
@@ -28,6 +28,16 @@ pub enum Type {
     Pair(Box<Type>, Box<Type>),
     List(Box<Type>),
 
+    /// An address type, `ByStr20 with end` when the fields are `None`, otherwise
+    /// `ByStr20 with contract field f1 : T1, field f2 : T2 end`.
+    Address(Option<FieldList>),
+    /// A function type, `T1 -> T2`.
+    Function(Box<Type>, Box<Type>),
+    /// A type variable, `'A`.
+    TypeVar(String),
+    /// A polymorphic type, `forall 'A. T`.
+    PolyFun(String, Box<Type>),
+
     Other(String),
 }
 
@@ -74,6 +84,43 @@ impl FromStr for Type {
     }
 }
 
+impl Type {
+    /// The type as printed by the Scilla tools, e.g. `Map (ByStr20) (Option (Uint128))`.
+    pub fn to_scilla_string(&self) -> String {
+        let adt = |name: &str, args: &[&Type]| {
+            std::iter::once(name.to_string())
+                .chain(args.iter().map(|t| format!("({})", t.to_scilla_string())))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        match self {
+            Type::Map(k, v) => format!("Map ({}) ({})", k.to_scilla_string(), v.to_scilla_string()),
+            Type::Option(t) => adt("Option", &[t]),
+            Type::List(t) => adt("List", &[t]),
+            Type::Pair(a, b) => adt("Pair", &[a, b]),
+            Type::Address(Some(fields)) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|f| format!("field {} : {}", f.name, f.r#type.to_scilla_string()))
+                    .collect();
+                if fields.is_empty() {
+                    "ByStr20 with contract end".to_string()
+                } else {
+                    format!("ByStr20 with contract {} end", fields.join(", "))
+                }
+            }
+            Type::Function(a, b) => match **a {
+                Type::Function(..) | Type::PolyFun(..) => {
+                    format!("({}) -> {}", a.to_scilla_string(), b.to_scilla_string())
+                }
+                _ => format!("{} -> {}", a.to_scilla_string(), b.to_scilla_string()),
+            },
+            Type::PolyFun(tvar, t) => format!("forall {}. {}", tvar, t.to_scilla_string()),
+            _ => self.to_string(),
+        }
+    }
+}
+
 impl Display for Type {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -93,6 +140,20 @@ impl Display for Type {
             Type::List(ref k) => write!(f, "(List {})", k),
             Type::Pair(ref k, ref v) => write!(f, "(Pair {} {})", k, v),
             Type::ByStr(n) => write!(f, "ByStr{}", n),
+            Type::Address(None) => write!(f, "ByStr20 with end"),
+            Type::Address(Some(ref fields)) => {
+                write!(f, "ByStr20 with contract ")?;
+                for (i, field) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "field {} : {}", field.name, field.r#type)?;
+                }
+                write!(f, "{}end", if fields.is_empty() { "" } else { " " })
+            }
+            Type::Function(ref a, ref b) => write!(f, "({} -> {})", a, b),
+            Type::TypeVar(ref s) => write!(f, "{}", s),
+            Type::PolyFun(ref v, ref t) => write!(f, "(forall {}. {})", v, t),
             Type::Other(ref s) => write!(f, "{}", s),
         }
     }
@@ -157,6 +218,29 @@ mod tests {
         assert_eq!(option_bool_type, Type::Option(Box::new(Type::Bool)));
     }
 
+    #[test]
+    fn test_type_to_scilla_string() {
+        let ty = Type::Map(
+            Box::new(Type::ByStr(20)),
+            Box::new(Type::Option(Box::new(Type::Pair(
+                Box::new(Type::String),
+                Box::new(Type::Uint32),
+            )))),
+        );
+        assert_eq!(
+            ty.to_scilla_string(),
+            "Map (ByStr20) (Option (Pair (String) (Uint32)))"
+        );
+        let ty = Type::Function(
+            Box::new(Type::Function(
+                Box::new(Type::TypeVar("'A".to_string())),
+                Box::new(Type::Bool),
+            )),
+            Box::new(Type::List(Box::new(Type::TypeVar("'A".to_string())))),
+        );
+        assert_eq!(ty.to_scilla_string(), "('A -> Bool) -> List ('A)");
+    }
+
     #[test]
     fn test_type_to_string() {
         //(List (Pair ByStr20 (List (Pair ByStr20 Uint32))))
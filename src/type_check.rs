@@ -0,0 +1,1268 @@
+//! Type checking of Scilla modules: inference of the types of expressions by unification,
+//! typing of statements against the fields and procedures they use, exhaustiveness of matches
+//! and the serializability of parameters and fields.
+
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    ast::{
+        ComponentKind, CtrDef, Expr, ExprKind, LibEntry, Literal, MessageEntry, Module, Pattern,
+        Span, Stmt, StmtKind,
+    },
+    exhaustiveness::{check_match, Constructors, MatchIssue, MatchIssueList},
+    infer::{is_modelled, stdlib},
+    Contract, Error, FieldList, Type,
+};
+
+/// A type error with the location of the offending declaration, statement or expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl Display for TypeError {
+    /// `12:5: value `one` stored in `count` has type Uint128 but Uint32 was expected`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}",
+            self.span.line, self.span.column, self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TypeErrorList(pub Vec<TypeError>);
+
+impl std::ops::Deref for TypeErrorList {
+    type Target = Vec<TypeError>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A type during inference.
+#[derive(Debug, Clone, PartialEq)]
+enum Ty {
+    /// A type still to be inferred.
+    Var(usize),
+    /// A type variable bound by `tfun` or `forall`, e.g. `'A`.
+    Param(String),
+    /// A primitive type or an ADT applied to its arguments, e.g. `Uint128` or `Option Uint128`.
+    Con(String, Vec<Ty>),
+    /// An address type, which can be used as a `ByStr20`.
+    Address(Option<FieldList>),
+    Fun(Box<Ty>, Box<Ty>),
+    Forall(String, Box<Ty>),
+}
+
+fn con(name: &str) -> Ty {
+    Ty::Con(name.to_string(), vec![])
+}
+
+fn app(name: &str, args: Vec<Ty>) -> Ty {
+    Ty::Con(name.to_string(), args)
+}
+
+impl Ty {
+    /// Replace the type variable `tvar` with `replacement`.
+    fn substitute(&self, tvar: &str, replacement: &Ty) -> Ty {
+        let sub = |t: &Ty| Box::new(t.substitute(tvar, replacement));
+        match self {
+            Ty::Param(v) if v == tvar => replacement.clone(),
+            Ty::Con(name, args) => Ty::Con(
+                name.clone(),
+                args.iter()
+                    .map(|t| t.substitute(tvar, replacement))
+                    .collect(),
+            ),
+            Ty::Fun(a, b) => Ty::Fun(sub(a), sub(b)),
+            Ty::Forall(v, t) if v != tvar => Ty::Forall(v.clone(), sub(t)),
+            _ => self.clone(),
+        }
+    }
+
+    /// The type in parentheses if it is made of several words.
+    fn atom(&self) -> String {
+        match self {
+            Ty::Con(_, args) if args.is_empty() => self.to_string(),
+            Ty::Con(..) | Ty::Address(_) | Ty::Fun(..) | Ty::Forall(..) => format!("({self})"),
+            Ty::Var(_) | Ty::Param(_) => self.to_string(),
+        }
+    }
+}
+
+impl Display for Ty {
+    /// The type in Scilla syntax, with `_` for the parts that are not inferred yet.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ty::Var(_) => write!(f, "_"),
+            Ty::Param(tvar) => write!(f, "{tvar}"),
+            Ty::Con(name, args) => {
+                write!(f, "{name}")?;
+                args.iter().try_for_each(|arg| write!(f, " {}", arg.atom()))
+            }
+            Ty::Address(None) => write!(f, "ByStr20 with end"),
+            Ty::Address(fields) => {
+                write!(f, "{}", Type::Address(fields.clone()).to_scilla_string())
+            }
+            Ty::Fun(a, b) => match **a {
+                Ty::Fun(..) | Ty::Forall(..) => write!(f, "({a}) -> {b}"),
+                _ => write!(f, "{a} -> {b}"),
+            },
+            Ty::Forall(tvar, t) => write!(f, "forall {tvar}. {t}"),
+        }
+    }
+}
+
+/// Builtin types that are not represented by a variant of [`Type`].
+const NAMED_TYPES: &[&str] = &["ByStr", "Nat", "Message", "Event", "Exception"];
+
+/// The instantiated type of a constructor.
+struct Signature {
+    /// The type arguments, e.g. `'A` of `Some {'A} a`.
+    params: Vec<Ty>,
+    args: Vec<Ty>,
+    result: Ty,
+}
+
+type Env = HashMap<String, Ty>;
+
+struct Checker<'a> {
+    module: &'a Module,
+    /// Bindings of the types still to be inferred.
+    subst: Vec<Option<Ty>>,
+    /// Types of the library values and the standard library functions.
+    library: Env,
+    /// User-defined ADTs and their constructors.
+    adts: HashMap<&'a str, &'a [CtrDef]>,
+    fields: Env,
+    errors: Vec<TypeError>,
+    matches: Vec<MatchIssue>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(module: &'a Module) -> Self {
+        let adts = module
+            .library
+            .iter()
+            .flat_map(|lib| &lib.entries)
+            .filter_map(|entry| match entry {
+                LibEntry::Type {
+                    name, constructors, ..
+                } => Some((name.as_str(), constructors.as_slice())),
+                LibEntry::Let { .. } => None,
+            })
+            .collect();
+        let mut checker = Self {
+            module,
+            subst: vec![],
+            library: Env::new(),
+            adts,
+            fields: Env::new(),
+            errors: vec![],
+            matches: vec![],
+        };
+        checker.library = stdlib(&module.imports)
+            .iter()
+            .filter_map(|(name, ty)| Some((name.clone(), checker.convert(ty).ok()?)))
+            .collect();
+        checker
+    }
+
+    fn error(&mut self, span: Span, message: String) {
+        self.errors.push(TypeError { message, span });
+    }
+
+    fn fresh(&mut self) -> Ty {
+        self.subst.push(None);
+        Ty::Var(self.subst.len() - 1)
+    }
+
+    /// Follow the bindings of `ty` until it is not a bound variable.
+    fn head(&self, ty: &Ty) -> Ty {
+        match ty {
+            Ty::Var(v) => match &self.subst[*v] {
+                Some(bound) => self.head(bound),
+                None => ty.clone(),
+            },
+            _ => ty.clone(),
+        }
+    }
+
+    /// Replace all bound variables of `ty` with their types.
+    fn resolve(&self, ty: &Ty) -> Ty {
+        match self.head(ty) {
+            Ty::Con(name, args) => Ty::Con(name, args.iter().map(|t| self.resolve(t)).collect()),
+            Ty::Fun(a, b) => Ty::Fun(Box::new(self.resolve(&a)), Box::new(self.resolve(&b))),
+            Ty::Forall(tvar, t) => Ty::Forall(tvar, Box::new(self.resolve(&t))),
+            ty => ty,
+        }
+    }
+
+    fn occurs(&self, var: usize, ty: &Ty) -> bool {
+        match self.head(ty) {
+            Ty::Var(v) => v == var,
+            Ty::Con(_, args) => args.iter().any(|t| self.occurs(var, t)),
+            Ty::Fun(a, b) => self.occurs(var, &a) || self.occurs(var, &b),
+            Ty::Forall(_, t) => self.occurs(var, &t),
+            Ty::Param(_) | Ty::Address(_) => false,
+        }
+    }
+
+    fn unify(&mut self, a: &Ty, b: &Ty) -> bool {
+        match (self.head(a), self.head(b)) {
+            (Ty::Var(x), Ty::Var(y)) if x == y => true,
+            (Ty::Var(v), ty) | (ty, Ty::Var(v)) => {
+                if self.occurs(v, &ty) {
+                    return false;
+                }
+                self.subst[v] = Some(ty);
+                true
+            }
+            (Ty::Param(x), Ty::Param(y)) => x == y,
+            (Ty::Con(x, xs), Ty::Con(y, ys)) => {
+                x == y && xs.len() == ys.len() && xs.iter().zip(&ys).all(|(x, y)| self.unify(x, y))
+            }
+            (Ty::Address(_), Ty::Address(_)) => true,
+            (Ty::Address(_), Ty::Con(name, args)) | (Ty::Con(name, args), Ty::Address(_)) => {
+                name == "ByStr20" && args.is_empty()
+            }
+            (Ty::Fun(a1, r1), Ty::Fun(a2, r2)) => self.unify(&a1, &a2) && self.unify(&r1, &r2),
+            (Ty::Forall(x, t), Ty::Forall(y, u)) => {
+                let u = self.resolve(&u).substitute(&y, &Ty::Param(x));
+                self.unify(&t, &u)
+            }
+            _ => false,
+        }
+    }
+
+    /// Unify `found` with `expected`, reporting `what` has the wrong type if they differ.
+    fn expect(&mut self, expected: &Ty, found: &Ty, span: Span, what: impl FnOnce() -> String) {
+        if !self.unify(expected, found) {
+            let message = format!(
+                "{} has type {} but {} was expected",
+                what(),
+                self.resolve(found),
+                self.resolve(expected)
+            );
+            self.error(span, message);
+        }
+    }
+
+    /// Convert a type written in the source, failing with the name of the first unknown type.
+    fn convert(&self, ty: &Type) -> Result<Ty, String> {
+        let boxed = |t: &Type| self.convert(t).map(Box::new);
+        Ok(match ty {
+            Type::Int32
+            | Type::Int64
+            | Type::Int128
+            | Type::Int256
+            | Type::Uint32
+            | Type::Uint64
+            | Type::Uint128
+            | Type::Uint256
+            | Type::String
+            | Type::BNum
+            | Type::Bool => con(&ty.to_string()),
+            Type::ByStr(n) => con(&format!("ByStr{n}")),
+            Type::Map(k, v) => app("Map", vec![self.convert(k)?, self.convert(v)?]),
+            Type::Option(t) => app("Option", vec![self.convert(t)?]),
+            Type::List(t) => app("List", vec![self.convert(t)?]),
+            Type::Pair(a, b) => app("Pair", vec![self.convert(a)?, self.convert(b)?]),
+            Type::Address(fields) => Ty::Address(fields.clone()),
+            Type::Function(a, b) => Ty::Fun(boxed(a)?, boxed(b)?),
+            Type::TypeVar(tvar) => Ty::Param(tvar.clone()),
+            Type::PolyFun(tvar, t) => Ty::Forall(tvar.clone(), boxed(t)?),
+            Type::Other(name)
+                if NAMED_TYPES.contains(&name.as_str())
+                    || self.adts.contains_key(name.as_str()) =>
+            {
+                con(name)
+            }
+            Type::Other(name) => return Err(name.clone()),
+        })
+    }
+
+    /// Convert a type annotation, reporting unknown types.
+    fn annotation(&mut self, ty: &Type, span: Span) -> Ty {
+        match self.convert(ty) {
+            Ok(ty) => ty,
+            Err(name) => {
+                self.error(span, format!("unknown type `{name}`"));
+                self.fresh()
+            }
+        }
+    }
+
+    fn var(&mut self, name: &str, env: &Env, span: Span) -> Ty {
+        if let Some(ty) = env.get(name).or_else(|| self.library.get(name)) {
+            return ty.clone();
+        }
+        // The functions of the libraries that are not modelled are not known.
+        if self.module.imports.iter().all(is_modelled) {
+            self.error(span, format!("undefined variable `{name}`"));
+        }
+        self.fresh()
+    }
+
+    fn field(&mut self, name: &str, span: Span) -> Ty {
+        match self.fields.get(name) {
+            Some(ty) => ty.clone(),
+            None => {
+                self.error(span, format!("undefined field `{name}`"));
+                self.fresh()
+            }
+        }
+    }
+
+    fn constructor(&mut self, name: &str) -> Option<Signature> {
+        let (params, args, result) = match name {
+            "True" | "False" => (vec![], vec![], con("Bool")),
+            "Zero" => (vec![], vec![], con("Nat")),
+            "Succ" => (vec![], vec![con("Nat")], con("Nat")),
+            "Some" | "None" | "Nil" | "Cons" => {
+                let a = self.fresh();
+                let (adt, args) = match name {
+                    "Some" => ("Option", vec![a.clone()]),
+                    "None" => ("Option", vec![]),
+                    "Nil" => ("List", vec![]),
+                    _ => ("List", vec![a.clone(), app("List", vec![a.clone()])]),
+                };
+                (vec![a.clone()], args, app(adt, vec![a]))
+            }
+            "Pair" => {
+                let (a, b) = (self.fresh(), self.fresh());
+                (
+                    vec![a.clone(), b.clone()],
+                    vec![a.clone(), b.clone()],
+                    app("Pair", vec![a, b]),
+                )
+            }
+            _ => {
+                let (adt, ctr) = self.adts.iter().find_map(|(adt, ctrs)| {
+                    ctrs.iter().find(|c| c.name == name).map(|c| (*adt, c))
+                })?;
+                let args = ctr
+                    .args
+                    .iter()
+                    .map(|t| self.convert(t))
+                    .collect::<Result<_, _>>();
+                (vec![], args.ok()?, con(adt))
+            }
+        };
+        Some(Signature {
+            params,
+            args,
+            result,
+        })
+    }
+
+    fn check_constructor(
+        &mut self,
+        name: &str,
+        type_args: &[Type],
+        args: &[String],
+        env: &Env,
+        span: Span,
+    ) -> Ty {
+        let Some(signature) = self.constructor(name) else {
+            self.error(span, format!("undefined constructor `{name}`"));
+            return self.fresh();
+        };
+        if !type_args.is_empty() {
+            if type_args.len() != signature.params.len() {
+                self.error(
+                    span,
+                    format!(
+                        "constructor `{name}` expects {} type arguments, got {}",
+                        signature.params.len(),
+                        type_args.len()
+                    ),
+                );
+            }
+            for (param, arg) in signature.params.iter().zip(type_args) {
+                let arg = self.annotation(arg, span);
+                self.unify(param, &arg);
+            }
+        }
+        if args.len() != signature.args.len() {
+            self.error(
+                span,
+                format!(
+                    "constructor `{name}` expects {} arguments, got {}",
+                    signature.args.len(),
+                    args.len()
+                ),
+            );
+        }
+        for (expected, arg) in signature.args.iter().zip(args) {
+            let found = self.var(arg, env, span);
+            self.expect(expected, &found, span, || {
+                format!("argument `{arg}` of `{name}`")
+            });
+        }
+        signature.result
+    }
+
+    /// Type `pattern` against a value of type `ty` and add its binders to `env`.
+    fn pattern(&mut self, pattern: &Pattern, ty: &Ty, env: &mut Env, span: Span) {
+        match pattern {
+            Pattern::Wildcard => {}
+            Pattern::Binder(name) => {
+                env.insert(name.clone(), ty.clone());
+            }
+            Pattern::Constructor(name, args) => {
+                let arg_types = match self.constructor(name) {
+                    Some(signature) => {
+                        if !self.unify(ty, &signature.result) {
+                            let message = format!(
+                                "pattern `{name}` cannot match a value of type {}",
+                                self.resolve(ty)
+                            );
+                            self.error(span, message);
+                        }
+                        if args.len() != signature.args.len() {
+                            self.error(
+                                span,
+                                format!(
+                                    "constructor `{name}` expects {} arguments, got {}",
+                                    signature.args.len(),
+                                    args.len()
+                                ),
+                            );
+                        }
+                        signature.args
+                    }
+                    None => {
+                        self.error(span, format!("undefined constructor `{name}`"));
+                        vec![]
+                    }
+                };
+                for (i, arg) in args.iter().enumerate() {
+                    let ty = arg_types.get(i).cloned().unwrap_or_else(|| self.fresh());
+                    self.pattern(arg, &ty, env, span);
+                }
+            }
+        }
+    }
+
+    fn check_match<'p>(
+        &mut self,
+        scrutinee: &str,
+        ty: &Ty,
+        patterns: impl Iterator<Item = &'p Pattern>,
+        span: Span,
+    ) {
+        let patterns: Vec<&Pattern> = patterns.collect();
+        for issue in check_match(self, scrutinee, ty, &patterns, span) {
+            if let MatchIssue::NonExhaustive { .. } = issue {
+                self.error(span, issue.to_string());
+            }
+            self.matches.push(issue);
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr, env: &Env) -> Ty {
+        let span = expr.span;
+        match &expr.kind {
+            ExprKind::Literal(literal) => match literal {
+                Literal::Int(ty, _) => self.annotation(ty, span),
+                Literal::String(_) => con("String"),
+                Literal::ByStr(hex) => con(&format!("ByStr{}", (hex.len() - 2) / 2)),
+                Literal::BNum(_) => con("BNum"),
+                Literal::EmptyMap(k, v) => {
+                    let k = self.annotation(k, span);
+                    let v = self.annotation(v, span);
+                    app("Map", vec![k, v])
+                }
+            },
+            ExprKind::Var(name) => self.var(name, env, span),
+            ExprKind::Message(entries) => {
+                for entry in entries {
+                    self.expr(&entry.value, env);
+                }
+                con(message_type(entries))
+            }
+            ExprKind::Let {
+                name,
+                ty,
+                value,
+                body,
+            } => {
+                let found = self.expr(value, env);
+                if let Some(ty) = ty {
+                    let expected = self.annotation(ty, span);
+                    self.expect(&expected, &found, value.span, || {
+                        format!("value of `{name}`")
+                    });
+                }
+                let mut env = env.clone();
+                env.insert(name.clone(), found);
+                self.expr(body, &env)
+            }
+            ExprKind::Fun { param, ty, body } => {
+                let ty = self.annotation(ty, span);
+                let mut env = env.clone();
+                env.insert(param.clone(), ty.clone());
+                Ty::Fun(Box::new(ty), Box::new(self.expr(body, &env)))
+            }
+            ExprKind::App { func, args } => {
+                let mut ty = self.var(func, env, span);
+                for arg in args {
+                    let found = self.var(arg, env, span);
+                    match self.head(&ty) {
+                        Ty::Fun(param, result) => {
+                            self.expect(&param, &found, span, || {
+                                format!("argument `{arg}` of `{func}`")
+                            });
+                            ty = *result;
+                        }
+                        Ty::Var(_) => {
+                            let result = self.fresh();
+                            self.unify(&ty, &Ty::Fun(Box::new(found), Box::new(result.clone())));
+                            ty = result;
+                        }
+                        Ty::Forall(..) => {
+                            self.error(
+                                span,
+                                format!("`{func}` is polymorphic and must be instantiated with `@{func}` before being applied"),
+                            );
+                            return self.fresh();
+                        }
+                        _ => {
+                            let func_ty = self.var(func, env, span);
+                            let message = format!(
+                                "`{func}` of type {} is applied to too many arguments",
+                                self.resolve(&func_ty)
+                            );
+                            self.error(span, message);
+                            return self.fresh();
+                        }
+                    }
+                }
+                ty
+            }
+            ExprKind::Constructor {
+                name,
+                type_args,
+                args,
+            } => self.check_constructor(name, type_args, args, env, span),
+            ExprKind::Builtin { name, args } => {
+                let args: Vec<Ty> = args.iter().map(|a| self.var(a, env, span)).collect();
+                self.builtin(name, &args, span)
+            }
+            ExprKind::Match { scrutinee, arms } => {
+                let ty = self.var(scrutinee, env, span);
+                let result = self.fresh();
+                for (pattern, arm) in arms {
+                    let mut env = env.clone();
+                    self.pattern(pattern, &ty, &mut env, arm.span);
+                    let found = self.expr(arm, &env);
+                    self.expect(&result, &found, arm.span, || "the arm".to_string());
+                }
+                self.check_match(scrutinee, &ty, arms.iter().map(|(p, _)| p), span);
+                result
+            }
+            ExprKind::TFun { tvar, body } => {
+                Ty::Forall(tvar.clone(), Box::new(self.expr(body, env)))
+            }
+            ExprKind::TApp { func, type_args } => {
+                let mut ty = self.var(func, env, span);
+                for arg in type_args {
+                    let arg = self.annotation(arg, span);
+                    match self.head(&ty) {
+                        Ty::Forall(tvar, body) => ty = self.resolve(&body).substitute(&tvar, &arg),
+                        Ty::Var(_) => return self.fresh(),
+                        other => {
+                            let message = format!(
+                                "`{func}` of type {} is not polymorphic",
+                                self.resolve(&other)
+                            );
+                            self.error(span, message);
+                            return self.fresh();
+                        }
+                    }
+                }
+                ty
+            }
+        }
+    }
+
+    /// The result type of a builtin applied to arguments of the types `args`. Unknown builtins
+    /// are not checked.
+    fn builtin(&mut self, name: &str, args: &[Ty], span: Span) -> Ty {
+        let arity = match name {
+            "isqrt"
+            | "strlen"
+            | "strrev"
+            | "to_string"
+            | "to_ascii"
+            | "sha256hash"
+            | "keccak256hash"
+            | "ripemd160hash"
+            | "to_bystr"
+            | "to_nat"
+            | "to_list"
+            | "size"
+            | "schnorr_get_address" => 1,
+            "eq" | "lt" | "add" | "sub" | "mul" | "div" | "rem" | "pow" | "blt" | "bsub"
+            | "badd" | "concat" | "get" | "contains" | "remove" | "bech32_to_bystr20"
+            | "bystr20_to_bech32" => 2,
+            "substr" | "put" | "schnorr_verify" | "ecdsa_verify" => 3,
+            _ if conversion_target(name).is_some() => 1,
+            _ => return self.fresh(),
+        };
+        if args.len() != arity {
+            self.error(
+                span,
+                format!(
+                    "builtin {name} expects {arity} arguments, got {}",
+                    args.len()
+                ),
+            );
+            return self.fresh();
+        }
+        let require = |checker: &mut Self, i: usize, ok: fn(&Ty) -> bool, what: &str| {
+            let ty = checker.resolve(&args[i]);
+            if !matches!(ty, Ty::Var(_)) && !ok(&ty) {
+                let message = format!("builtin {name} expects {what}, got {ty}");
+                checker.error(span, message);
+            }
+        };
+        let same = |checker: &mut Self| {
+            if !checker.unify(&args[0], &args[1]) {
+                let message = format!(
+                    "builtin {name} expects arguments of the same type, got {} and {}",
+                    checker.resolve(&args[0]),
+                    checker.resolve(&args[1])
+                );
+                checker.error(span, message);
+            }
+        };
+        match name {
+            "eq" => {
+                same(self);
+                require(self, 0, is_comparable, "comparable values");
+                con("Bool")
+            }
+            "lt" => {
+                same(self);
+                require(self, 0, is_int, "integers");
+                con("Bool")
+            }
+            "add" | "sub" | "mul" | "div" | "rem" => {
+                same(self);
+                require(self, 0, is_int, "integers");
+                args[0].clone()
+            }
+            "pow" => {
+                require(self, 0, is_int, "an integer");
+                self.expect(&con("Uint32"), &args[1], span, || {
+                    "the exponent of builtin pow".to_string()
+                });
+                args[0].clone()
+            }
+            "isqrt" => {
+                require(self, 0, is_uint, "an unsigned integer");
+                args[0].clone()
+            }
+            "blt" | "bsub" => {
+                for arg in args {
+                    self.expect(&con("BNum"), arg, span, || {
+                        format!("argument of builtin {name}")
+                    });
+                }
+                con(if name == "blt" { "Bool" } else { "Int256" })
+            }
+            "badd" => {
+                self.expect(&con("BNum"), &args[0], span, || {
+                    "block number of builtin badd".to_string()
+                });
+                require(self, 1, is_uint, "an unsigned integer");
+                con("BNum")
+            }
+            "concat" => match (
+                bystr_width(&self.head(&args[0])),
+                bystr_width(&self.head(&args[1])),
+            ) {
+                (Some(a), Some(b)) => con(&format!("ByStr{}", a + b)),
+                _ => {
+                    same(self);
+                    require(self, 0, |t| is(t, "String") || is(t, "ByStr"), "strings");
+                    args[0].clone()
+                }
+            },
+            "substr" => {
+                require(self, 0, |t| is(t, "String") || is(t, "ByStr"), "a string");
+                for arg in &args[1..] {
+                    self.expect(&con("Uint32"), arg, span, || {
+                        "position of builtin substr".to_string()
+                    });
+                }
+                args[0].clone()
+            }
+            "strlen" => con("Uint32"),
+            "strrev" | "to_string" | "to_ascii" => con("String"),
+            "sha256hash" | "keccak256hash" => con("ByStr32"),
+            "ripemd160hash" | "schnorr_get_address" => con("ByStr20"),
+            "to_bystr" => con("ByStr"),
+            "to_nat" => {
+                self.expect(&con("Uint32"), &args[0], span, || {
+                    "argument of builtin to_nat".to_string()
+                });
+                con("Nat")
+            }
+            "schnorr_verify" | "ecdsa_verify" => con("Bool"),
+            "bech32_to_bystr20" => app("Option", vec![con("ByStr20")]),
+            "bystr20_to_bech32" => app("Option", vec![con("String")]),
+            "put" | "get" | "contains" | "remove" | "to_list" | "size" => {
+                let (k, v) = (self.fresh(), self.fresh());
+                let map = app("Map", vec![k.clone(), v.clone()]);
+                self.expect(&map, &args[0], span, || {
+                    format!("first argument of builtin {name}")
+                });
+                if name != "to_list" && name != "size" {
+                    self.expect(&k, &args[1], span, || format!("key of builtin {name}"));
+                }
+                match name {
+                    "put" => {
+                        self.expect(&v, &args[2], span, || "value of builtin put".to_string());
+                        map
+                    }
+                    "get" => app("Option", vec![v]),
+                    "contains" => con("Bool"),
+                    "remove" => map,
+                    "to_list" => app("List", vec![app("Pair", vec![k, v])]),
+                    _ => con("Uint32"),
+                }
+            }
+            _ => match conversion_target(name) {
+                // Byte strings that fit are converted without failing.
+                Some(target) => match bystr_width(&self.head(&args[0])) {
+                    Some(n) if n * 8 <= int_width(target) => con(target),
+                    _ => app("Option", vec![con(target)]),
+                },
+                None => unreachable!("unknown builtins return early"),
+            },
+        }
+    }
+
+    fn map_value(&mut self, map: &str, ty: Ty, keys: &[String], env: &Env, span: Span) -> Ty {
+        let map_ty = ty.clone();
+        let mut ty = ty;
+        for key in keys {
+            let found = self.var(key, env, span);
+            let (k, v) = (self.fresh(), self.fresh());
+            if !self.unify(&ty, &app("Map", vec![k.clone(), v.clone()])) {
+                let message = format!(
+                    "`{map}` of type {} is accessed with {} keys",
+                    self.resolve(&map_ty),
+                    keys.len()
+                );
+                self.error(span, message);
+                return self.fresh();
+            }
+            self.expect(&k, &found, span, || format!("key `{key}` of `{map}`"));
+            ty = v;
+        }
+        ty
+    }
+
+    fn remote_field(&mut self, address: &str, field: &str, env: &Env, span: Span) -> Ty {
+        let ty = self.var(address, env, span);
+        let declared = match self.head(&ty) {
+            Ty::Address(Some(fields)) => fields
+                .iter()
+                .find(|f| f.name == field)
+                .map(|f| f.r#type.clone()),
+            Ty::Address(None) | Ty::Con(..) => None,
+            _ => return self.fresh(),
+        };
+        let ty = match field {
+            _ if declared.is_some() => declared,
+            "_balance" => Some(Type::Uint128),
+            "_nonce" => Some(Type::Uint64),
+            "_this_address" => Some(Type::ByStr(20)),
+            "_codehash" => Some(Type::ByStr(32)),
+            _ => None,
+        };
+        match ty {
+            Some(ty) => self.annotation(&ty, span),
+            None => {
+                self.error(
+                    span,
+                    format!("the type of `{address}` does not declare the field `{field}`"),
+                );
+                self.fresh()
+            }
+        }
+    }
+
+    fn stmts(&mut self, stmts: &[Stmt], env: &mut Env) {
+        for stmt in stmts {
+            let span = stmt.span;
+            match &stmt.kind {
+                StmtKind::Load { lhs, field } => {
+                    let ty = self.field(field, span);
+                    env.insert(lhs.clone(), ty);
+                }
+                StmtKind::Store { field, rhs } => {
+                    let expected = self.field(field, span);
+                    let found = self.var(rhs, env, span);
+                    self.expect(&expected, &found, span, || {
+                        format!("value `{rhs}` stored in `{field}`")
+                    });
+                }
+                StmtKind::Bind { lhs, expr } => {
+                    let ty = self.expr(expr, env);
+                    env.insert(lhs.clone(), ty);
+                }
+                StmtKind::MapUpdate { map, keys, value } => {
+                    let ty = self.field(map, span);
+                    let expected = self.map_value(map, ty, keys, env, span);
+                    let found = self.var(value, env, span);
+                    self.expect(&expected, &found, span, || {
+                        format!("value `{value}` stored in `{map}`")
+                    });
+                }
+                StmtKind::MapDelete { map, keys } => {
+                    let ty = self.field(map, span);
+                    self.map_value(map, ty, keys, env, span);
+                }
+                StmtKind::MapGet {
+                    lhs,
+                    map,
+                    keys,
+                    exists,
+                } => {
+                    let ty = self.field(map, span);
+                    let value = self.map_value(map, ty, keys, env, span);
+                    let ty = if *exists {
+                        con("Bool")
+                    } else {
+                        app("Option", vec![value])
+                    };
+                    env.insert(lhs.clone(), ty);
+                }
+                StmtKind::RemoteLoad {
+                    lhs,
+                    address,
+                    field,
+                } => {
+                    let ty = self.remote_field(address, field, env, span);
+                    env.insert(lhs.clone(), ty);
+                }
+                StmtKind::RemoteMapGet {
+                    lhs,
+                    address,
+                    map,
+                    keys,
+                    exists,
+                } => {
+                    let ty = self.remote_field(address, map, env, span);
+                    let value = self.map_value(map, ty, keys, env, span);
+                    let ty = if *exists {
+                        con("Bool")
+                    } else {
+                        app("Option", vec![value])
+                    };
+                    env.insert(lhs.clone(), ty);
+                }
+                StmtKind::TypeCast { lhs, address, ty } => {
+                    let found = self.var(address, env, span);
+                    self.expect(&con("ByStr20"), &found, span, || {
+                        format!("address `{address}`")
+                    });
+                    let ty = self.annotation(ty, span);
+                    env.insert(lhs.clone(), app("Option", vec![ty]));
+                }
+                StmtKind::ReadFromBC { lhs, query, args } => {
+                    let ty = match query.as_str() {
+                        "BLOCKNUMBER" => con("BNum"),
+                        "CHAINID" => con("Uint32"),
+                        "TIMESTAMP" => {
+                            for arg in args {
+                                let found = self.var(arg, env, span);
+                                self.expect(&con("BNum"), &found, span, || {
+                                    format!("block number `{arg}`")
+                                });
+                            }
+                            app("Option", vec![con("Uint64")])
+                        }
+                        _ => self.fresh(),
+                    };
+                    env.insert(lhs.clone(), ty);
+                }
+                StmtKind::Accept | StmtKind::Throw(None) => {}
+                StmtKind::Send(name) | StmtKind::Event(name) | StmtKind::Throw(Some(name)) => {
+                    let (expected, what) = match &stmt.kind {
+                        StmtKind::Send(_) => (app("List", vec![con("Message")]), "sent messages"),
+                        StmtKind::Event(_) => (con("Event"), "event"),
+                        _ => (con("Exception"), "exception"),
+                    };
+                    let found = self.var(name, env, span);
+                    self.expect(&expected, &found, span, || format!("{what} `{name}`"));
+                }
+                StmtKind::Match { scrutinee, arms } => {
+                    let ty = self.var(scrutinee, env, span);
+                    for (pattern, body) in arms {
+                        let mut env = env.clone();
+                        self.pattern(pattern, &ty, &mut env, span);
+                        self.stmts(body, &mut env);
+                    }
+                    self.check_match(scrutinee, &ty, arms.iter().map(|(p, _)| p), span);
+                }
+                StmtKind::CallProc { name, args } => {
+                    let Some(params) = self.procedure_params(name, span) else {
+                        continue;
+                    };
+                    if params.len() != args.len() {
+                        self.error(
+                            span,
+                            format!(
+                                "procedure `{name}` expects {} arguments, got {}",
+                                params.len(),
+                                args.len()
+                            ),
+                        );
+                    }
+                    for (param, arg) in params.iter().zip(args) {
+                        let found = self.var(arg, env, span);
+                        self.expect(param, &found, span, || {
+                            format!("argument `{arg}` of `{name}`")
+                        });
+                    }
+                }
+                StmtKind::Iterate { list, proc } => {
+                    let Some(params) = self.procedure_params(proc, span) else {
+                        continue;
+                    };
+                    let found = self.var(list, env, span);
+                    match params.as_slice() {
+                        [param] => {
+                            let expected = app("List", vec![param.clone()]);
+                            self.expect(&expected, &found, span, || format!("list `{list}`"));
+                        }
+                        _ => self.error(
+                            span,
+                            format!(
+                                "procedure `{proc}` must take one argument to be used with forall"
+                            ),
+                        ),
+                    }
+                }
+            }
+        }
+    }
+
+    /// The types of the parameters of the procedure `name`, reporting it if there is none.
+    fn procedure_params(&mut self, name: &str, span: Span) -> Option<Vec<Ty>> {
+        let contract = &self.module.contract;
+        match contract.component(name) {
+            Some(proc) if proc.kind == ComponentKind::Procedure => Some(
+                proc.params
+                    .iter()
+                    .map(|p| self.annotation(&p.ty, p.span))
+                    .collect(),
+            ),
+            Some(_) => {
+                self.error(span, format!("transition `{name}` cannot be called"));
+                None
+            }
+            None => {
+                self.error(span, format!("undefined procedure `{name}`"));
+                None
+            }
+        }
+    }
+
+    /// Whether values of `ty` can be passed in a transaction, and stored in fields if `maps` is
+    /// set.
+    fn serializable(&self, ty: &Type, maps: bool, seen: &mut Vec<String>) -> bool {
+        match ty {
+            Type::Map(k, v) => {
+                maps && (is_primitive(k) || matches!(**k, Type::Address(_)))
+                    && self.serializable(v, true, seen)
+            }
+            Type::Option(t) | Type::List(t) => self.serializable(t, false, seen),
+            Type::Pair(a, b) => {
+                self.serializable(a, false, seen) && self.serializable(b, false, seen)
+            }
+            Type::Address(_) => true,
+            Type::Function(..) | Type::TypeVar(_) | Type::PolyFun(..) => false,
+            Type::Other(name) => match self.adts.get(name.as_str()) {
+                Some(_) if seen.contains(name) => true,
+                Some(ctrs) => {
+                    seen.push(name.clone());
+                    ctrs.iter()
+                        .flat_map(|c| &c.args)
+                        .all(|t| self.serializable(t, false, seen))
+                }
+                None => name == "ByStr" || name == "Nat",
+            },
+            _ => is_primitive(ty) || *ty == Type::Bool,
+        }
+    }
+
+    fn check_serializable(&mut self, ty: &Type, maps: bool, span: Span, what: String) {
+        match self.convert(ty) {
+            Ok(converted) if !self.serializable(ty, maps, &mut vec![]) => {
+                self.error(
+                    span,
+                    format!("{what} has type {converted}, which is not serializable"),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    fn check(mut self) -> (TypeErrorList, MatchIssueList) {
+        let module = self.module;
+        for entry in module.library.iter().flat_map(|lib| &lib.entries) {
+            match entry {
+                LibEntry::Let {
+                    name,
+                    ty,
+                    expr,
+                    span,
+                    ..
+                } => {
+                    let found = self.expr(expr, &Env::new());
+                    if let Some(ty) = ty {
+                        let expected = self.annotation(ty, *span);
+                        self.expect(&expected, &found, *span, || format!("value of `{name}`"));
+                    }
+                    self.library.insert(name.clone(), found);
+                }
+                LibEntry::Type {
+                    constructors, span, ..
+                } => {
+                    for ty in constructors.iter().flat_map(|c| &c.args) {
+                        self.annotation(ty, *span);
+                    }
+                }
+            }
+        }
+
+        let contract = &module.contract;
+        let mut env: Env = [
+            ("_this_address", Ty::Address(None)),
+            ("_creation_block", con("BNum")),
+        ]
+        .into_iter()
+        .map(|(name, ty)| (name.to_string(), ty))
+        .collect();
+        for param in &contract.params {
+            let ty = self.annotation(&param.ty, param.span);
+            env.insert(param.name.clone(), ty);
+            self.check_serializable(
+                &param.ty,
+                false,
+                param.span,
+                format!("contract parameter `{}`", param.name),
+            );
+        }
+        if let Some(constraint) = &contract.constraint {
+            let found = self.expr(constraint, &env);
+            self.expect(&con("Bool"), &found, constraint.span, || {
+                "the contract constraint".to_string()
+            });
+        }
+        self.fields.insert("_balance".to_string(), con("Uint128"));
+        for field in &contract.fields {
+            let expected = self.annotation(&field.ty, field.span);
+            let found = self.expr(&field.init, &env);
+            self.expect(&expected, &found, field.init.span, || {
+                format!("initial value of `{}`", field.name)
+            });
+            self.check_serializable(
+                &field.ty,
+                true,
+                field.span,
+                format!("field `{}`", field.name),
+            );
+            self.fields.insert(field.name.clone(), expected);
+        }
+
+        env.extend(
+            [
+                ("_sender", Ty::Address(None)),
+                ("_origin", Ty::Address(None)),
+                ("_amount", con("Uint128")),
+            ]
+            .into_iter()
+            .map(|(name, ty)| (name.to_string(), ty)),
+        );
+        for component in &contract.components {
+            let mut env = env.clone();
+            for param in &component.params {
+                let ty = self.annotation(&param.ty, param.span);
+                env.insert(param.name.clone(), ty);
+                if component.kind == ComponentKind::Transition {
+                    self.check_serializable(
+                        &param.ty,
+                        false,
+                        param.span,
+                        format!("parameter `{}` of `{}`", param.name, component.name),
+                    );
+                }
+            }
+            self.stmts(&component.body, &mut env);
+        }
+
+        let mut errors = self.errors;
+        errors.sort_by_key(|e| e.span.start);
+        let mut matches = self.matches;
+        matches.sort_by_key(|m| m.span().start);
+        (TypeErrorList(errors), MatchIssueList(matches))
+    }
+}
+
+impl Constructors<Ty> for Checker<'_> {
+    fn constructors(&mut self, ty: &Ty) -> Option<Vec<(String, Vec<Ty>)>> {
+        let Ty::Con(name, args) = self.head(ty) else {
+            return None;
+        };
+        let ctr = |name: &str, args: Vec<Ty>| (name.to_string(), args);
+        Some(match (name.as_str(), args.as_slice()) {
+            ("Bool", []) => vec![ctr("True", vec![]), ctr("False", vec![])],
+            ("Nat", []) => vec![ctr("Zero", vec![]), ctr("Succ", vec![con("Nat")])],
+            ("Option", [a]) => vec![ctr("Some", vec![a.clone()]), ctr("None", vec![])],
+            ("List", [a]) => vec![
+                ctr("Nil", vec![]),
+                ctr("Cons", vec![a.clone(), app("List", vec![a.clone()])]),
+            ],
+            ("Pair", [a, b]) => vec![ctr("Pair", vec![a.clone(), b.clone()])],
+            (adt, []) => {
+                let ctrs = *self.adts.get(adt)?;
+                ctrs.iter()
+                    .map(|c| {
+                        let args = c.args.iter().map(|t| self.convert(t));
+                        Some(ctr(&c.name, args.collect::<Result<_, _>>().ok()?))
+                    })
+                    .collect::<Option<_>>()?
+            }
+            _ => return None,
+        })
+    }
+}
+
+/// `Event`, `Exception` or `Message`, depending on the entries of a message literal.
+fn message_type(entries: &[MessageEntry]) -> &'static str {
+    if entries.iter().any(|e| e.name == "_eventname") {
+        "Event"
+    } else if entries.iter().any(|e| e.name == "_exception") {
+        "Exception"
+    } else {
+        "Message"
+    }
+}
+
+fn is(ty: &Ty, name: &str) -> bool {
+    matches!(ty, Ty::Con(n, args) if n == name && args.is_empty())
+}
+
+fn int_width(name: &str) -> usize {
+    name.trim_start_matches("Uint")
+        .trim_start_matches("Int")
+        .parse()
+        .unwrap_or(0)
+}
+
+fn is_int(ty: &Ty) -> bool {
+    matches!(ty, Ty::Con(name, args) if args.is_empty()
+        && (name.starts_with("Int") || name.starts_with("Uint"))
+        && int_width(name) > 0)
+}
+
+fn is_uint(ty: &Ty) -> bool {
+    is_int(ty) && matches!(ty, Ty::Con(name, _) if name.starts_with("Uint"))
+}
+
+fn bystr_width(ty: &Ty) -> Option<usize> {
+    match ty {
+        Ty::Con(name, args) if args.is_empty() => name.strip_prefix("ByStr")?.parse().ok(),
+        Ty::Address(_) => Some(20),
+        _ => None,
+    }
+}
+
+fn is_comparable(ty: &Ty) -> bool {
+    is_int(ty)
+        || bystr_width(ty).is_some()
+        || ["String", "BNum", "ByStr"].iter().any(|name| is(ty, name))
+}
+
+/// The integer type returned by a conversion builtin such as `to_uint128`.
+fn conversion_target(builtin: &str) -> Option<&'static str> {
+    Some(match builtin {
+        "to_int32" => "Int32",
+        "to_int64" => "Int64",
+        "to_int128" => "Int128",
+        "to_int256" => "Int256",
+        "to_uint32" => "Uint32",
+        "to_uint64" => "Uint64",
+        "to_uint128" => "Uint128",
+        "to_uint256" => "Uint256",
+        _ => return None,
+    })
+}
+
+fn is_primitive(ty: &Type) -> bool {
+    match ty {
+        Type::Int32
+        | Type::Int64
+        | Type::Int128
+        | Type::Int256
+        | Type::Uint32
+        | Type::Uint64
+        | Type::Uint128
+        | Type::Uint256
+        | Type::String
+        | Type::BNum
+        | Type::ByStr(_) => true,
+        Type::Other(name) => name == "ByStr",
+        _ => false,
+    }
+}
+
+/// The type errors of `module` and the issues of its matches.
+pub(crate) fn check_module(module: &Module) -> (TypeErrorList, MatchIssueList) {
+    Checker::new(module).check()
+}
+
+impl Contract {
+    /// Type check the library, fields and components of the contract: the types of expressions
+    /// are inferred and checked against annotations, fields, procedure parameters and builtins,
+    /// matches must be exhaustive, and parameters and fields must have serializable types. The
+    /// list is empty if the contract is well-typed.
+    ///
+    /// Functions imported from libraries other than the standard ones are not known, so their
+    /// uses are not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0
+    ///     contract Counter ()
+    ///     field count : Uint32 = Uint32 0
+    ///     transition Increment ()
+    ///       one = Uint128 1;
+    ///       count := one
+    ///     end",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     contract.type_check().unwrap()[0].to_string(),
+    ///     "6:7: value `one` stored in `count` has type Uint128 but Uint32 was expected"
+    /// );
+    /// ```
+    pub fn type_check(&self) -> Result<TypeErrorList, Error> {
+        Ok(check_module(self.syntax_tree()?).0)
+    }
+}
@@ -0,0 +1,196 @@
+//! Generation of TypeScript declarations for the frontends using a contract through zilliqa-js.
+
+use std::fmt::Write;
+
+use crate::{Contract, Error, Field, FieldList, InferredField, Type};
+
+/// Declarations shared by all generated modules. Integers are strings in the JSON of the Zilliqa
+/// API so that they keep their precision.
+const PRELUDE: &str = r#"export type Int32 = string;
+export type Int64 = string;
+export type Int128 = string;
+export type Int256 = string;
+export type Uint32 = string;
+export type Uint64 = string;
+export type Uint128 = string;
+export type Uint256 = string;
+export type BNum = string;
+/** A hex string prefixed with `0x`. */
+export type ByStr = string;
+export type Bool = { constructor: "True" | "False"; argtypes: []; arguments: [] };
+export type Option<T> =
+  | { constructor: "Some"; argtypes: string[]; arguments: [T] }
+  | { constructor: "None"; argtypes: string[]; arguments: [] };
+export type Pair<A, B> = { constructor: "Pair"; argtypes: string[]; arguments: [A, B] };
+/** A value of a user-defined ADT. */
+export type Adt = { constructor: string; argtypes: string[]; arguments: unknown[] };
+/** A parameter in the format expected by `Contract.call` of zilliqa-js. */
+export type CallParam = { vname: string; type: string; value: unknown };
+/** A parameter of an event log in a transaction receipt. */
+export type EventParam<N extends string, T> = { vname: N; type: string; value: T };
+"#;
+
+/// The TypeScript type of the JSON representation of a Scilla type.
+fn ts_type(ty: &Type) -> String {
+    match ty {
+        Type::String => "string".to_string(),
+        Type::ByStr(_) | Type::Address(_) => "ByStr".to_string(),
+        Type::Map(_, v) => format!("Record<string, {}>", ts_type(v)),
+        Type::Option(t) => format!("Option<{}>", ts_type(t)),
+        Type::List(t) => format!("Array<{}>", ts_type(t)),
+        Type::Pair(a, b) => format!("Pair<{}, {}>", ts_type(a), ts_type(b)),
+        Type::Other(name) if name == "ByStr" => "ByStr".to_string(),
+        Type::Other(_) => "Adt".to_string(),
+        Type::Function(..) | Type::TypeVar(_) | Type::PolyFun(..) => "unknown".to_string(),
+        _ => ty.to_string(),
+    }
+}
+
+/// An object type with a property per parameter, e.g. `{ to: ByStr; amount: Uint128 }`.
+fn object_type(params: &FieldList) -> String {
+    if params.is_empty() {
+        return "{}".to_string();
+    }
+    let properties: Vec<String> = params
+        .iter()
+        .map(|p| format!("{}: {}", p.name, ts_type(&p.r#type)))
+        .collect();
+    format!("{{ {} }}", properties.join("; "))
+}
+
+/// The `params` of an event log, e.g. `[EventParam<"to", ByStr>]`, with `unknown` for the
+/// parameters whose type is unknown.
+fn event_params(params: &[InferredField]) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|p| {
+            let ty = p.r#type.as_ref().map_or("unknown".to_string(), ts_type);
+            format!("EventParam<\"{}\", {ty}>", p.name)
+        })
+        .collect();
+    format!("[{}]", params.join(", "))
+}
+
+/// An interface with a property per parameter, one per line.
+fn interface(name: &str, params: &FieldList) -> String {
+    let mut out = format!("export interface {name} {{\n");
+    for p in params.iter() {
+        let _ = writeln!(out, "  {}: {};", p.name, ts_type(&p.r#type));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// A union type of the given variants, or `never` if there are none.
+fn union(name: &str, variants: &[String]) -> String {
+    if variants.is_empty() {
+        return format!("export type {name} = never;\n");
+    }
+    let mut out = format!("export type {name} =\n");
+    for variant in variants {
+        let _ = writeln!(out, "  | {variant}");
+    }
+    out.pop();
+    out.push_str(";\n");
+    out
+}
+
+/// Lower camel case, e.g. `FungibleToken` becomes `fungibleToken`.
+fn camel_case(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|c| c.to_lowercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+impl Contract {
+    /// Generate a TypeScript module describing the contract: interfaces for its init parameters
+    /// and its state, a union of the transition payloads discriminated by `_tag`, a union of its
+    /// event logs as found in transaction receipts, discriminated by `_eventname`, and a function
+    /// converting a payload to the parameters expected by `Contract.call` of zilliqa-js.
+    ///
+    /// Fails with [`Error::NoSource`] if the contract was not parsed from its Scilla source, since
+    /// its events are not known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scilla_parser::Contract;
+    /// let contract = Contract::from_source(
+    ///     "scilla_version 0 contract Counter () transition Increase (by : Uint128) end",
+    /// )
+    /// .unwrap();
+    /// let ts = contract.typescript().unwrap();
+    /// assert!(ts.contains(r#"| { _tag: "Increase"; params: { by: Uint128 } };"#));
+    /// ```
+    pub fn typescript(&self) -> Result<String, Error> {
+        let name = &self.name;
+        let mut out = format!("// Generated from the `{name}` contract. Do not edit.\n\n");
+        out.push_str(PRELUDE);
+        out.push('\n');
+
+        out.push_str(&interface(&format!("{name}Init"), &self.init_params));
+        out.push('\n');
+
+        let mut state = self.fields.clone();
+        state.0.insert(0, Field::new("_balance", Type::Uint128));
+        out.push_str(&interface(&format!("{name}State"), &state));
+        out.push('\n');
+
+        let transitions: Vec<String> = self
+            .transitions
+            .iter()
+            .map(|t| {
+                format!(
+                    "{{ _tag: \"{}\"; params: {} }}",
+                    t.name,
+                    object_type(&t.params)
+                )
+            })
+            .collect();
+        out.push_str(&union(&format!("{name}Transition"), &transitions));
+        out.push('\n');
+
+        let events: Vec<String> = self
+            .events()?
+            .iter()
+            .map(|e| {
+                format!(
+                    "{{ _eventname: \"{}\"; address: ByStr; params: {} }}",
+                    e.name,
+                    event_params(&e.params)
+                )
+            })
+            .collect();
+        out.push_str(&union(&format!("{name}Event"), &events));
+        out.push('\n');
+
+        let _ = writeln!(
+            out,
+            "const {}ParamTypes: Record<string, Record<string, string>> = {{",
+            camel_case(name)
+        );
+        for t in self.transitions.iter() {
+            let types: Vec<String> = t
+                .params
+                .iter()
+                .map(|p| format!("{}: \"{}\"", p.name, p.r#type.to_scilla_string()))
+                .collect();
+            let _ = writeln!(out, "  {}: {{ {} }},", t.name, types.join(", "));
+        }
+        out.push_str("};\n\n");
+
+        let _ = write!(
+            out,
+            "/** The parameters of a `{name}` transition as expected by `Contract.call`. */
+export function {camel}CallParams(call: {name}Transition): CallParam[] {{
+  const types = {camel}ParamTypes[call._tag];
+  return Object.entries(call.params).map(([vname, value]) => ({{ vname, type: types[vname], value }}));
+}}
+",
+            camel = camel_case(name)
+        );
+        Ok(out)
+    }
+}
@@ -0,0 +1,385 @@
+use std::fmt::Display;
+
+use num_bigint::{BigInt, BigUint};
+use serde_json::Value as JsonValue;
+
+use crate::{
+    ast::{LibEntry, Library},
+    Error, Type,
+};
+
+/// A Scilla value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// An integer of one of the `IntX` or `UintX` types.
+    Int(Type, BigInt),
+    String(String),
+    BNum(BigUint),
+    /// A fixed size byte string, `ByStrX`.
+    ByStrX(Vec<u8>),
+    /// A byte string of arbitrary length, `ByStr`.
+    ByStr(Vec<u8>),
+    Map {
+        key_type: Type,
+        value_type: Type,
+        entries: Vec<(Value, Value)>,
+    },
+    /// A value of an algebraic data type, including `Bool`, `Option`, `List` and `Pair`.
+    Adt {
+        constructor: String,
+        type_args: Vec<Type>,
+        args: Vec<Value>,
+    },
+    /// A message or an event.
+    Message(Vec<(String, Value)>),
+}
+
+/// Returns the inclusive range of an integer type, or `None` if `ty` is not an integer type.
+pub fn int_bounds(ty: &Type) -> Option<(BigInt, BigInt)> {
+    let (bits, signed) = match ty {
+        Type::Int32 => (32, true),
+        Type::Int64 => (64, true),
+        Type::Int128 => (128, true),
+        Type::Int256 => (256, true),
+        Type::Uint32 => (32, false),
+        Type::Uint64 => (64, false),
+        Type::Uint128 => (128, false),
+        Type::Uint256 => (256, false),
+        _ => return None,
+    };
+    let one = BigInt::from(1);
+    Some(if signed {
+        let half: BigInt = &one << (bits - 1);
+        (-&half, half - one)
+    } else {
+        let max: BigInt = (&one << bits) - one;
+        (BigInt::from(0), max)
+    })
+}
+
+impl Value {
+    /// Creates an integer value, checking that it fits into `ty`.
+    pub fn int(ty: Type, value: impl Into<BigInt>) -> Result<Self, Error> {
+        let value = value.into();
+        match int_bounds(&ty) {
+            Some((min, max)) if value >= min && value <= max => Ok(Value::Int(ty, value)),
+            Some(_) => Err(Error::InvalidValue(format!(
+                "{value} does not fit into {ty}"
+            ))),
+            None => Err(Error::InvalidValue(format!("{ty} is not an integer type"))),
+        }
+    }
+
+    pub fn bool(b: bool) -> Self {
+        Value::Adt {
+            constructor: if b { "True" } else { "False" }.to_string(),
+            type_args: vec![],
+            args: vec![],
+        }
+    }
+
+    /// `Some {ty} value`, or `None {ty}`.
+    pub fn option(ty: Type, value: Option<Value>) -> Self {
+        Value::Adt {
+            constructor: if value.is_some() { "Some" } else { "None" }.to_string(),
+            type_args: vec![ty],
+            args: value.into_iter().collect(),
+        }
+    }
+
+    /// `Pair {a_type b_type} a b`.
+    pub fn pair(a_type: Type, b_type: Type, a: Value, b: Value) -> Self {
+        Value::Adt {
+            constructor: "Pair".to_string(),
+            type_args: vec![a_type, b_type],
+            args: vec![a, b],
+        }
+    }
+
+    /// Builds a Scilla list out of `items`, i.e. nested `Cons` constructors ending in `Nil`.
+    pub fn list(elem_type: Type, items: Vec<Value>) -> Self {
+        items.into_iter().rev().fold(
+            Value::Adt {
+                constructor: "Nil".to_string(),
+                type_args: vec![elem_type.clone()],
+                args: vec![],
+            },
+            |tail, head| Value::Adt {
+                constructor: "Cons".to_string(),
+                type_args: vec![elem_type.clone()],
+                args: vec![head, tail],
+            },
+        )
+    }
+
+    /// Returns the elements of a list value.
+    pub fn as_list(&self) -> Option<Vec<&Value>> {
+        let mut items = vec![];
+        let mut current = self;
+        loop {
+            match current {
+                Value::Adt {
+                    constructor, args, ..
+                } if constructor == "Nil" && args.is_empty() => return Some(items),
+                Value::Adt {
+                    constructor, args, ..
+                } if constructor == "Cons" && args.len() == 2 => {
+                    items.push(&args[0]);
+                    current = &args[1];
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    /// Decode a value from the JSON representation used by the Zilliqa API, e.g. in transaction
+    /// receipts and contract states. Constructors of user defined ADTs are looked up in `library`.
+    pub fn from_json(
+        json: &JsonValue,
+        ty: &Type,
+        library: Option<&Library>,
+    ) -> Result<Self, Error> {
+        let invalid = || Error::InvalidValue(format!("{json} is not a valid {ty}"));
+        let as_str = || json.as_str().ok_or_else(invalid);
+        // Integers are usually encoded as strings, but accept JSON numbers as well.
+        let as_number = || match json {
+            JsonValue::String(s) => Ok(s.clone()),
+            JsonValue::Number(n) => Ok(n.to_string()),
+            _ => Err(invalid()),
+        };
+
+        match ty {
+            Type::Int32
+            | Type::Int64
+            | Type::Int128
+            | Type::Int256
+            | Type::Uint32
+            | Type::Uint64
+            | Type::Uint128
+            | Type::Uint256 => {
+                let value = as_number()?.parse::<BigInt>().map_err(|_| invalid())?;
+                Value::int(ty.clone(), value)
+            }
+            Type::String => Ok(Value::String(as_str()?.to_string())),
+            Type::BNum => Ok(Value::BNum(as_number()?.parse().map_err(|_| invalid())?)),
+            Type::ByStr(n) => {
+                let bytes = decode_hex(as_str()?).ok_or_else(invalid)?;
+                if bytes.len() != *n {
+                    return Err(invalid());
+                }
+                Ok(Value::ByStrX(bytes))
+            }
+            Type::Address(_) => Value::from_json(json, &Type::ByStr(20), library),
+            Type::Other(name) if name == "ByStr" => {
+                Ok(Value::ByStr(decode_hex(as_str()?).ok_or_else(invalid)?))
+            }
+            Type::Map(key_type, value_type) => {
+                let entries = match json {
+                    // Contract states encode maps as objects ...
+                    JsonValue::Object(object) => object
+                        .iter()
+                        .map(|(k, v)| {
+                            Ok((
+                                Value::from_json(&JsonValue::String(k.clone()), key_type, library)?,
+                                Value::from_json(v, value_type, library)?,
+                            ))
+                        })
+                        .collect::<Result<_, Error>>()?,
+                    // ... while messages and events use a list of key/val pairs.
+                    JsonValue::Array(array) => array
+                        .iter()
+                        .map(|entry| {
+                            Ok((
+                                Value::from_json(&entry["key"], key_type, library)?,
+                                Value::from_json(&entry["val"], value_type, library)?,
+                            ))
+                        })
+                        .collect::<Result<_, Error>>()?,
+                    _ => return Err(invalid()),
+                };
+                Ok(Value::Map {
+                    key_type: *key_type.clone(),
+                    value_type: *value_type.clone(),
+                    entries,
+                })
+            }
+            Type::List(elem_type) => {
+                let items = json
+                    .as_array()
+                    .ok_or_else(invalid)?
+                    .iter()
+                    .map(|item| Value::from_json(item, elem_type, library))
+                    .collect::<Result<_, Error>>()?;
+                Ok(Value::list(*elem_type.clone(), items))
+            }
+            Type::Bool => match json["constructor"].as_str() {
+                Some("True") => Ok(Value::bool(true)),
+                Some("False") => Ok(Value::bool(false)),
+                _ => Err(invalid()),
+            },
+            Type::Option(t) => match json["constructor"].as_str() {
+                Some("None") => Ok(Value::Adt {
+                    constructor: "None".to_string(),
+                    type_args: vec![*t.clone()],
+                    args: vec![],
+                }),
+                Some("Some") => Ok(Value::Adt {
+                    constructor: "Some".to_string(),
+                    type_args: vec![*t.clone()],
+                    args: vec![Value::from_json(&json["arguments"][0], t, library)?],
+                }),
+                _ => Err(invalid()),
+            },
+            Type::Pair(a, b) => match json["constructor"].as_str() {
+                Some("Pair") => Ok(Value::Adt {
+                    constructor: "Pair".to_string(),
+                    type_args: vec![*a.clone(), *b.clone()],
+                    args: vec![
+                        Value::from_json(&json["arguments"][0], a, library)?,
+                        Value::from_json(&json["arguments"][1], b, library)?,
+                    ],
+                }),
+                _ => Err(invalid()),
+            },
+            Type::Other(name) => {
+                // Receipts qualify constructors with the address of the contract defining them.
+                let constructor = json["constructor"].as_str().ok_or_else(invalid)?;
+                let constructor = constructor.rsplit('.').next().unwrap_or(constructor);
+                let arg_types = library
+                    .and_then(|lib| {
+                        lib.entries.iter().find_map(|entry| match entry {
+                            LibEntry::Type {
+                                name: adt,
+                                constructors,
+                                ..
+                            } if adt == name => constructors
+                                .iter()
+                                .find(|c| c.name == constructor)
+                                .map(|c| &c.args),
+                            _ => None,
+                        })
+                    })
+                    .ok_or_else(invalid)?;
+                let args = arg_types
+                    .iter()
+                    .enumerate()
+                    .map(|(i, t)| Value::from_json(&json["arguments"][i], t, library))
+                    .collect::<Result<_, Error>>()?;
+                Ok(Value::Adt {
+                    constructor: constructor.to_string(),
+                    type_args: vec![],
+                    args,
+                })
+            }
+            Type::Function(..) | Type::TypeVar(_) | Type::PolyFun(..) => Err(invalid()),
+        }
+    }
+}
+
+pub(crate) fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let digit = |b: u8| (b as char).to_digit(16);
+            Some((digit(pair[0])? * 16 + digit(pair[1])?) as u8)
+        })
+        .collect()
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Int(ty, n) => write!(f, "{} {}", ty, n),
+            Value::String(s) => write!(f, "{:?}", s),
+            Value::BNum(n) => write!(f, "BNum {}", n),
+            Value::ByStrX(bytes) | Value::ByStr(bytes) => {
+                write!(f, "0x")?;
+                bytes.iter().try_for_each(|b| write!(f, "{:02x}", b))
+            }
+            Value::Map { entries, .. } => {
+                write!(f, "[")?;
+                for (i, (k, v)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{} => {}", k, v)?;
+                }
+                write!(f, "]")
+            }
+            Value::Adt {
+                constructor, args, ..
+            } => {
+                if args.is_empty() {
+                    return write!(f, "{}", constructor);
+                }
+                write!(f, "({}", constructor)?;
+                args.iter().try_for_each(|arg| write!(f, " {}", arg))?;
+                write!(f, ")")
+            }
+            Value::Message(entries) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{} : {}", k, v)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_int_overflow() {
+        assert!(Value::int(Type::Uint32, 4294967295u64).is_ok());
+        assert!(Value::int(Type::Uint32, 4294967296u64).is_err());
+        assert!(Value::int(Type::Int32, -2147483648i64).is_ok());
+        assert!(Value::int(Type::Uint128, -1).is_err());
+    }
+
+    #[test]
+    fn test_from_json() {
+        let ty = Type::List(Box::new(Type::Pair(
+            Box::new(Type::ByStr(20)),
+            Box::new(Type::Uint128),
+        )));
+        let json = json!([{
+            "constructor": "Pair",
+            "argtypes": ["ByStr20", "Uint128"],
+            "arguments": ["0x1234567890123456789012345678901234567890", "100"]
+        }]);
+        let value = Value::from_json(&json, &ty, None).unwrap();
+        let items = value.as_list().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0].to_string(),
+            "(Pair 0x1234567890123456789012345678901234567890 Uint128 100)"
+        );
+    }
+
+    #[test]
+    fn test_map_from_json() {
+        let ty = Type::Map(Box::new(Type::String), Box::new(Type::Bool));
+        let json =
+            json!([{"key": "a", "val": {"constructor": "True", "argtypes": [], "arguments": []}}]);
+        assert_eq!(
+            Value::from_json(&json, &ty, None).unwrap(),
+            Value::Map {
+                key_type: Type::String,
+                value_type: Type::Bool,
+                entries: vec![(Value::String("a".to_string()), Value::bool(true))]
+            }
+        );
+    }
+}
@@ -0,0 +1,182 @@
+/// Bindings of the `FungibleToken` contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FungibleToken {
+    pub address: [u8; 20],
+}
+#[allow(clippy::too_many_arguments)]
+impl FungibleToken {
+    pub fn new(address: [u8; 20]) -> Self {
+        Self { address }
+    }
+    /// Call the `Mint` transition.
+    pub fn mint(
+        &self,
+        recipient: [u8; 20],
+        amount: u128,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "Mint",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("recipient", "ByStr20", &
+                recipient), ::scilla_parser::bindings::CallParam::new("amount",
+                "Uint128", & amount)
+            ],
+        )
+    }
+    /// Call the `Burn` transition.
+    pub fn burn(
+        &self,
+        burn_account: [u8; 20],
+        amount: u128,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "Burn",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("burn_account", "ByStr20", &
+                burn_account), ::scilla_parser::bindings::CallParam::new("amount",
+                "Uint128", & amount)
+            ],
+        )
+    }
+    /// Call the `IncreaseAllowance` transition.
+    pub fn increase_allowance(
+        &self,
+        spender: [u8; 20],
+        amount: u128,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "IncreaseAllowance",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("spender", "ByStr20", &
+                spender), ::scilla_parser::bindings::CallParam::new("amount", "Uint128",
+                & amount)
+            ],
+        )
+    }
+    /// Call the `DecreaseAllowance` transition.
+    pub fn decrease_allowance(
+        &self,
+        spender: [u8; 20],
+        amount: u128,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "DecreaseAllowance",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("spender", "ByStr20", &
+                spender), ::scilla_parser::bindings::CallParam::new("amount", "Uint128",
+                & amount)
+            ],
+        )
+    }
+    /// Call the `Transfer` transition.
+    pub fn transfer(
+        &self,
+        to: [u8; 20],
+        amount: u128,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "Transfer",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("to", "ByStr20", & to),
+                ::scilla_parser::bindings::CallParam::new("amount", "Uint128", & amount)
+            ],
+        )
+    }
+    /// Call the `TransferFrom` transition.
+    pub fn transfer_from(
+        &self,
+        from: [u8; 20],
+        to: [u8; 20],
+        amount: u128,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "TransferFrom",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("from", "ByStr20", & from),
+                ::scilla_parser::bindings::CallParam::new("to", "ByStr20", & to),
+                ::scilla_parser::bindings::CallParam::new("amount", "Uint128", & amount)
+            ],
+        )
+    }
+}
+/// Init parameters of the `FungibleToken` contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FungibleTokenInit {
+    pub contract_owner: [u8; 20],
+    pub name: ::std::string::String,
+    pub symbol: ::std::string::String,
+    pub decimals: u32,
+    pub init_supply: u128,
+}
+impl FungibleTokenInit {
+    /// The init JSON used to deploy the contract.
+    pub fn to_json(&self) -> ::scilla_parser::bindings::JsonValue {
+        ::scilla_parser::bindings::params_json(
+            &[
+                ::scilla_parser::bindings::CallParam::new(
+                    "_scilla_version",
+                    "Uint32",
+                    &0u32,
+                ),
+                ::scilla_parser::bindings::CallParam::new(
+                    "contract_owner",
+                    "ByStr20",
+                    &self.contract_owner,
+                ),
+                ::scilla_parser::bindings::CallParam::new("name", "String", &self.name),
+                ::scilla_parser::bindings::CallParam::new(
+                    "symbol",
+                    "String",
+                    &self.symbol,
+                ),
+                ::scilla_parser::bindings::CallParam::new(
+                    "decimals",
+                    "Uint32",
+                    &self.decimals,
+                ),
+                ::scilla_parser::bindings::CallParam::new(
+                    "init_supply",
+                    "Uint128",
+                    &self.init_supply,
+                ),
+            ],
+        )
+    }
+}
+/// State of the `FungibleToken` contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FungibleTokenState(pub ::scilla_parser::bindings::JsonValue);
+#[allow(clippy::type_complexity)]
+impl FungibleTokenState {
+    /// The `total_supply` field.
+    pub fn total_supply(&self) -> ::std::result::Result<u128, ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["total_supply"])
+    }
+    /// The `balances` field.
+    pub fn balances(
+        &self,
+    ) -> ::std::result::Result<
+        ::std::collections::BTreeMap<[u8; 20], u128>,
+        ::scilla_parser::Error,
+    > {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["balances"])
+    }
+    /// The `allowances` field.
+    pub fn allowances(
+        &self,
+    ) -> ::std::result::Result<
+        ::std::collections::BTreeMap<
+            [u8; 20],
+            ::std::collections::BTreeMap<[u8; 20], u128>,
+        >,
+        ::scilla_parser::Error,
+    > {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["allowances"])
+    }
+}
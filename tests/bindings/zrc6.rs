@@ -0,0 +1,400 @@
+/// Bindings of the `NonfungibleToken` contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonfungibleToken {
+    pub address: [u8; 20],
+}
+#[allow(clippy::too_many_arguments)]
+impl NonfungibleToken {
+    pub fn new(address: [u8; 20]) -> Self {
+        Self { address }
+    }
+    /// Call the `Pause` transition.
+    pub fn pause(&self) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(self.address, "Pause", vec![])
+    }
+    /// Call the `Unpause` transition.
+    pub fn unpause(&self) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(self.address, "Unpause", vec![])
+    }
+    /// Call the `SetRoyaltyRecipient` transition.
+    pub fn set_royalty_recipient(
+        &self,
+        to: [u8; 20],
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "SetRoyaltyRecipient",
+            vec![::scilla_parser::bindings::CallParam::new("to", "ByStr20", & to)],
+        )
+    }
+    /// Call the `SetRoyaltyFeeBPS` transition.
+    pub fn set_royalty_fee_bps(
+        &self,
+        fee_bps: u128,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "SetRoyaltyFeeBPS",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("fee_bps", "Uint128", &
+                fee_bps)
+            ],
+        )
+    }
+    /// Call the `SetBaseURI` transition.
+    pub fn set_base_uri(
+        &self,
+        uri: ::std::string::String,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "SetBaseURI",
+            vec![::scilla_parser::bindings::CallParam::new("uri", "String", & uri)],
+        )
+    }
+    /// Call the `Mint` transition.
+    pub fn mint(
+        &self,
+        to: [u8; 20],
+        token_uri: ::std::string::String,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "Mint",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("to", "ByStr20", & to),
+                ::scilla_parser::bindings::CallParam::new("token_uri", "String", &
+                token_uri)
+            ],
+        )
+    }
+    /// Call the `BatchMint` transition.
+    pub fn batch_mint(
+        &self,
+        to_token_uri_pair_list: ::std::vec::Vec<([u8; 20], ::std::string::String)>,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "BatchMint",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("to_token_uri_pair_list",
+                "List (Pair (ByStr20) (String))", & to_token_uri_pair_list)
+            ],
+        )
+    }
+    /// Call the `Burn` transition.
+    pub fn burn(
+        &self,
+        token_id: ::scilla_parser::bindings::U256,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "Burn",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("token_id", "Uint256", &
+                token_id)
+            ],
+        )
+    }
+    /// Call the `BatchBurn` transition.
+    pub fn batch_burn(
+        &self,
+        token_id_list: ::std::vec::Vec<::scilla_parser::bindings::U256>,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "BatchBurn",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("token_id_list",
+                "List (Uint256)", & token_id_list)
+            ],
+        )
+    }
+    /// Call the `AddMinter` transition.
+    pub fn add_minter(
+        &self,
+        minter: [u8; 20],
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "AddMinter",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("minter", "ByStr20", & minter)
+            ],
+        )
+    }
+    /// Call the `RemoveMinter` transition.
+    pub fn remove_minter(
+        &self,
+        minter: [u8; 20],
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "RemoveMinter",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("minter", "ByStr20", & minter)
+            ],
+        )
+    }
+    /// Call the `SetSpender` transition.
+    pub fn set_spender(
+        &self,
+        spender: [u8; 20],
+        token_id: ::scilla_parser::bindings::U256,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "SetSpender",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("spender", "ByStr20", &
+                spender), ::scilla_parser::bindings::CallParam::new("token_id",
+                "Uint256", & token_id)
+            ],
+        )
+    }
+    /// Call the `AddOperator` transition.
+    pub fn add_operator(
+        &self,
+        operator: [u8; 20],
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "AddOperator",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("operator", "ByStr20", &
+                operator)
+            ],
+        )
+    }
+    /// Call the `RemoveOperator` transition.
+    pub fn remove_operator(
+        &self,
+        operator: [u8; 20],
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "RemoveOperator",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("operator", "ByStr20", &
+                operator)
+            ],
+        )
+    }
+    /// Call the `TransferFrom` transition.
+    pub fn transfer_from(
+        &self,
+        to: [u8; 20],
+        token_id: ::scilla_parser::bindings::U256,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "TransferFrom",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("to", "ByStr20", & to),
+                ::scilla_parser::bindings::CallParam::new("token_id", "Uint256", &
+                token_id)
+            ],
+        )
+    }
+    /// Call the `BatchTransferFrom` transition.
+    pub fn batch_transfer_from(
+        &self,
+        to_token_id_pair_list: ::std::vec::Vec<
+            ([u8; 20], ::scilla_parser::bindings::U256),
+        >,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "BatchTransferFrom",
+            vec![
+                ::scilla_parser::bindings::CallParam::new("to_token_id_pair_list",
+                "List (Pair (ByStr20) (Uint256))", & to_token_id_pair_list)
+            ],
+        )
+    }
+    /// Call the `SetContractOwnershipRecipient` transition.
+    pub fn set_contract_ownership_recipient(
+        &self,
+        to: [u8; 20],
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "SetContractOwnershipRecipient",
+            vec![::scilla_parser::bindings::CallParam::new("to", "ByStr20", & to)],
+        )
+    }
+    /// Call the `AcceptContractOwnership` transition.
+    pub fn accept_contract_ownership(
+        &self,
+    ) -> ::scilla_parser::bindings::TransitionCall {
+        ::scilla_parser::bindings::TransitionCall::new(
+            self.address,
+            "AcceptContractOwnership",
+            vec![],
+        )
+    }
+}
+/// Init parameters of the `NonfungibleToken` contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonfungibleTokenInit {
+    pub initial_contract_owner: [u8; 20],
+    pub initial_base_uri: ::std::string::String,
+    pub name: ::std::string::String,
+    pub symbol: ::std::string::String,
+}
+impl NonfungibleTokenInit {
+    /// The init JSON used to deploy the contract.
+    pub fn to_json(&self) -> ::scilla_parser::bindings::JsonValue {
+        ::scilla_parser::bindings::params_json(
+            &[
+                ::scilla_parser::bindings::CallParam::new(
+                    "_scilla_version",
+                    "Uint32",
+                    &0u32,
+                ),
+                ::scilla_parser::bindings::CallParam::new(
+                    "initial_contract_owner",
+                    "ByStr20",
+                    &self.initial_contract_owner,
+                ),
+                ::scilla_parser::bindings::CallParam::new(
+                    "initial_base_uri",
+                    "String",
+                    &self.initial_base_uri,
+                ),
+                ::scilla_parser::bindings::CallParam::new("name", "String", &self.name),
+                ::scilla_parser::bindings::CallParam::new(
+                    "symbol",
+                    "String",
+                    &self.symbol,
+                ),
+            ],
+        )
+    }
+}
+/// State of the `NonfungibleToken` contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonfungibleTokenState(pub ::scilla_parser::bindings::JsonValue);
+#[allow(clippy::type_complexity)]
+impl NonfungibleTokenState {
+    /// The `is_paused` field.
+    pub fn is_paused(&self) -> ::std::result::Result<bool, ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["is_paused"])
+    }
+    /// The `token_name` field.
+    pub fn token_name(
+        &self,
+    ) -> ::std::result::Result<::std::string::String, ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["token_name"])
+    }
+    /// The `token_symbol` field.
+    pub fn token_symbol(
+        &self,
+    ) -> ::std::result::Result<::std::string::String, ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["token_symbol"])
+    }
+    /// The `contract_owner` field.
+    pub fn contract_owner(
+        &self,
+    ) -> ::std::result::Result<[u8; 20], ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["contract_owner"])
+    }
+    /// The `contract_ownership_recipient` field.
+    pub fn contract_ownership_recipient(
+        &self,
+    ) -> ::std::result::Result<[u8; 20], ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(
+            &self.0["contract_ownership_recipient"],
+        )
+    }
+    /// The `royalty_recipient` field.
+    pub fn royalty_recipient(
+        &self,
+    ) -> ::std::result::Result<[u8; 20], ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["royalty_recipient"])
+    }
+    /// The `royalty_fee_bps` field.
+    pub fn royalty_fee_bps(
+        &self,
+    ) -> ::std::result::Result<u128, ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["royalty_fee_bps"])
+    }
+    /// The `base_uri` field.
+    pub fn base_uri(
+        &self,
+    ) -> ::std::result::Result<::std::string::String, ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["base_uri"])
+    }
+    /// The `token_uris` field.
+    pub fn token_uris(
+        &self,
+    ) -> ::std::result::Result<
+        ::std::collections::BTreeMap<
+            ::scilla_parser::bindings::U256,
+            ::std::string::String,
+        >,
+        ::scilla_parser::Error,
+    > {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["token_uris"])
+    }
+    /// The `token_owners` field.
+    pub fn token_owners(
+        &self,
+    ) -> ::std::result::Result<
+        ::std::collections::BTreeMap<::scilla_parser::bindings::U256, [u8; 20]>,
+        ::scilla_parser::Error,
+    > {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["token_owners"])
+    }
+    /// The `token_id_count` field.
+    pub fn token_id_count(
+        &self,
+    ) -> ::std::result::Result<::scilla_parser::bindings::U256, ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["token_id_count"])
+    }
+    /// The `total_supply` field.
+    pub fn total_supply(
+        &self,
+    ) -> ::std::result::Result<::scilla_parser::bindings::U256, ::scilla_parser::Error> {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["total_supply"])
+    }
+    /// The `balances` field.
+    pub fn balances(
+        &self,
+    ) -> ::std::result::Result<
+        ::std::collections::BTreeMap<[u8; 20], ::scilla_parser::bindings::U256>,
+        ::scilla_parser::Error,
+    > {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["balances"])
+    }
+    /// The `minters` field.
+    pub fn minters(
+        &self,
+    ) -> ::std::result::Result<
+        ::std::collections::BTreeMap<[u8; 20], bool>,
+        ::scilla_parser::Error,
+    > {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["minters"])
+    }
+    /// The `spenders` field.
+    pub fn spenders(
+        &self,
+    ) -> ::std::result::Result<
+        ::std::collections::BTreeMap<::scilla_parser::bindings::U256, [u8; 20]>,
+        ::scilla_parser::Error,
+    > {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["spenders"])
+    }
+    /// The `operators` field.
+    pub fn operators(
+        &self,
+    ) -> ::std::result::Result<
+        ::std::collections::BTreeMap<
+            [u8; 20],
+            ::std::collections::BTreeMap<[u8; 20], bool>,
+        >,
+        ::scilla_parser::Error,
+    > {
+        ::scilla_parser::bindings::ScillaValue::from_json(&self.0["operators"])
+    }
+}
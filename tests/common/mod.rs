@@ -0,0 +1,8 @@
+use std::path::PathBuf;
+
+use scilla_parser::Contract;
+
+/// Parse the contract `name` from `tests/contracts`.
+pub fn contract(name: &str) -> Contract {
+    Contract::from_path(&PathBuf::from(format!("tests/contracts/{name}.scilla"))).unwrap()
+}
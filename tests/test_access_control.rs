@@ -0,0 +1,143 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use scilla_parser::{Authorization, Caller, Contract, Error, Guard};
+
+use common::contract;
+
+fn names<'a>(authorizations: impl Iterator<Item = &'a Authorization>) -> Vec<&'a str> {
+    authorizations.map(|a| a.transition.as_str()).collect()
+}
+
+#[test]
+fn test_owner_procedures() {
+    // IsOwner compares its parameter with the `contract_owner` immutable parameter and throws.
+    let authorization = contract("ZRC2").authorization().unwrap();
+    assert_eq!(
+        authorization.of("Burn"),
+        Some(&Authorization {
+            transition: "Burn".to_string(),
+            guards: vec![Guard::Is {
+                caller: Caller::Sender,
+                owner: "contract_owner".to_string(),
+            }],
+            mutates_state: true,
+        })
+    );
+    assert_eq!(names(authorization.restricted()), vec!["Mint", "Burn"]);
+    // IsNotSender forbids a caller rather than requiring one.
+    assert_eq!(
+        names(authorization.unguarded()),
+        vec![
+            "IncreaseAllowance",
+            "DecreaseAllowance",
+            "Transfer",
+            "TransferFrom"
+        ]
+    );
+
+    let authorization = contract("ZRC2Interop").authorization().unwrap();
+    assert_eq!(
+        authorization.of("Mint").unwrap().to_string(),
+        "Mint: _origin == contract_owner"
+    );
+}
+
+#[test]
+fn test_fields_and_maps() {
+    let authorization = contract("ZRC6").authorization().unwrap();
+    // The owner is loaded from a field.
+    assert_eq!(
+        authorization.of("Pause").unwrap().to_string(),
+        "Pause: _sender == contract_owner"
+    );
+    // Through `forall`, MintToken and `exists minters[_sender]`.
+    assert_eq!(
+        authorization.of("BatchMint").unwrap().to_string(),
+        "BatchMint: _sender in minters"
+    );
+
+    let authorization = contract("multisig_wallet").authorization().unwrap();
+    assert_eq!(
+        authorization.of("SignTransaction").unwrap().to_string(),
+        "SignTransaction: _sender in owners"
+    );
+    assert_eq!(names(authorization.unguarded()), vec!["ExecuteTransaction"]);
+    assert_eq!(
+        authorization.of("AddFunds").unwrap().to_string(),
+        "AddFunds: anyone"
+    );
+
+    // The staging owner is stored in an optional field.
+    assert_eq!(
+        contract("stzil")
+            .authorization()
+            .unwrap()
+            .of("ClaimOwner")
+            .unwrap()
+            .to_string(),
+        "ClaimOwner: _sender == staging_owner_address"
+    );
+}
+
+#[test]
+fn test_guarded_branch() {
+    // HelloWorld only emits an event when the caller is not the owner.
+    let authorization = contract("HelloWorld").authorization().unwrap();
+    assert_eq!(
+        authorization.of("setHello").unwrap().to_string(),
+        "setHello: _sender == owner"
+    );
+    assert!(!authorization.of("getHello").unwrap().is_unguarded());
+
+    let contract = Contract::from_source(
+        "scilla_version 0
+        contract Registry (admin : ByStr20)
+        field names : Map ByStr20 String = Emp ByStr20 String
+        field count : Uint32 = Uint32 0
+        transition Register (name : String)
+          names[_sender] := name
+        end
+        transition Reset ()
+          is_admin = builtin eq admin _sender;
+          match is_admin with
+          | True =>
+          | False =>
+            throw
+          end;
+          zero = Uint32 0;
+          count := zero
+        end
+        transition ResetAfterWrite ()
+          zero = Uint32 0;
+          count := zero;
+          is_admin = builtin eq _sender admin;
+          match is_admin with
+          | True =>
+          | False =>
+            throw
+          end
+        end",
+    )
+    .unwrap();
+    let authorization = contract.authorization().unwrap();
+    assert_eq!(
+        authorization
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec![
+            "Register: anyone (mutates state)",
+            "Reset: _sender == admin",
+            "ResetAfterWrite: anyone (mutates state)"
+        ]
+    );
+}
+
+#[test]
+fn test_authorization_unavailable_without_source() {
+    let json = std::fs::read_to_string("tests/contract_info/ZRC2.json").unwrap();
+    let contract = Contract::from_contract_info_json(&json).unwrap();
+    assert!(matches!(contract.authorization(), Err(Error::NoSource)));
+}
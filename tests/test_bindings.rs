@@ -0,0 +1,132 @@
+use pretty_assertions::assert_eq;
+use std::collections::BTreeMap;
+
+use scilla_parser::bindings::{json, U256};
+
+#[allow(dead_code)]
+mod zrc2 {
+    include!("bindings/zrc2.rs");
+}
+
+#[allow(dead_code)]
+mod zrc6 {
+    include!("bindings/zrc6.rs");
+}
+
+const TOKEN: [u8; 20] = [0x1a; 20];
+const ALICE: [u8; 20] = [0xaa; 20];
+const BOB: [u8; 20] = [0xbb; 20];
+
+#[test]
+fn test_transition_call() {
+    let token = zrc2::FungibleToken::new(TOKEN);
+    let call = token.transfer(BOB, 1000);
+    assert_eq!(call.contract, TOKEN);
+    assert_eq!(
+        call.to_json(),
+        json!({
+            "_tag": "Transfer",
+            "params": [
+                {"vname": "to", "type": "ByStr20", "value": "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"},
+                {"vname": "amount", "type": "Uint128", "value": "1000"}
+            ]
+        })
+    );
+
+    let nft = zrc6::NonfungibleToken::new(TOKEN);
+    let call = nft.burn(U256::from(7));
+    assert_eq!(call.params[0].r#type, "Uint256");
+    assert_eq!(call.params[0].value, json!("7"));
+}
+
+#[test]
+fn test_init_params() {
+    let init = zrc2::FungibleTokenInit {
+        contract_owner: ALICE,
+        name: "Token".to_string(),
+        symbol: "TKN".to_string(),
+        decimals: 12,
+        init_supply: 1_000_000,
+    };
+    let json = init.to_json();
+    assert_eq!(
+        json[0],
+        json!({"vname": "_scilla_version", "type": "Uint32", "value": "0"})
+    );
+    assert_eq!(
+        json[4],
+        json!({"vname": "decimals", "type": "Uint32", "value": "12"})
+    );
+}
+
+#[test]
+fn test_state_accessors() {
+    let state = zrc2::FungibleTokenState(json!({
+        "_balance": "0",
+        "total_supply": "1000",
+        "balances": {
+            "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa": "600",
+            "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb": "400"
+        },
+        "allowances": {
+            "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa": {
+                "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb": "50"
+            }
+        }
+    }));
+    assert_eq!(state.total_supply().unwrap(), 1000);
+    assert_eq!(
+        state.balances().unwrap(),
+        BTreeMap::from([(ALICE, 600), (BOB, 400)])
+    );
+    assert_eq!(state.allowances().unwrap()[&ALICE][&BOB], 50);
+
+    let state = zrc2::FungibleTokenState(json!({
+        "balances": {"0xaé1": "600"}
+    }));
+    assert!(state.balances().is_err());
+}
+
+#[cfg(feature = "codegen")]
+#[test]
+fn test_generated_bindings_are_up_to_date() {
+    use scilla_parser::Contract;
+    use std::path::PathBuf;
+
+    for (contract, bindings) in [("ZRC2", "zrc2"), ("ZRC6", "zrc6")] {
+        let contract =
+            Contract::from_path(&PathBuf::from(format!("tests/contracts/{contract}.scilla")))
+                .unwrap();
+        let expected = std::fs::read_to_string(format!("tests/bindings/{bindings}.rs")).unwrap();
+        assert_eq!(contract.rust_bindings_string().unwrap(), expected);
+    }
+}
+
+#[cfg(feature = "codegen")]
+#[test]
+fn test_clashing_names_are_disambiguated() {
+    use scilla_parser::Contract;
+
+    let contract = Contract::from_source(
+        r#"scilla_version 0
+        contract Greeter (owner : ByStr20)
+        field welcomeMsg : String = ""
+        field welcome_msg : String = ""
+        transition New () end
+        transition setHello (msg : String) end
+        transition SetHello (new_msg : String, newMsg : String) end"#,
+    )
+    .unwrap();
+    let bindings = contract.rust_bindings_string().unwrap();
+    for item in [
+        "pub fn new(address: [u8; 20]) -> Self",
+        "pub fn new_2(&self) -> ",
+        "pub fn set_hello(\n",
+        "pub fn set_hello_2(\n",
+        "new_msg: ::std::string::String,\n        new_msg_2: ::std::string::String,\n",
+        "pub fn welcome_msg(\n",
+        "pub fn welcome_msg_2(\n",
+    ] {
+        assert!(bindings.contains(item), "{item} in\n{bindings}");
+    }
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use scilla_parser::{Contract, Error, NodeKind};
+
+use common::contract;
+
+#[test]
+fn test_ssnlist_call_graph() {
+    let contract = contract("ssnlist");
+    let graph = contract.call_graph().unwrap();
+    assert_eq!(
+        graph.callees("UpdateComm").collect::<Vec<_>>(),
+        vec![
+            "IsNotPaused",
+            "IsProxy",
+            "ThrowError",
+            "ValidateRate",
+            "ValidateChangeRate"
+        ]
+    );
+    assert_eq!(graph.node("IsAdmin").unwrap().kind, NodeKind::Procedure);
+    assert!(graph.callers("IsAdmin").any(|caller| caller == "AddSSN"));
+    // Every transition can fail through the ThrowError procedure.
+    assert_eq!(
+        graph.transitions_reaching("ThrowError").len(),
+        contract.transitions.len()
+    );
+
+    let unused: Vec<(&str, NodeKind)> = graph
+        .unused()
+        .into_iter()
+        .map(|n| (n.name.as_str(), n.kind))
+        .collect();
+    assert_eq!(
+        unused,
+        vec![
+            ("IsPaused", NodeKind::Procedure),
+            ("uint128_100", NodeKind::Library),
+            ("option_map_uint128_uint128_value", NodeKind::Library),
+            ("list_map_to_pair_with_constant", NodeKind::Library),
+        ]
+    );
+}
+
+#[test]
+fn test_library_uses() {
+    let contract = Contract::from_source(
+        "scilla_version 0
+        library Counter
+        let zero = Uint32 0
+        let one = Uint32 1
+        let two = Uint32 2
+        let increment = fun (n : Uint32) => builtin add n one
+        let unused = fun (n : Uint32) => builtin sub n two
+        contract Counter (initial : Uint32)
+        field count : Uint32 = zero
+        procedure Save (one : Uint32)
+          count := one
+        end
+        procedure Reset ()
+          count := zero
+        end
+        transition Increase ()
+          c <- count;
+          new_count = increment c;
+          Save new_count
+        end
+        transition Shadow ()
+          zero = Uint32 3;
+          count := zero
+        end",
+    )
+    .unwrap();
+    let graph = contract.call_graph().unwrap();
+    assert_eq!(graph.callees("Counter").collect::<Vec<_>>(), vec!["zero"]);
+    assert_eq!(
+        graph.callees("Increase").collect::<Vec<_>>(),
+        vec!["Save", "increment"]
+    );
+    assert_eq!(
+        graph.reachable_from("Increase"),
+        vec!["Save", "increment", "one"]
+    );
+    // The parameter of Save and the local binding of Shadow hide the library values.
+    assert_eq!(graph.callees("Save").count(), 0);
+    assert_eq!(graph.callees("Shadow").count(), 0);
+    assert_eq!(
+        graph
+            .unused()
+            .iter()
+            .map(|n| n.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Reset", "two", "unused"]
+    );
+    assert!(graph.transitions_reaching("Reset").is_empty());
+}
+
+#[test]
+fn test_export() {
+    let graph = contract("HelloWorld").call_graph().unwrap();
+    assert_eq!(
+        graph.to_dot(),
+        r#"digraph "HelloWorld" {
+  "HelloWorld" [shape=house];
+  "setHello" [shape=box];
+  "getHello" [shape=box];
+  "not_owner_code" [shape=note];
+  "set_hello_code" [shape=note];
+  "setHello" -> "not_owner_code";
+  "setHello" -> "set_hello_code";
+}
+"#
+    );
+    let json = graph.to_json();
+    assert_eq!(
+        json["nodes"][3],
+        serde_json::json!({"name": "not_owner_code", "kind": "library"})
+    );
+    assert_eq!(
+        json["edges"][1],
+        serde_json::json!({"from": "setHello", "to": "set_hello_code"})
+    );
+}
+
+#[test]
+fn test_call_graph_unavailable_without_source() {
+    let json = std::fs::read_to_string("tests/contract_info/ZRC2.json").unwrap();
+    let contract = Contract::from_contract_info_json(&json).unwrap();
+    assert!(matches!(contract.call_graph(), Err(Error::NoSource)));
+}
@@ -0,0 +1,222 @@
+#![cfg(feature = "cli")]
+
+use pretty_assertions::assert_eq;
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_scilla-parser"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = run(args);
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_inspect() {
+    assert_eq!(
+        stdout(&["inspect", "tests/contracts/HelloWorld.scilla"]),
+        "HelloWorld: 1 immutable parameters, 1 mutable fields, 2 transitions, 0 procedures, 2 events
+
+Immutable parameters:
+  owner : ByStr20
+
+Mutable fields:
+  welcome_msg : String
+
+Transitions:
+  setHello (msg : String)
+  getHello ()
+
+Events:
+  setHello() (code : Int32)
+  getHello() (msg : String)
+"
+    );
+}
+
+#[test]
+fn test_globs_and_directories() {
+    assert_eq!(
+        stdout(&[
+            "transitions",
+            "tests/contracts/Hello*.scilla",
+            "tests/contracts/chainid.scilla"
+        ]),
+        "==> tests/contracts/HelloWorld.scilla <==
+setHello (msg : String)
+getHello ()
+
+==> tests/contracts/chainid.scilla <==
+EventChainID ()
+"
+    );
+
+    let fields = stdout(&["fields", "tests/contracts"]);
+    assert_eq!(fields.matches("==> ").count(), 14);
+    let fields = stdout(&["fields", "tests/contract_info/*.json"]);
+    assert_eq!(fields.matches("==> ").count(), 14);
+
+    // Directories may hold init and state JSON files, which are not contract info.
+    let output = run(&["fields", "tests/contract_info"]);
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: tests/contract_info: no contract found\n"
+    );
+}
+
+#[test]
+fn test_json_and_types() {
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout(&["json", "tests/contract_info/ZRC2.json"])).unwrap();
+    assert_eq!(json["name"], "FungibleToken");
+    assert_eq!(json["fields"][1]["name"], "balances");
+    assert_eq!(json["fields"][1]["type"], "Map (ByStr20) (Uint128)");
+    assert_eq!(json["procedures"][0]["name"], "ThrowError");
+
+    assert_eq!(
+        stdout(&["types", "tests/contracts/ZRC2.scilla"]),
+        "type Error = | CodeIsSender | CodeInsufficientFunds | CodeInsufficientAllowance | CodeNotOwner
+type Unit = | Unit
+"
+    );
+}
+
+#[test]
+fn test_analyses_fail_without_source() {
+    for command in ["types", "typecheck"] {
+        let output = run(&[command, "tests/contract_info/ZRC2.json"]);
+        assert!(!output.status.success());
+        assert_eq!(
+            String::from_utf8(output.stderr).unwrap(),
+            "error: tests/contract_info/ZRC2.json: The contract was not parsed from its Scilla source\n"
+        );
+    }
+    assert!(stdout(&["inspect", "tests/contract_info/ZRC2.json"]).starts_with(
+        "FungibleToken: 5 immutable parameters, 3 mutable fields, 6 transitions, 6 procedures\n"
+    ));
+}
+
+#[test]
+fn test_errors() {
+    let output = run(&["events", "tests/contracts/Missing*.scilla"]);
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: tests/contracts/Missing*.scilla: no contract found\n"
+    );
+}
+
+#[test]
+fn test_diff() {
+    let output = run(&[
+        "diff",
+        "tests/contracts/ZRC2.scilla",
+        "tests/contracts/FungibleToken.scilla",
+    ]);
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "Breaking changes:
+  - transition `Mint` removed
+  - transition `Burn` removed
+Compatible changes:
+  - transition `TransferFailed` added
+"
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&stdout(&[
+        "diff",
+        "--json",
+        "tests/contracts/ZRC2.scilla",
+        "tests/contracts/ZRC2.scilla",
+    ]))
+    .unwrap();
+    assert_eq!(json, serde_json::json!({"breaking": false, "changes": []}));
+}
+
+#[test]
+fn test_effects() {
+    assert_eq!(
+        stdout(&["effects", "tests/contracts/HelloWorld.scilla"]),
+        "setHello: writes welcome_msg\ngetHello: reads welcome_msg\n"
+    );
+}
+
+#[test]
+fn test_call_graph() {
+    assert_eq!(
+        stdout(&["call-graph", "--unused", "tests/contracts/ZRC6.scilla"]),
+        "RequireExistingToken (procedure)\nRequireTokenOwner (procedure)\n"
+    );
+    assert!(stdout(&["call-graph", "tests/contracts/ZRC2.scilla"])
+        .contains("\"Transfer\" -> \"AuthorizedMoveIfSufficientBalance\";"));
+}
+
+#[test]
+fn test_access() {
+    assert_eq!(
+        stdout(&["access", "tests/contracts/HelloWorld.scilla"]),
+        "setHello: _sender == owner\ngetHello: anyone\n"
+    );
+}
+
+#[test]
+fn test_lint() {
+    let output = run(&[
+        "lint",
+        "--only",
+        "accept-without-record",
+        "tests/contracts/SendZil.scilla",
+    ]);
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "22:3: warning[accept-without-record]: `acceptZil` accepts funds but never uses `_amount`\n"
+    );
+    assert_eq!(
+        stdout(&[
+            "lint",
+            "--disable",
+            "magic-number",
+            "tests/contracts/HelloWorld.scilla"
+        ]),
+        ""
+    );
+}
+
+#[test]
+fn test_interactions() {
+    assert_eq!(
+        stdout(&[
+            "interactions",
+            "--paths",
+            "tests/contracts/HelloWorld.scilla"
+        ]),
+        "setHello: event\nsetHello: write welcome_msg -> event\ngetHello: event\n"
+    );
+    let output = run(&["interactions", "tests/contracts/multisig_wallet.scilla"]);
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().count(), 3);
+}
+
+#[test]
+fn test_typecheck() {
+    assert_eq!(stdout(&["typecheck", "tests/contracts/ZRC2.scilla"]), "");
+    let output = run(&["typecheck", "tests/contracts/"]);
+    assert!(output.status.success());
+    assert!(!String::from_utf8(output.stdout).unwrap().contains(": "));
+}
+
+#[test]
+fn test_gas() {
+    assert_eq!(
+        stdout(&["gas", "tests/contracts/HelloWorld.scilla"]),
+        "setHello: at least 51\ngetHello: at least 24\n"
+    );
+}
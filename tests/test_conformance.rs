@@ -0,0 +1,134 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use scilla_parser::{check_conformance, Contract, Field, FieldList, Standard, Type, Violation};
+
+use common::contract;
+
+/// The contract `name` with its source rewritten by `replacements`.
+fn modified(name: &str, replacements: &[(&str, &str)]) -> Contract {
+    let path = format!("tests/contracts/{name}.scilla");
+    let mut source = std::fs::read_to_string(path).unwrap().replace("\r\n", "\n");
+    for (from, to) in replacements {
+        assert!(source.contains(from), "{from}");
+        source = source.replace(from, to);
+    }
+    Contract::from_source(&source).unwrap()
+}
+
+#[test]
+fn test_reference_contracts_conform() {
+    for (name, standard) in [
+        ("ZRC1", Standard::Zrc1),
+        ("ZRC2", Standard::Zrc2),
+        ("ZRC2Interop", Standard::Zrc2),
+        ("FungibleToken", Standard::Zrc2),
+        ("ZRC6", Standard::Zrc6),
+    ] {
+        assert_eq!(
+            check_conformance(&contract(name), standard).0,
+            vec![],
+            "{name} {standard}"
+        );
+    }
+}
+
+#[test]
+fn test_contract_info_conforms() {
+    // Events and callbacks are unknown without the source, only the interface is checked.
+    let json = std::fs::read_to_string("tests/contract_info/ZRC6.json").unwrap();
+    let contract = Contract::from_contract_info_json(&json).unwrap();
+    assert!(check_conformance(&contract, Standard::Zrc6).is_empty());
+}
+
+#[test]
+fn test_zrc4_multisig_wallet() {
+    assert_eq!(
+        check_conformance(&contract("multisig_wallet"), Standard::Zrc4).0,
+        vec![Violation::MissingTransition {
+            name: "SubmitTransaction".to_string(),
+            params: FieldList(vec![
+                Field::new("recipient", Type::ByStr(20)),
+                Field::new("amount", Type::Uint128),
+                Field::new("tag", Type::String),
+            ]),
+        }]
+    );
+
+    let wallet = modified(
+        "multisig_wallet",
+        &[(
+            "transition SubmitNativeTransaction",
+            "transition SubmitTransaction",
+        )],
+    );
+    assert!(check_conformance(&wallet, Standard::Zrc4).is_empty());
+}
+
+#[test]
+fn test_address_types_satisfy_bystr20() {
+    let token = modified(
+        "ZRC2",
+        &[(
+            "transition Transfer(to: ByStr20, amount: Uint128)",
+            "transition Transfer(to: ByStr20 with end, amount: Uint128)",
+        )],
+    );
+    assert!(check_conformance(&token, Standard::Zrc2).is_empty());
+}
+
+#[test]
+fn test_set_values_are_left_to_the_contract() {
+    // ZRC-1 minters and operators are sets, whatever the type of their values.
+    let token = modified("ZRC1", &[("Dummy", "Unit")]);
+    assert!(check_conformance(&token, Standard::Zrc1).is_empty());
+}
+
+#[test]
+fn test_zrc2_violations() {
+    let token = modified(
+        "ZRC2",
+        &[
+            (
+                "transition Transfer(to: ByStr20, amount: Uint128)",
+                "transition Transfer(to: ByStr20, amount: Uint64)",
+            ),
+            (
+                "AuthorizedMoveIfSufficientBalance _sender to amount;\n  e = {_eventname : \"TransferSuccess\"",
+                "AuthorizedMoveIfSufficientBalance _sender to amount;\n  e = {_eventname : \"Transferred\"",
+            ),
+            ("field total_supply : Uint128", "field total_supply : Uint256"),
+            ("  decimals: Uint32,\n", ""),
+        ],
+    );
+    assert_eq!(
+        check_conformance(&token, Standard::Zrc2)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec![
+            "missing immutable parameter `decimals : Uint32`",
+            "field `total_supply` has type `Uint256` instead of `Uint128`",
+            "transition `Transfer` takes `(to : ByStr20, amount : Uint64)` instead of \
+             `(to : ByStr20, amount : Uint128)`",
+            "transition `Transfer` does not emit `TransferSuccess`",
+        ]
+    );
+}
+
+#[test]
+fn test_wrong_standard() {
+    let violations = check_conformance(&contract("ZRC6"), Standard::Zrc1);
+    assert!(violations.contains(&Violation::MissingTransition {
+        name: "Transfer".to_string(),
+        params: FieldList(vec![
+            Field::new("to", Type::ByStr(20)),
+            Field::new("token_id", Type::Uint256),
+        ]),
+    }));
+    assert!(violations.contains(&Violation::CallbackNotSent {
+        transition: "Mint".to_string(),
+        tag: "MintCallBack".to_string(),
+    }));
+}
@@ -0,0 +1,78 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Contract, Field, FieldList, Transition, TransitionList, Type};
+
+const FIXTURES: &[&str] = &[
+    "chainid",
+    "FungibleToken",
+    "HelloWorld",
+    "multisig_wallet",
+    "SendZil",
+    "SetGet",
+    "ssnlist",
+    "staking_proxy_v2",
+    "stzil",
+    "Timestamp",
+    "ZRC1",
+    "ZRC2",
+    "ZRC2Interop",
+    "ZRC6",
+];
+
+// The files in tests/contract_info were written for these tests from the fixture contracts; they
+// were not generated by `scilla-checker`.
+#[test]
+fn test_contract_info_matches_source() {
+    for name in FIXTURES {
+        let contract =
+            Contract::from_path(&PathBuf::from(format!("tests/contracts/{name}.scilla"))).unwrap();
+        let json = std::fs::read_to_string(format!("tests/contract_info/{name}.json")).unwrap();
+        let parsed = Contract::from_contract_info_json(&json).unwrap();
+        assert_eq!(parsed, contract, "{name}");
+        assert_eq!(parsed.procedures, contract.procedures, "{name}");
+    }
+}
+
+#[test]
+fn test_contract_info_from_checker_output() {
+    let json = r#"{
+        "cashflow_tags": {},
+        "contract_info": {
+            "scilla_major_version": "0",
+            "vname": "Wallet",
+            "params": [{"vname": "owner", "type": "ByStr20"}],
+            "fields": [],
+            "transitions": [
+                {"vname": "Pay", "params": [{"vname": "to", "type": "ByStr20 with end"}]}
+            ],
+            "procedures": [
+                {"vname": "Check", "params": [{"vname": "amounts", "type": "List (Uint128)"}]}
+            ],
+            "events": [],
+            "ADTs": []
+        },
+        "warnings": [],
+        "gas_remaining": "9999"
+    }"#;
+    let contract = Contract::from_contract_info_json(json).unwrap();
+    assert_eq!(contract.name, "Wallet");
+    assert_eq!(
+        contract.transitions,
+        TransitionList(vec![Transition::new(
+            "Pay",
+            FieldList(vec![Field::new("to", Type::Address(None))])
+        )])
+    );
+    assert_eq!(
+        contract.procedures,
+        TransitionList(vec![Transition::new(
+            "Check",
+            FieldList(vec![Field::new(
+                "amounts",
+                Type::List(Box::new(Type::Uint128))
+            )])
+        )])
+    );
+    assert!(Contract::from_contract_info_json(r#"{"params": []}"#).is_err());
+}
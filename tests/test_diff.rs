@@ -0,0 +1,173 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Change, Contract, Field, Scope, Type};
+
+const V2: &str = "tests/contracts/staking_proxy_v2.scilla";
+
+/// Staking proxy v2 with its source rewritten by `replacements`.
+fn upgraded(replacements: &[(&str, &str)]) -> Contract {
+    let mut source = std::fs::read_to_string(V2).unwrap().replace("\r\n", "\n");
+    for (from, to) in replacements {
+        assert!(source.contains(from), "{from}");
+        source = source.replace(from, to);
+    }
+    Contract::from_source(&source).unwrap()
+}
+
+#[test]
+fn test_same_interface() {
+    let v2 = Contract::from_path(&PathBuf::from(V2)).unwrap();
+    let diff = v2.diff(&upgraded(&[(
+        "let zero = Uint128 0",
+        "let zero = Uint128 1",
+    )]));
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "No interface change.\n");
+}
+
+#[test]
+fn test_transition_changes() {
+    let v2 = Contract::from_path(&PathBuf::from(V2)).unwrap();
+    let v3 = upgraded(&[
+        (
+            "transition UpgradeTo(newImplementation: ByStr20)",
+            "transition UpgradeTo(newImplementation: ByStr20 with contract end)",
+        ),
+        (
+            "transition ChangeProxyAdmin(newAdmin: ByStr20)",
+            "transition SetProxyAdmin(newAdmin: ByStr20)",
+        ),
+        ("transition ClaimProxyAdmin()", "transition ClaimAdmin()"),
+        (
+            "transition AddSSNNonStaking(ssnaddr: ByStr20, name: String,",
+            "transition AddSSNNonStaking(name: String, ssnaddr: ByStr20,",
+        ),
+        (
+            "transition ChangeMinStake(input_minstake: Uint128)",
+            "transition ChangeMinStake(input_minstake: Uint128, input_maxstake: Uint128)",
+        ),
+        (
+            "transition ChangeVerifier(input_verifier: ByStr20)",
+            "transition ChangeVerifier(input_verifier: ByStr20)\nend\n\
+             transition PauseStaking(paused: Bool)",
+        ),
+    ]);
+    let diff = v2.diff(&v3);
+    assert_eq!(
+        diff.0,
+        vec![
+            Change::TypeChanged {
+                scope: Scope::Transition("UpgradeTo".to_string()),
+                name: "newImplementation".to_string(),
+                from: Type::ByStr(20),
+                to: Type::Address(Some(Default::default())),
+            },
+            Change::TransitionRenamed {
+                from: "ChangeProxyAdmin".to_string(),
+                to: "SetProxyAdmin".to_string()
+            },
+            // Transitions without parameters are too alike to be reported as renamed.
+            Change::TransitionRemoved("ClaimProxyAdmin".to_string()),
+            Change::Reordered {
+                scope: Scope::Transition("AddSSNNonStaking".to_string()),
+                from: vec!["ssnaddr", "name", "urlraw", "urlapi", "comm"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+                to: vec!["name", "ssnaddr", "urlraw", "urlapi", "comm"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect(),
+            },
+            Change::Added(
+                Scope::Transition("ChangeMinStake".to_string()),
+                Field::new("input_maxstake", Type::Uint128)
+            ),
+            Change::TransitionAdded("ClaimAdmin".to_string()),
+            Change::TransitionAdded("PauseStaking".to_string()),
+        ]
+    );
+    assert!(diff.is_breaking());
+    assert_eq!(
+        diff.to_string(),
+        "Breaking changes:
+  - type of parameter `newImplementation` of `UpgradeTo` changed from `ByStr20` to `ByStr20 with contract end`
+  - transition `ChangeProxyAdmin` renamed to `SetProxyAdmin`
+  - transition `ClaimProxyAdmin` removed
+  - parameter `input_maxstake : Uint128` of `ChangeMinStake` added
+Compatible changes:
+  - parameters of `AddSSNNonStaking` reordered from (ssnaddr, name, urlraw, urlapi, comm) to (name, ssnaddr, urlraw, urlapi, comm)
+  - transition `ClaimAdmin` added
+  - transition `PauseStaking` added
+"
+    );
+}
+
+#[test]
+fn test_ambiguous_rename() {
+    // Both removed transitions take a single Uint128, so neither is reported as renamed.
+    let v2 = Contract::from_path(&PathBuf::from(V2)).unwrap();
+    let v3 = upgraded(&[
+        (
+            "transition ChangeMinStake(input_minstake: Uint128)",
+            "transition SetMinStake(input_minstake: Uint128)",
+        ),
+        (
+            "transition ChangeMinDelegStake(input_mindelegstake: Uint128)",
+            "transition SetMinDelegStake(input_minstake: Uint128)",
+        ),
+    ]);
+    assert_eq!(
+        v2.diff(&v3).to_string(),
+        "Breaking changes:
+  - transition `ChangeMinStake` removed
+  - transition `ChangeMinDelegStake` removed
+Compatible changes:
+  - transition `SetMinStake` added
+  - transition `SetMinDelegStake` added
+"
+    );
+}
+
+#[test]
+fn test_init_params_and_fields() {
+    let v2 = Contract::from_path(&PathBuf::from(V2)).unwrap();
+    let v3 = upgraded(&[
+        (
+            "init_admin: ByStr20\n)",
+            "init_admin: ByStr20,\n  init_owner: ByStr20\n)",
+        ),
+        (
+            "field stagingadmin: Option ByStr20 = None {ByStr20}",
+            "field stagingadmin: ByStr20 = init_admin\nfield paused: Bool = False",
+        ),
+        ("field admin: ByStr20 = init_admin\n", ""),
+    ]);
+    let diff = v2.diff(&v3);
+    assert_eq!(
+        diff.to_string(),
+        "Breaking changes:
+  - immutable parameter `init_owner : ByStr20` added
+  - field `admin : ByStr20` removed
+  - type of field `stagingadmin` changed from `Option ByStr20` to `ByStr20`
+Compatible changes:
+  - field `paused : Bool` added
+"
+    );
+
+    let json = diff.to_json();
+    assert_eq!(json["breaking"], true);
+    assert_eq!(
+        json["changes"][2],
+        serde_json::json!({
+            "kind": "type_changed",
+            "breaking": true,
+            "description": "type of field `stagingadmin` changed from `Option ByStr20` to `ByStr20`",
+            "scope": "fields",
+            "name": "stagingadmin",
+            "from": "Option (ByStr20)",
+            "to": "ByStr20",
+        })
+    );
+}
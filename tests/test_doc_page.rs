@@ -0,0 +1,54 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use scilla_parser::{Contract, Transition, TransitionList};
+
+use common::contract;
+
+#[test]
+fn test_markdown_pages() {
+    for name in ["HelloWorld", "ZRC2", "ZRC6"] {
+        let expected = std::fs::read_to_string(format!("tests/doc_page/{name}.md")).unwrap();
+        assert_eq!(contract(name).markdown(), expected, "{name}");
+    }
+}
+
+#[test]
+fn test_html_page() {
+    let expected = std::fs::read_to_string("tests/doc_page/HelloWorld.html").unwrap();
+    assert_eq!(contract("HelloWorld").html(), expected);
+}
+
+#[test]
+fn test_markdown_without_syntax_tree() {
+    // Contracts parsed from an S-expression have no procedures, events or docs.
+    let contract = Contract {
+        name: "ChainId".to_string(),
+        transitions: TransitionList(vec![Transition::new_without_param("EventChainID")]),
+        ..Default::default()
+    };
+    assert_eq!(
+        contract.markdown(),
+        "# ChainId
+
+`ChainId` has 0 immutable parameters, 0 mutable fields, 1 transition and 0 procedures.
+
+## Immutable parameters
+
+None.
+
+## Mutable fields
+
+None.
+
+## Transitions
+
+### EventChainID
+
+#### Parameters
+
+None.
+"
+    );
+}
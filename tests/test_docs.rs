@@ -0,0 +1,101 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use scilla_parser::{Contract, Docs, Error};
+
+use common::contract;
+
+#[test]
+fn test_zrc2_transition_docs() {
+    let contract = contract("ZRC2");
+    let docs = contract.docs("Transfer").unwrap().unwrap();
+    assert_eq!(
+        docs.dev.as_deref(),
+        Some(
+            "Moves an amount tokens from _sender to the recipient. Used by token_owner.\n\
+             Balance of recipient will increase. Balance of _sender will decrease."
+        )
+    );
+    assert_eq!(
+        docs.params,
+        vec![
+            (
+                "to".to_string(),
+                "Address of the recipient whose balance is increased.".to_string()
+            ),
+            (
+                "amount".to_string(),
+                "Amount of tokens to be sent.".to_string()
+            ),
+        ]
+    );
+    assert_eq!(docs.comments.len(), 4);
+
+    // Section banners separated by a blank line are not documentation.
+    assert_eq!(contract.docs("FungibleToken").unwrap(), None);
+    assert_eq!(
+        contract
+            .docs("Error")
+            .unwrap()
+            .map(|d| d.description.as_str()),
+        Some("Error events")
+    );
+}
+
+#[test]
+fn test_zrc6_field_and_requirement_docs() {
+    let contract = contract("ZRC6");
+    assert_eq!(
+        contract
+            .docs("royalty_fee_bps")
+            .unwrap()
+            .unwrap()
+            .description,
+        "Royalty fee BPS\ne.g. 1 = 0.01%, 10000 = 100%\nDefaults to 1000"
+    );
+
+    let docs = contract.docs("Burn").unwrap().unwrap();
+    assert_eq!(docs.description, "Destroys `token_id`.");
+    assert_eq!(
+        docs.param("token_id"),
+        Some("Unique ID of the NFT to be destroyed")
+    );
+    assert_eq!(
+        docs.tag("Requirements"),
+        Some("- The contract must not be paused. Otherwise, it must throw `PausedError`")
+    );
+}
+
+#[test]
+fn test_comments_inside_bodies_are_not_docs() {
+    let contract = Contract::from_source(
+        r#"scilla_version 0
+        contract Counter ()
+        field count : Uint32 = Uint32 0 (* trailing comment *)
+        (* The increment. *)
+
+        (* Increase the counter. *)
+        transition Increase ()
+          (* not documentation *)
+          c <- count
+        end"#,
+    )
+    .unwrap();
+    assert_eq!(contract.docs("count").unwrap(), None);
+    assert_eq!(
+        contract.docs("Increase").unwrap(),
+        Some(&Docs::from_comments(vec![
+            "(* Increase the counter. *)".to_string()
+        ]))
+    );
+}
+
+#[test]
+fn test_docs_unavailable_without_source() {
+    let contract = Contract {
+        name: "HelloWorld".to_string(),
+        ..Default::default()
+    };
+    assert!(matches!(contract.docs("HelloWorld"), Err(Error::NoSource)));
+}
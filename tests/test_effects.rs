@@ -0,0 +1,93 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use scilla_parser::{Contract, Effects, Error};
+
+use common::contract;
+
+fn strings(names: &[&str]) -> Vec<String> {
+    names.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_zrc2_effects() {
+    let effects = contract("ZRC2").effects().unwrap();
+    assert_eq!(
+        effects.of("TransferFrom"),
+        Some(&Effects {
+            name: "TransferFrom".to_string(),
+            reads: strings(&["allowances", "balances"]),
+            writes: vec![],
+            map_updates: strings(&["allowances", "balances"]),
+            deletes: vec![],
+        })
+    );
+    // Through the AuthorizedMint procedure.
+    assert_eq!(
+        effects.of("Mint").unwrap().to_string(),
+        "Mint: reads balances, total_supply; writes total_supply; updates balances"
+    );
+    assert!(effects.of("IsOwner").unwrap().is_read_only());
+
+    let total_supply: Vec<&str> = effects
+        .modifying("total_supply")
+        .map(|e| e.name.as_str())
+        .collect();
+    assert_eq!(
+        total_supply,
+        vec![
+            "AuthorizedMint",
+            "AuthorizedBurnIfSufficientBalance",
+            "Mint",
+            "Burn"
+        ]
+    );
+}
+
+#[test]
+fn test_zrc6_deletes() {
+    let effects = contract("ZRC6").effects().unwrap();
+    let burn = effects.of("Burn").unwrap();
+    assert_eq!(burn.deletes, strings(&["token_owners", "spenders"]));
+    assert!(burn.modifies("balances"));
+    assert!(!burn.modifies("operators"));
+    assert_eq!(
+        effects.of("Pause").unwrap().to_string(),
+        "Pause: reads is_paused, contract_owner; writes is_paused"
+    );
+}
+
+#[test]
+fn test_exists_is_a_read() {
+    let contract = Contract::from_source(
+        "scilla_version 0
+        contract Registry ()
+        field names : Map ByStr20 String = Emp ByStr20 String
+        transition Unregister ()
+          registered <- exists names[_sender];
+          match registered with
+          | True => delete names[_sender]
+          | False =>
+          end
+        end
+        transition Noop () end",
+    )
+    .unwrap();
+    let effects = contract.effects().unwrap();
+    assert_eq!(
+        effects.of("Unregister").unwrap().to_string(),
+        "Unregister: reads names; deletes from names"
+    );
+    assert_eq!(
+        effects.of("Noop").unwrap().to_string(),
+        "Noop: no field access"
+    );
+}
+
+#[test]
+fn test_effects_unavailable_without_source() {
+    let json = std::fs::read_to_string("tests/contract_info/ZRC2.json").unwrap();
+    let contract = Contract::from_contract_info_json(&json).unwrap();
+    assert!(matches!(contract.effects(), Err(Error::NoSource)));
+}
@@ -0,0 +1,54 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Contract, ErrorCode};
+
+#[test]
+fn test_zrc2_error_codes() {
+    let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    assert_eq!(
+        *contract.error_codes().unwrap(),
+        vec![
+            ErrorCode::new(
+                "CodeIsSender",
+                -1,
+                &["IsNotSender", "IncreaseAllowance", "DecreaseAllowance"]
+            ),
+            ErrorCode::new(
+                "CodeInsufficientFunds",
+                -2,
+                &[
+                    "AuthorizedBurnIfSufficientBalance",
+                    "AuthorizedMoveIfSufficientBalance",
+                    "Burn",
+                    "Transfer",
+                    "TransferFrom"
+                ]
+            ),
+            ErrorCode::new("CodeInsufficientAllowance", -3, &["TransferFrom"]),
+            ErrorCode::new("CodeNotOwner", -4, &["IsOwner", "Mint", "Burn"]),
+        ]
+    );
+}
+
+#[test]
+fn test_error_codes_thrown_by() {
+    let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC6.scilla")).unwrap();
+    let codes = contract.error_codes().unwrap();
+    assert_eq!(codes.len(), 18);
+    let thrown: Vec<(&str, i64)> = codes
+        .thrown_by("SetRoyaltyFeeBPS")
+        .map(|e| (e.name.as_str(), e.code))
+        .collect();
+    assert_eq!(
+        thrown,
+        vec![("NotContractOwnerError", -4), ("InvalidFeeBPSError", -15)]
+    );
+}
+
+#[test]
+fn test_no_error_codes() {
+    let contract =
+        Contract::from_path(&PathBuf::from("tests/contracts/HelloWorld.scilla")).unwrap();
+    assert!(contract.error_codes().unwrap().is_empty());
+}
@@ -0,0 +1,56 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Contract, Error, Event, InferredField, Type};
+
+#[test]
+fn test_hello_world_events() {
+    let contract =
+        Contract::from_path(&PathBuf::from("tests/contracts/HelloWorld.scilla")).unwrap();
+    assert_eq!(
+        *contract.events().unwrap(),
+        vec![
+            Event::new(
+                "setHello()",
+                vec![InferredField::new("code", Type::Int32)],
+                &["setHello"]
+            ),
+            Event::new(
+                "getHello()",
+                vec![InferredField::new("msg", Type::String)],
+                &["getHello"]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_zrc2_events() {
+    let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    let events = contract.events().unwrap();
+    let event = |name: &str| events.iter().find(|e| e.name == name).unwrap();
+
+    assert_eq!(
+        *event("TransferSuccess"),
+        Event::new(
+            "TransferSuccess",
+            vec![
+                InferredField::new("sender", Type::ByStr(20)),
+                InferredField::new("recipient", Type::ByStr(20)),
+                InferredField::new("amount", Type::Uint128),
+            ],
+            &["Transfer"]
+        )
+    );
+    assert_eq!(event("Minted").emitted_by, vec!["AuthorizedMint", "Mint"]);
+    assert_eq!(
+        event("Burnt").emitted_by,
+        vec!["AuthorizedBurnIfSufficientBalance", "Burn"]
+    );
+}
+
+#[test]
+fn test_events_unavailable_without_source() {
+    let contract = Contract::default();
+    assert!(matches!(contract.events(), Err(Error::NoSource)));
+}
@@ -0,0 +1,114 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Contract, Error, Linter, Rule};
+
+const MATCHES: &str = "scilla_version 0
+library Matches
+type Color = | Red | Green | Blue of Uint32
+let first =
+  fun (l : List Uint32) =>
+    match l with
+    | Nil => Uint32 0
+    | Cons a Nil => a
+    end
+let is_zero =
+  fun (n : Nat) =>
+    match n with
+    | Zero => True
+    end
+let both =
+  fun (p : Pair Bool Bool) =>
+    match p with
+    | Pair True _ => Uint32 0
+    | Pair _ False => Uint32 1
+    | Pair True True => Uint32 2
+    end
+contract Matches ()
+transition Paint (c : Option Color, n : Uint32)
+  match c with
+  | Some Red =>
+  | Some (Blue _) =>
+  end;
+  match c with
+  | _ =>
+  | Some a =>
+  | None =>
+  end;
+  match n with
+  | x =>
+  end
+end
+";
+
+#[test]
+fn test_missing_patterns() {
+    let contract = Contract::from_source(MATCHES).unwrap();
+    let issues: Vec<String> = contract
+        .match_issues()
+        .unwrap()
+        .non_exhaustive()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            "match on `l` is not exhaustive, missing Cons _ (Cons _ _)",
+            "match on `n` is not exhaustive, missing Succ _",
+            "match on `p` is not exhaustive, missing Pair False True",
+            "match on `c` is not exhaustive, missing Some Green, None",
+        ]
+    );
+    // Non-exhaustive matches are type errors.
+    assert_eq!(contract.type_check().unwrap().len(), 4);
+}
+
+#[test]
+fn test_unreachable_arms() {
+    let contract = Contract::from_source(MATCHES).unwrap();
+    let issues: Vec<String> = contract
+        .match_issues()
+        .unwrap()
+        .unreachable_arms()
+        .map(|issue| format!("{}: {issue}", issue.span().line))
+        .collect();
+    assert_eq!(
+        issues,
+        vec![
+            "17: arm `Pair True True` of the match on `p` is unreachable",
+            "28: arm `Some a` of the match on `c` is unreachable",
+            "28: arm `None` of the match on `c` is unreachable",
+        ]
+    );
+
+    let diagnostics = Linter::empty()
+        .enable(Rule::UnreachableArm)
+        .lint(&contract)
+        .unwrap();
+    let diagnostics: Vec<String> = diagnostics.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        diagnostics,
+        vec![
+            "17:5: warning[unreachable-arm]: arm `Pair True True` of the match on `p` is unreachable",
+            "28:3: warning[unreachable-arm]: arms `Some a`, `None` of the match on `c` are unreachable",
+        ]
+    );
+}
+
+#[test]
+fn test_fixtures() {
+    for entry in std::fs::read_dir("tests/contracts").unwrap() {
+        let path = entry.unwrap().path();
+        let contract = Contract::from_path(&PathBuf::from(&path)).unwrap();
+        assert_eq!(
+            *contract.match_issues().unwrap(),
+            vec![],
+            "{}",
+            path.display()
+        );
+    }
+
+    let json = std::fs::read_to_string("tests/contract_info/ZRC2.json").unwrap();
+    let contract = Contract::from_contract_info_json(&json).unwrap();
+    assert!(matches!(contract.match_issues(), Err(Error::NoSource)));
+}
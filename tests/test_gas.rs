@@ -0,0 +1,128 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use scilla_parser::{Contract, Error, GasBounds, GasEstimator, Type, Value};
+
+use common::contract;
+
+fn bounds(contract: &Contract) -> Vec<String> {
+    contract
+        .gas_bounds()
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn test_static_bounds() {
+    assert_eq!(
+        bounds(&contract("SetGet")),
+        vec![
+            "set_uint128: 32",
+            "emit: 78",
+            "set_string: at least 0",
+            "get_string: at least 52",
+            "set_address: 40",
+            "get_address: 93",
+            "set_option_bystr20: 0 to 40",
+        ]
+    );
+
+    // The library functions building the messages and events are followed.
+    let gas = contract("ZRC2").gas_bounds().unwrap();
+    assert_eq!(
+        gas.transition("Transfer").unwrap().gas,
+        Some(GasBounds {
+            min: 637,
+            max: Some(682)
+        })
+    );
+
+    // Iterating over a list parameter has no upper bound.
+    let gas = contract("ZRC6").gas_bounds().unwrap();
+    assert!(!gas
+        .transition("BatchBurn")
+        .unwrap()
+        .gas
+        .unwrap()
+        .is_bounded());
+}
+
+#[test]
+fn test_throwing_paths() {
+    let contract = Contract::from_source(
+        "scilla_version 0
+        contract Vault (owner : ByStr20)
+        field total : Uint128 = Uint128 0
+        transition Deposit ()
+          is_owner = builtin eq owner _sender;
+          match is_owner with
+          | True =>
+            accept;
+            total := _amount
+          | False =>
+            e = { _exception : \"NotOwner\" };
+            throw e
+          end
+        end
+        transition Close ()
+          throw
+        end",
+    )
+    .unwrap();
+    // The cost of the paths that throw is left out.
+    assert_eq!(
+        bounds(&contract),
+        vec!["Deposit: 39", "Close: always throws"]
+    );
+}
+
+#[test]
+fn test_concrete_inputs() {
+    let zrc6 = contract("ZRC6");
+    let ids: Vec<Value> = (1..=3)
+        .map(|id| Value::int(Type::Uint256, id).unwrap())
+        .collect();
+    let one = GasEstimator::new(&zrc6)
+        .with_param(
+            "token_id_list",
+            Value::list(Type::Uint256, ids[..1].to_vec()),
+        )
+        .estimate("BatchBurn")
+        .unwrap()
+        .unwrap();
+    let three = GasEstimator::new(&zrc6)
+        .with_param("token_id_list", Value::list(Type::Uint256, ids))
+        .estimate("BatchBurn")
+        .unwrap()
+        .unwrap();
+    assert!(one.is_bounded() && three.is_bounded());
+    assert!(three.min > one.min);
+
+    // The size of the fields is taken from the state.
+    let hello = contract("HelloWorld");
+    let gas = GasEstimator::new(&hello)
+        .with_state(&json!({ "welcome_msg": "Hello", "_balance": "0" }))
+        .unwrap()
+        .estimate("getHello")
+        .unwrap();
+    assert_eq!(gas, Some(GasBounds::exact(34)));
+
+    assert!(GasEstimator::new(&hello).estimate("Unknown").is_err());
+    assert!(GasEstimator::new(&hello)
+        .with_state(&json!({ "welcome_msg": 1 }))
+        .is_err());
+}
+
+#[test]
+fn test_no_module() {
+    let contract = Contract::default();
+    assert!(matches!(contract.gas_bounds(), Err(Error::NoSource)));
+    assert!(matches!(
+        GasEstimator::new(&contract).estimate("Transfer"),
+        Err(Error::NoSource)
+    ));
+}
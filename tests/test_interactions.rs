@@ -0,0 +1,162 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use scilla_parser::{Contract, Error, InteractionWarning, StepKind};
+
+use common::contract;
+
+fn warnings(contract: &Contract) -> Vec<String> {
+    contract
+        .interaction_warnings()
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn test_transition_orders() {
+    let orders = contract("ZRC2").transition_orders().unwrap();
+    let transfer_from = orders
+        .iter()
+        .find(|o| o.transition == "TransferFrom")
+        .unwrap();
+    assert_eq!(
+        transfer_from.to_string(),
+        "TransferFrom: write balances -> write balances -> event -> write allowances -> send\n"
+    );
+    // The balances are written by the AuthorizedMoveIfSufficientBalance procedure.
+    let step = &transfer_from.paths[0][0];
+    assert_eq!(step.kind, StepKind::Write("balances".to_string()));
+    assert_eq!(step.component, "AuthorizedMoveIfSufficientBalance");
+    assert!(orders.iter().all(|o| !o.truncated));
+
+    // Paths throwing an error are left out.
+    let orders = contract("HelloWorld").transition_orders().unwrap();
+    assert_eq!(
+        orders[0].to_string(),
+        "setHello: event\nsetHello: write welcome_msg -> event\n"
+    );
+
+    let orders = contract("ssnlist").transition_orders().unwrap();
+    let swap = orders
+        .iter()
+        .find(|o| o.transition == "ConfirmDelegatorSwap")
+        .unwrap();
+    assert!(swap.truncated);
+    assert_eq!(swap.paths.len(), 1024);
+}
+
+#[test]
+fn test_write_after_send() {
+    // ExecuteNativeTransaction sends the funds before DeleteTransaction deletes the transaction.
+    assert_eq!(
+        warnings(&contract("multisig_wallet")),
+        vec![
+            "`ExecuteTransaction` writes `transactions` at line 1346 after sending a message at line 1375",
+            "`ExecuteTransaction` writes `signatures` at line 1347 after sending a message at line 1375",
+            "`ExecuteTransaction` writes `signature_counts` at line 1348 after sending a message at line 1375",
+        ]
+    );
+    assert!(warnings(&contract("ZRC2")).is_empty());
+
+    let contract = Contract::from_source(
+        "scilla_version 0
+        contract Payroll ()
+        field paid : Uint32 = Uint32 0
+        procedure Pay (to : ByStr20)
+          msg = {_tag : \"\"; _recipient : to; _amount : Uint128 0};
+          nil = Nil {Message};
+          msgs = Cons {Message} msg nil;
+          send msgs;
+          one = Uint32 1;
+          paid := one
+        end
+        transition PayAll (employees : List ByStr20)
+          forall employees Pay
+        end
+        transition PayOrFail (to : ByStr20, ok : Bool)
+          Pay to;
+          match ok with
+          | True =>
+          | False => throw
+          end
+        end
+        transition Refund (to : ByStr20)
+          msg = {_tag : \"\"; _recipient : to; _amount : Uint128 0};
+          nil = Nil {Message};
+          msgs = Cons {Message} msg nil;
+          send msgs;
+          throw
+        end
+        transition Reset ()
+          zero = Uint32 0;
+          paid := zero
+        end",
+    )
+    .unwrap();
+    let list = contract.interaction_warnings().unwrap();
+    // The write of a procedure follows its send, and the send of the previous iteration.
+    assert_eq!(list.of("PayAll").count(), 1);
+    assert!(matches!(
+        list.of("PayOrFail").next(),
+        Some(InteractionWarning::WriteAfterSend { field, write, .. })
+            if field == "paid" && write.component == "Pay"
+    ));
+    assert_eq!(list.of("Refund").count(), 0);
+    assert_eq!(list.of("Reset").count(), 0);
+}
+
+#[test]
+fn test_callbacks() {
+    let callbacks: Vec<String> = warnings(&contract("stzil"))
+        .into_iter()
+        .filter(|w| w.starts_with("callback"))
+        .collect();
+    assert_eq!(
+        callbacks,
+        vec![
+            "callback `DelegateStakeSuccessCallBack` of `PerformAutoRestake` reads intermediate state written by it: tmp_stake_delegate_amount, tmp_delegator",
+            "callback `DelegateStakeSuccessCallBack` of `DelegateStake` reads intermediate state written by it: tmp_stake_delegate_amount, tmp_delegator",
+            "callback `DelegateStakeSuccessCallBack` of `DelegateStakeWithReferral` reads intermediate state written by it: tmp_stake_delegate_amount, tmp_delegator",
+            "callback `CompleteWithdrawalSuccessCallBack` of `ClaimWithdrawal` reads intermediate state written by it: tmp_complete_withdrawal_available",
+        ]
+    );
+
+    // A message to `_this_address` calls back the transition named by its tag.
+    let contract = Contract::from_source(
+        "scilla_version 0
+        contract Batch ()
+        field step : Uint32 = Uint32 0
+        field log : String = \"\"
+        transition Start ()
+          one = Uint32 1;
+          step := one;
+          msg = {_tag : \"Continue\"; _recipient : _this_address; _amount : Uint128 0};
+          nil = Nil {Message};
+          msgs = Cons {Message} msg nil;
+          send msgs
+        end
+        transition Continue ()
+          s <- step;
+          l <- log
+        end",
+    )
+    .unwrap();
+    assert_eq!(
+        warnings(&contract),
+        vec!["callback `Continue` of `Start` reads intermediate state written by it: step"]
+    );
+}
+
+#[test]
+fn test_interactions_unavailable_without_source() {
+    let json = std::fs::read_to_string("tests/contract_info/ZRC2.json").unwrap();
+    let contract = Contract::from_contract_info_json(&json).unwrap();
+    assert!(matches!(contract.transition_orders(), Err(Error::NoSource)));
+    assert!(matches!(
+        contract.interaction_warnings(),
+        Err(Error::NoSource)
+    ));
+}
@@ -0,0 +1,248 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use scilla_parser::{Contract, Error, Interpreter, Type, Value};
+
+use common::contract;
+
+fn uint128(n: u128) -> Value {
+    Value::int(Type::Uint128, n).unwrap()
+}
+
+fn eval(src: &str) -> String {
+    Interpreter::default().eval(src).unwrap().to_string()
+}
+
+fn runtime_error(result: Result<Value, Error>) -> String {
+    match result {
+        Err(Error::RuntimeError(message)) => message,
+        other => panic!("expected a runtime error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_library_functions() {
+    let zrc2 = contract("ZRC2");
+    let interpreter = Interpreter::new(&zrc2).unwrap();
+    assert_eq!(interpreter.value("zero").unwrap(), uint128(0));
+
+    let error = Value::Adt {
+        constructor: "CodeInsufficientFunds".to_string(),
+        type_args: vec![],
+        args: vec![],
+    };
+    assert_eq!(
+        interpreter.call("make_error", &[error]).unwrap(),
+        Value::Message(vec![
+            ("_exception".to_string(), Value::String("Error".to_string())),
+            ("code".to_string(), Value::int(Type::Int32, -2).unwrap()),
+        ])
+    );
+
+    let msg =
+        |tag: &str| Value::Message(vec![("_tag".to_string(), Value::String(tag.to_string()))]);
+    let msgs = interpreter.call("two_msgs", &[msg("A"), msg("B")]).unwrap();
+    assert_eq!(
+        msgs.to_string(),
+        r#"(Cons {_tag : "A"} (Cons {_tag : "B"} Nil))"#
+    );
+
+    let some = Value::option(Type::Uint128, Some(uint128(7)));
+    assert_eq!(interpreter.call("get_val", &[some]).unwrap(), uint128(7));
+    let none = Value::option(Type::Uint128, None);
+    assert_eq!(interpreter.call("get_val", &[none]).unwrap(), uint128(0));
+}
+
+#[test]
+fn test_integer_semantics() {
+    let stzil = contract("stzil");
+    let interpreter = Interpreter::new(&stzil).unwrap();
+    // The intermediate product does not fit into 128 bits, but the result does.
+    let max = uint128(u128::MAX);
+    assert_eq!(
+        interpreter
+            .call("muldiv", &[max.clone(), uint128(3), uint128(4)])
+            .unwrap(),
+        uint128(u128::MAX / 4 * 3 + 2)
+    );
+    assert_eq!(
+        runtime_error(interpreter.call("muldiv", &[max, uint128(2), uint128(1)])),
+        "integer overflow in `mul` of Uint128"
+    );
+
+    let interpreter = Interpreter::default();
+    assert_eq!(
+        runtime_error(interpreter.eval("let a = Uint32 0 in let b = Uint32 1 in builtin sub a b")),
+        "integer overflow in `sub` of Uint32"
+    );
+    assert_eq!(
+        runtime_error(interpreter.eval("let a = Int32 1 in let b = Int32 0 in builtin div a b")),
+        "division by zero in `div`"
+    );
+    assert_eq!(
+        runtime_error(
+            interpreter.eval("let a = Uint256 2 in let b = Uint32 256 in builtin pow a b")
+        ),
+        "integer overflow in `pow` of Uint256"
+    );
+    assert_eq!(
+        eval("let a = Int32 -7 in let b = Int32 2 in builtin div a b"),
+        "Int32 -3"
+    );
+    assert_eq!(
+        eval("let a = Int32 -7 in let b = Int32 2 in builtin rem a b"),
+        "Int32 -1"
+    );
+    assert_eq!(eval("let a = Int64 -1 in builtin to_uint32 a"), "None");
+    assert_eq!(
+        eval("let a = \"42\" in builtin to_uint128 a"),
+        "(Some Uint128 42)"
+    );
+}
+
+#[test]
+fn test_builtins() {
+    assert_eq!(
+        eval("let a = \"hello \" in let b = \"world\" in builtin concat a b"),
+        r#""hello world""#
+    );
+    assert_eq!(
+        eval("let s = \"hello\" in let a = Uint32 1 in let b = Uint32 3 in builtin substr s a b"),
+        r#""ell""#
+    );
+    assert_eq!(
+        eval("let s = \"hello\" in builtin sha256hash s"),
+        "0x2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    );
+    assert_eq!(
+        eval("let s = \"hello\" in builtin keccak256hash s"),
+        "0x1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8"
+    );
+    assert_eq!(
+        eval(
+            "let m = Emp String Uint128 in
+            let k = \"alice\" in
+            let v = Uint128 5 in
+            let m = builtin put m k v in
+            let v = Uint128 6 in
+            let m = builtin put m k v in
+            builtin get m k"
+        ),
+        "(Some Uint128 6)"
+    );
+    assert_eq!(
+        eval(
+            "let b = BNum 10 in let n = Uint128 5 in let c = builtin badd b n in builtin bsub c b"
+        ),
+        "Int256 5"
+    );
+    assert_eq!(
+        runtime_error(Interpreter::default().eval("let a = 0x12 in builtin schnorr_get_address a")),
+        "builtin `schnorr_get_address` is not supported"
+    );
+}
+
+#[test]
+fn test_standard_library() {
+    let list = Value::list(
+        Type::Uint32,
+        (1..=4)
+            .map(|n| Value::int(Type::Uint32, n).unwrap())
+            .collect(),
+    );
+    let interpreter = Interpreter::default().with_var("l", list);
+    let eval = |src| interpreter.eval(src).unwrap().to_string();
+    assert_eq!(eval("let f = @list_length Uint32 in f l"), "Uint32 4");
+    assert_eq!(
+        eval("let f = @list_reverse Uint32 in f l"),
+        "(Cons Uint32 4 (Cons Uint32 3 (Cons Uint32 2 (Cons Uint32 1 Nil))))"
+    );
+    assert_eq!(
+        eval(
+            "let f = @list_map Uint32 Uint32 in
+            let double = fun (x : Uint32) => builtin add x x in
+            let l = f double l in
+            let nth = @list_nth Uint32 in
+            let i = Uint32 3 in
+            nth i l"
+        ),
+        "(Some Uint32 8)"
+    );
+    assert_eq!(
+        eval(
+            "let f = @list_filter Uint32 in
+            let small = fun (x : Uint32) => let two = Uint32 3 in builtin lt x two in
+            f small l"
+        ),
+        "(Cons Uint32 1 (Cons Uint32 2 Nil))"
+    );
+    assert_eq!(
+        eval(
+            "let r = @list_reverse Uint32 in
+            let s = @list_sort Uint32 in
+            let e = @list_eq Uint32 in
+            let reversed = r l in
+            let sorted = s uint32_lt reversed in
+            e uint32_eq sorted l"
+        ),
+        "True"
+    );
+    assert_eq!(
+        eval("let n = Uint32 3 in let n = builtin to_nat n in nat_to_int n"),
+        "Uint32 3"
+    );
+    assert_eq!(
+        eval("let a = True in let b = False in let c = andb a b in orb c a"),
+        "True"
+    );
+}
+
+#[test]
+fn test_errors() {
+    let interpreter = Interpreter::default();
+    assert_eq!(
+        runtime_error(interpreter.eval("builtin add x y")),
+        "undefined variable `x`"
+    );
+    assert_eq!(
+        runtime_error(interpreter.eval("fun (x : Uint32) => x")),
+        "the result is a function"
+    );
+    assert!(matches!(
+        interpreter.eval("let x ="),
+        Err(Error::SyntaxError { .. })
+    ));
+    assert!(Interpreter::new(&Contract::default()).is_err());
+}
+
+#[test]
+fn test_limits() {
+    let nat = |n: u32| format!("let n = Uint32 {n} in let n = builtin to_nat n in");
+    assert_eq!(eval(&format!("{} nat_to_int n", nat(1000))), "Uint32 1000");
+    assert_eq!(
+        runtime_error(Interpreter::default().eval(&format!("{} Uint32 0", nat(1001)))),
+        "`to_nat` of Uint32 1001 is too large"
+    );
+
+    let sum = "let f = fun (acc : Uint32) => fun (x : Uint32) => fun (rest : Uint32 -> Uint32) =>
+          let acc = builtin add acc x in rest acc in
+        let fold = @list_foldk Uint32 Uint32 in
+        let zero = Uint32 0 in
+        fold f zero l";
+    let list = |n: u32| {
+        Value::list(
+            Type::Uint32,
+            (0..n)
+                .map(|_| Value::int(Type::Uint32, 1).unwrap())
+                .collect(),
+        )
+    };
+    let interpreter = Interpreter::default().with_var("l", list(1000));
+    assert_eq!(
+        runtime_error(interpreter.eval(sum)),
+        "too many nested function applications"
+    );
+    let interpreter = Interpreter::default().with_var("l", list(10));
+    assert_eq!(interpreter.eval(sum).unwrap().to_string(), "Uint32 10");
+}
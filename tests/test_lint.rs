@@ -0,0 +1,144 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Contract, Error, Linter, Rule, Severity};
+
+const VAULT: &str = "scilla_version 0
+library Vault
+let one = Uint128 1
+let unused_value = Uint128 5
+let one_msg = fun (msg : Message) =>
+  let nil = Nil {Message} in Cons {Message} msg nil
+
+contract Vault (owner : ByStr20)
+field deposits : Map ByStr20 Uint128 = Emp ByStr20 Uint128
+field total : Uint128 = Uint128 0
+field note : String = \"\"
+
+procedure Pay (recipient : ByStr20)
+  msg = {_tag : \"\"; _recipient : recipient; _amount : one};
+  msgs = one_msg msg;
+  send msgs
+end
+
+transition PayAll (recipients : List ByStr20)
+  forall recipients Pay
+end
+
+transition Deposit (memo : String)
+  accept;
+  fee = Uint128 42;
+  deposits[_sender] := fee
+end
+
+transition Withdraw (amount : Uint128)
+  msg = {_tag : \"\"; _recipient : _sender; _amount : amount};
+  msgs = one_msg msg;
+  send msgs;
+  t <- total;
+  one = Uint128 1;
+  total := t
+end
+
+transition Acknowledge (sender : ByStr20) end
+";
+
+fn lines(contract: &Contract, linter: &Linter) -> Vec<String> {
+    linter
+        .lint(contract)
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn test_rules() {
+    let contract = Contract::from_source(VAULT).unwrap();
+    assert_eq!(
+        lines(&contract, &Linter::default()),
+        vec![
+            "4:1: warning[unused-library-value]: library value `unused_value` is never used",
+            "9:1: warning[field-never-read]: field `deposits` is never read",
+            "11:1: warning[field-never-read]: field `note` is never read",
+            "16:3: warning[send-in-loop]: message sent for each element of `recipients` by `forall` in `PayAll`",
+            "23:21: warning[unused-parameter]: parameter `memo` of `Deposit` is never used",
+            "24:3: warning[accept-without-record]: `Deposit` accepts funds but never uses `_amount`",
+            "25:9: note[magic-number]: magic number `Uint128 42`, consider a named library value",
+            "32:3: warning[send-before-state-update]: message to `_sender` sent before `total` is changed",
+            "34:3: note[shadowed-binding]: `one` shadows a library entry",
+        ]
+    );
+}
+
+#[test]
+fn test_configuration() {
+    let contract = Contract::from_source(VAULT).unwrap();
+    let linter = Linter::empty()
+        .enable(Rule::UnusedParameter)
+        .with_severity(Rule::MagicNumber, Severity::Error);
+    assert!(!linter.is_enabled(Rule::FieldNeverRead));
+    let diagnostics = linter.lint(&contract).unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics.max_severity(), Some(Severity::Error));
+    assert_eq!(diagnostics.of(Rule::MagicNumber).count(), 1);
+
+    let linter = Linter::default()
+        .disable(Rule::FieldNeverRead)
+        .disable(Rule::ShadowedBinding);
+    let diagnostics = linter.lint(&contract).unwrap();
+    assert_eq!(diagnostics.len(), 6);
+    assert_eq!(diagnostics.of(Rule::FieldNeverRead).count(), 0);
+
+    assert_eq!("send-in-loop".parse::<Rule>().unwrap(), Rule::SendInLoop);
+    assert!("unknown".parse::<Rule>().is_err());
+    for rule in Rule::ALL {
+        assert_eq!(rule.name().parse::<Rule>().unwrap(), rule);
+    }
+}
+
+#[test]
+fn test_fixtures() {
+    let contract = |name: &str| {
+        Contract::from_path(&PathBuf::from(format!("tests/contracts/{name}.scilla"))).unwrap()
+    };
+    assert!(contract("HelloWorld").lint().unwrap().is_empty());
+
+    let diagnostics = contract("ssnlist").lint().unwrap();
+    let unused: Vec<&str> = diagnostics
+        .of(Rule::UnusedLibraryValue)
+        .map(|d| d.message.as_str())
+        .collect();
+    assert_eq!(
+        unused,
+        vec![
+            "library value `uint128_100` is never used",
+            "library value `option_map_uint128_uint128_value` is never used",
+            "library value `list_map_to_pair_with_constant` is never used",
+        ]
+    );
+    assert_eq!(
+        diagnostics
+            .of(Rule::AcceptWithoutRecord)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec!["1472:3: warning[accept-without-record]: `AddFunds` accepts funds but never uses `_amount`"]
+    );
+
+    // `Some bal` in AuthorizedMoveIfSufficientBalance hides the balance of the sender.
+    let diagnostics = contract("ZRC2").lint().unwrap();
+    assert_eq!(
+        diagnostics
+            .iter()
+            .map(|d| (d.rule, d.span.line))
+            .collect::<Vec<_>>(),
+        vec![(Rule::ShadowedBinding, 149)]
+    );
+}
+
+#[test]
+fn test_lint_unavailable_without_source() {
+    let json = std::fs::read_to_string("tests/contract_info/ZRC2.json").unwrap();
+    let contract = Contract::from_contract_info_json(&json).unwrap();
+    assert!(matches!(contract.lint(), Err(Error::NoSource)));
+}
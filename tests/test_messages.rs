@@ -0,0 +1,101 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Contract, InferredField, Message, Type};
+
+#[test]
+fn test_zrc2_messages() {
+    let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    let messages = contract.messages().unwrap();
+    let transfer: Vec<_> = messages.sent_by("Transfer").collect();
+    assert_eq!(
+        *transfer[0],
+        Message {
+            tag: Some("RecipientAcceptTransfer".to_string()),
+            recipient: "to".to_string(),
+            amount: "zero".to_string(),
+            params: vec![
+                InferredField::new("sender", Type::ByStr(20)),
+                InferredField::new("recipient", Type::ByStr(20)),
+                InferredField::new("amount", Type::Uint128),
+            ],
+            sent_by: vec!["Transfer".to_string()],
+        }
+    );
+    assert_eq!(transfer[1].recipient, "_sender");
+    assert_eq!(transfer[1].tag.as_deref(), Some("TransferSuccessCallBack"));
+}
+
+#[test]
+fn test_messages_built_by_library_functions() {
+    let contract = Contract::from_source(
+        r#"scilla_version 0
+        library Notifier
+
+        let notify =
+          fun (to : ByStr20) =>
+          fun (value : Uint32) =>
+            let msg = {_tag : "Notified"; _recipient : to; _amount : Uint128 0; value : value} in
+            let nil = Nil {Message} in
+            Cons {Message} msg nil
+
+        contract Notifier ()
+
+        procedure Notify (value : Uint32)
+          msgs = notify _sender value;
+          send msgs
+        end
+
+        transition Set (value : Uint32)
+          Notify value
+        end
+
+        transition Forward (to : ByStr20, tag : String)
+          msg = {_tag : tag; _recipient : to; _amount : Uint128 0};
+          msgs = one_msg msg;
+          send msgs
+        end"#,
+    )
+    .unwrap();
+
+    let messages = contract.messages().unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].tag.as_deref(), Some("Notified"));
+    assert_eq!(
+        messages[0].params,
+        vec![InferredField::new("value", Type::Uint32)]
+    );
+    assert_eq!(messages[0].sent_by, vec!["Notify", "Set"]);
+    assert_eq!(
+        messages[1],
+        Message {
+            tag: None,
+            recipient: "to".to_string(),
+            amount: "Uint128 0".to_string(),
+            params: vec![],
+            sent_by: vec!["Forward".to_string()],
+        }
+    );
+}
+
+#[test]
+fn test_counterparty_accepts_callbacks() {
+    let token = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    let receiver = Contract::from_source(
+        r#"scilla_version 0
+        contract Receiver ()
+        transition RecipientAcceptTransfer (sender : ByStr20 with end, recipient : ByStr20, amount : Uint128)
+        end
+        transition TransferSuccessCallBack (sender : ByStr20, recipient : ByStr20, amount : Uint64)
+        end"#,
+    )
+    .unwrap();
+
+    let messages = token.messages().unwrap();
+    let missing: Vec<_> = messages
+        .sent_by("Transfer")
+        .filter(|m| !receiver.accepts(m))
+        .filter_map(|m| m.tag.as_deref())
+        .collect();
+    assert_eq!(missing, vec!["TransferSuccessCallBack"]);
+}
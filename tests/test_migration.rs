@@ -0,0 +1,106 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Contract, FieldMigration, Migration, Type};
+
+const STZIL: &str = "tests/contracts/stzil.scilla";
+
+/// stZIL with its source rewritten by `replacements`.
+fn upgraded(replacements: &[(&str, &str)]) -> Contract {
+    let mut source = std::fs::read_to_string(STZIL)
+        .unwrap()
+        .replace("\r\n", "\n");
+    for (from, to) in replacements {
+        assert!(source.contains(from), "{from}");
+        source = source.replace(from, to);
+    }
+    Contract::from_source(&source).unwrap()
+}
+
+fn names(plan: &scilla_parser::MigrationPlan, migration: Migration) -> Vec<&str> {
+    plan.fields(migration).map(|f| f.name.as_str()).collect()
+}
+
+#[test]
+fn test_identical_state() {
+    let stzil = Contract::from_path(&PathBuf::from(STZIL)).unwrap();
+    let plan = stzil.migration_plan(&upgraded(&[]));
+    assert_eq!(plan.len(), stzil.fields.len());
+    assert_eq!(names(&plan, Migration::Identical).len(), plan.len());
+    assert!(!plan.needs_transformation());
+}
+
+#[test]
+fn test_migration_plan() {
+    let stzil = Contract::from_path(&PathBuf::from(STZIL)).unwrap();
+    let new = upgraded(&[
+        (
+            "field local_lastrewardcycle             : Uint32",
+            "field local_lastrewardcycle             : Uint64",
+        ),
+        (
+            "field buffer_drained_cycle: Map ByStr20 Uint32 = Emp ByStr20 Uint32",
+            "field buffer_drained_cycle: Map ByStr20 Int64 = Emp ByStr20 Int64",
+        ),
+        (
+            "field owner_address          : ByStr20",
+            "field owner_address          : ByStr20 with end",
+        ),
+        (
+            "field mindelegstake     : Uint128",
+            "field mindelegstake     : Uint64",
+        ),
+        (
+            "field tmp_bnum                          : BNum           = BNum 0\n",
+            "field is_paused_migration : Bool = False\n",
+        ),
+    ]);
+    let plan = stzil.migration_plan(&new);
+    assert_eq!(
+        plan.iter()
+            .filter(|f| f.migration != Migration::Identical)
+            .map(|f| (f.name.as_str(), f.migration))
+            .collect::<Vec<_>>(),
+        vec![
+            ("owner_address", Migration::Transform),
+            ("mindelegstake", Migration::Transform),
+            ("buffer_drained_cycle", Migration::Widened),
+            ("tmp_bnum", Migration::Dropped),
+            ("local_lastrewardcycle", Migration::Widened),
+            ("is_paused_migration", Migration::Added),
+        ]
+    );
+    assert!(plan.needs_transformation());
+    assert_eq!(
+        plan.fields(Migration::Added).next(),
+        Some(&FieldMigration {
+            name: "is_paused_migration".to_string(),
+            migration: Migration::Added,
+            from: None,
+            to: Some(Type::Bool),
+        })
+    );
+}
+
+#[test]
+fn test_library_types() {
+    let stzil = Contract::from_path(&PathBuf::from(STZIL)).unwrap();
+    let withdrawals = [
+        "withdrawal_pending",
+        "withdrawal_pending_of_delegator",
+        "withdrawal_unbonded",
+    ];
+
+    // A new constructor keeps the existing values valid.
+    let plan = stzil.migration_plan(&upgraded(&[(
+        "  | Withdrawal of Uint128 Uint128\n",
+        "  | Withdrawal of Uint128 Uint128\n  | Cancelled\n",
+    )]));
+    assert_eq!(names(&plan, Migration::Widened), withdrawals);
+
+    let plan = stzil.migration_plan(&upgraded(&[(
+        "  | Withdrawal of Uint128 Uint128\n",
+        "  | Withdrawal of Uint128 Uint128 BNum\n",
+    )]));
+    assert_eq!(names(&plan, Migration::Transform), withdrawals);
+}
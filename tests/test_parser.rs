@@ -30,7 +30,8 @@ fn test_chain_id_contract_parse() {
             transitions: TransitionList(vec![Transition::new(
                 "EventChainID",
                 FieldList::default()
-            )])
+            )]),
+            ..Default::default()
         }
     );
 }
@@ -49,7 +50,8 @@ fn test_hello_world_contract_parse() {
             transitions: TransitionList(vec![
                 Transition::new("setHello", FieldList(vec![Field::new("msg", Type::String)])),
                 Transition::new_without_param("getHello")
-            ])
+            ]),
+            ..Default::default()
         }
     );
 }
@@ -111,7 +113,8 @@ fn test_send_zil_contract_parse() {
                         Field::new("value", Type::Uint256)
                     ])
                 ),
-            ])
+            ]),
+            ..Default::default()
         }
     );
 }
@@ -130,7 +133,8 @@ fn test_timestamp_contract_parse() {
             transitions: TransitionList(vec![Transition::new(
                 "EventTimestamp",
                 FieldList(vec![Field::new("bnum", Type::BNum)])
-            )])
+            )]),
+            ..Default::default()
         }
     );
 }
@@ -204,7 +208,8 @@ fn test_fungible_token_parse() {
                         Field::new("amount", Type::Uint128)
                     ])
                 ),
-            ])
+            ]),
+            ..Default::default()
         }
     );
 }
@@ -501,7 +506,8 @@ fn test_staking_proxy_v2_parse() {
                     "ChangeTotalStakeAmount",
                     FieldList(vec![Field::new("input_totalstakeamount", Type::Uint128)])
                 ),
-            ])
+            ]),
+            ..Default::default()
         }
     );
 }
@@ -740,7 +746,8 @@ fn test_stzil_contract_parse() {
                         Field::new("amount", Type::Uint128)
                     ],)
                 ),
-            ])
+            ]),
+            ..Default::default()
         }
     )
 }
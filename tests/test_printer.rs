@@ -0,0 +1,131 @@
+use pretty_assertions::assert_eq;
+use std::error::Error;
+
+use scilla_parser::{
+    ast::{Expr, ExprKind, LibEntry, Module, Span, Stmt, StmtKind},
+    parser::parse_module,
+    printer::PrettyPrinter,
+};
+
+fn clear_expr(expr: &mut Expr) {
+    expr.span = Span::default();
+    match &mut expr.kind {
+        ExprKind::Message(entries) => entries.iter_mut().for_each(|e| clear_expr(&mut e.value)),
+        ExprKind::Let { value, body, .. } => {
+            clear_expr(value);
+            clear_expr(body);
+        }
+        ExprKind::Fun { body, .. } | ExprKind::TFun { body, .. } => clear_expr(body),
+        ExprKind::Match { arms, .. } => arms.iter_mut().for_each(|(_, e)| clear_expr(e)),
+        _ => {}
+    }
+}
+
+fn clear_stmt(stmt: &mut Stmt) {
+    stmt.span = Span::default();
+    match &mut stmt.kind {
+        StmtKind::Bind { expr, .. } => clear_expr(expr),
+        StmtKind::Match { arms, .. } => arms
+            .iter_mut()
+            .for_each(|(_, stmts)| stmts.iter_mut().for_each(clear_stmt)),
+        _ => {}
+    }
+}
+
+/// Reset all spans so that modules parsed from differently formatted sources can be compared.
+fn clear_spans(module: &mut Module) {
+    module
+        .imports
+        .iter_mut()
+        .for_each(|i| i.span = Span::default());
+    if let Some(library) = &mut module.library {
+        library.span = Span::default();
+        for entry in &mut library.entries {
+            match entry {
+                LibEntry::Let { expr, span, .. } => {
+                    clear_expr(expr);
+                    *span = Span::default();
+                }
+                LibEntry::Type { span, .. } => *span = Span::default(),
+            }
+        }
+    }
+    let contract = &mut module.contract;
+    contract.span = Span::default();
+    contract
+        .params
+        .iter_mut()
+        .for_each(|p| p.span = Span::default());
+    contract.constraint.iter_mut().for_each(clear_expr);
+    for field in &mut contract.fields {
+        field.span = Span::default();
+        clear_expr(&mut field.init);
+    }
+    for component in &mut contract.components {
+        component.span = Span::default();
+        component
+            .params
+            .iter_mut()
+            .for_each(|p| p.span = Span::default());
+        component.body.iter_mut().for_each(clear_stmt);
+    }
+}
+
+#[test]
+fn test_parse_print_parse() -> Result<(), Box<dyn Error>> {
+    for entry in std::fs::read_dir("tests/contracts")? {
+        let path = entry?.path();
+        let mut expected = parse_module(&std::fs::read_to_string(&path)?)?;
+        clear_spans(&mut expected);
+
+        for indent in [2, 4] {
+            let printed = PrettyPrinter::default()
+                .with_indent(indent)
+                .print_module(&expected);
+            let mut actual = parse_module(&printed)
+                .map_err(|e| format!("{}: {e}\n{printed}", path.display()))?;
+            clear_spans(&mut actual);
+            assert_eq!(actual, expected, "{}", path.display());
+            assert_eq!(actual.to_string(), expected.to_string());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_print_modified_module() {
+    let mut module = parse_module(
+        r#"scilla_version 0
+        library Counter
+        let one = Uint32 1
+        contract Counter ()
+        field count : Uint32 = Uint32 0
+        transition Increase ()
+          c <- count; c = builtin add c one; count := c
+        end"#,
+    )
+    .unwrap();
+    module.contract.components[0].name = "Increment".to_string();
+
+    assert_eq!(
+        PrettyPrinter::default()
+            .with_indent(4)
+            .print_module(&module),
+        "scilla_version 0
+
+library Counter
+
+let one = Uint32 1
+
+contract Counter ()
+
+field count : Uint32 = Uint32 0
+
+transition Increment ()
+    c <- count;
+    c = builtin add c one;
+    count := c
+end
+"
+    );
+}
@@ -0,0 +1,190 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Contract, DecodedValue, Error, Receipt, Type, Value};
+
+const TRANSFER_RECEIPT: &str = r#"{
+    "ID": "5f3c4b0e8ae1c1b6dd84e2b54a2e0e4cbe1ea2cb2e0b1c0fd0c3f0c3a5e5d1c1",
+    "receipt": {
+        "accepted": false,
+        "cumulative_gas": "1003",
+        "epoch_num": "3028",
+        "event_logs": [
+            {
+                "_eventname": "TransferSuccess",
+                "address": "0x1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d",
+                "params": [
+                    {"type": "ByStr20", "value": "0x381f4008505e940ad7681ec3468a719060caf796", "vname": "sender"},
+                    {"type": "ByStr20", "value": "0xb028055ea3bc78d759d10663da40d171dec992aa", "vname": "recipient"},
+                    {"type": "Uint128", "value": "1000", "vname": "amount"}
+                ]
+            },
+            {
+                "_eventname": "Approval",
+                "address": "0x1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d",
+                "params": []
+            }
+        ],
+        "success": true,
+        "transitions": [
+            {
+                "accepted": false,
+                "addr": "0x1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d",
+                "depth": 0,
+                "msg": {
+                    "_amount": "0",
+                    "_recipient": "0xb028055ea3bc78d759d10663da40d171dec992aa",
+                    "_tag": "RecipientAcceptTransfer",
+                    "params": [
+                        {"type": "ByStr20", "value": "0x381f4008505e940ad7681ec3468a719060caf796", "vname": "sender"},
+                        {"type": "ByStr20", "value": "0xb028055ea3bc78d759d10663da40d171dec992aa", "vname": "recipient"},
+                        {"type": "Uint128", "value": "1000", "vname": "amount"}
+                    ]
+                }
+            }
+        ]
+    }
+}"#;
+
+#[test]
+fn test_decode_zrc2_transfer_receipt() {
+    let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    let receipt: Receipt = TRANSFER_RECEIPT.parse().unwrap();
+    assert!(receipt.success);
+
+    let decoded = contract.decode_receipt(&receipt, None).unwrap();
+    assert_eq!(decoded.events.len(), 2);
+
+    let transfer = &decoded.events[0];
+    assert_eq!(transfer.name, "TransferSuccess");
+    assert_eq!(transfer.emitted_by, vec!["Transfer".to_string()]);
+    assert_eq!(
+        transfer.params[2],
+        (
+            "amount".to_string(),
+            DecodedValue::Value(Value::int(Type::Uint128, 1000).unwrap())
+        )
+    );
+
+    let unknown: Vec<_> = decoded.unknown_events().map(|e| e.name.as_str()).collect();
+    assert_eq!(unknown, vec!["Approval"]);
+
+    let message = &decoded.messages[0];
+    assert_eq!(message.tag, "RecipientAcceptTransfer");
+    assert_eq!(
+        message.amount.value(),
+        Some(&Value::int(Type::Uint128, 0).unwrap())
+    );
+    assert_eq!(message.sent_by, vec!["Transfer".to_string()]);
+}
+
+#[test]
+fn test_events_emitted_in_procedures() {
+    let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    let receipt: Receipt = r#"{
+        "success": true,
+        "event_logs": [{"_eventname": "Minted", "address": "0x1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d", "params": []}],
+        "transitions": []
+    }"#
+    .parse()
+    .unwrap();
+
+    let decoded = contract.decode_receipt(&receipt, None).unwrap();
+    assert_eq!(decoded.events[0].emitted_by, vec!["Mint".to_string()]);
+}
+
+#[test]
+fn test_undecodable_params() {
+    let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    let receipt: Receipt = r#"{
+        "success": false,
+        "event_logs": [
+            {
+                "_eventname": "Error",
+                "address": "0x1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d",
+                "params": [
+                    {
+                        "type": "0x1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d.Error",
+                        "value": {"constructor": "0x1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d.CodeNotOwner", "argtypes": [], "arguments": []},
+                        "vname": "err"
+                    }
+                ]
+            },
+            {
+                "_eventname": "Swapped",
+                "address": "0x9999999999999999999999999999999999999999",
+                "params": [
+                    {
+                        "type": "0x9999999999999999999999999999999999999999.Direction",
+                        "value": {"constructor": "0x9999999999999999999999999999999999999999.TokenToZil", "argtypes": [], "arguments": []},
+                        "vname": "direction"
+                    },
+                    {"type": "Uint128", "value": "12", "vname": "amount"}
+                ]
+            }
+        ],
+        "transitions": []
+    }"#
+    .parse()
+    .unwrap();
+
+    let decoded = contract.decode_receipt(&receipt, None).unwrap();
+    assert_eq!(
+        decoded.events[0].params[0].1,
+        DecodedValue::Value(Value::Adt {
+            constructor: "CodeNotOwner".to_string(),
+            type_args: vec![],
+            args: vec![],
+        })
+    );
+
+    // An event of another contract in the call chain.
+    let swapped = &decoded.events[1];
+    assert!(!swapped.is_known());
+    assert!(matches!(
+        &swapped.params[0].1,
+        DecodedValue::Raw { r#type, .. } if r#type.ends_with(".Direction")
+    ));
+    assert_eq!(
+        swapped.params[1].1.value(),
+        Some(&Value::int(Type::Uint128, 12).unwrap())
+    );
+}
+
+#[test]
+fn test_entries_of_other_addresses() {
+    let contract = Contract::from_path(&PathBuf::from("tests/contracts/ZRC2.scilla")).unwrap();
+    let receipt: Receipt = TRANSFER_RECEIPT.parse().unwrap();
+
+    let decoded = contract
+        .decode_receipt(&receipt, Some("0x1A2B3C4D5E6F708192A3B4C5D6E7F8091A2B3C4D"))
+        .unwrap();
+    assert_eq!(decoded.events[0].emitted_by, vec!["Transfer".to_string()]);
+    assert_eq!(decoded.messages[0].sent_by, vec!["Transfer".to_string()]);
+
+    let decoded = contract
+        .decode_receipt(&receipt, Some("0x9999999999999999999999999999999999999999"))
+        .unwrap();
+    assert_eq!(decoded.unknown_events().count(), 2);
+    assert!(!decoded.messages[0].is_known());
+}
+
+#[test]
+fn test_decode_receipt_without_source() {
+    let json = std::fs::read_to_string("tests/contract_info/ZRC2.json").unwrap();
+    let contract = Contract::from_contract_info_json(&json).unwrap();
+    let receipt: Receipt = TRANSFER_RECEIPT.parse().unwrap();
+    assert!(matches!(
+        contract.decode_receipt(&receipt, None),
+        Err(Error::NoSource)
+    ));
+}
+
+#[test]
+fn test_invalid_byte_strings() {
+    let decode = |s: &str| Value::from_json(&serde_json::json!(s), &Type::ByStr(2), None);
+    assert_eq!(decode("0xab01").unwrap(), Value::ByStrX(vec![0xab, 0x01]));
+    assert!(decode("0xaé1").is_err());
+    assert!(decode("0xabc").is_err());
+    assert!(decode("0xzz01").is_err());
+}
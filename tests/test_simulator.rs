@@ -0,0 +1,299 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use scilla_parser::{CallContext, Contract, Error, Simulator, Type, Value};
+
+use common::contract;
+
+const OWNER: [u8; 20] = [1; 20];
+const BOB: [u8; 20] = [2; 20];
+
+fn uint128(n: u128) -> Value {
+    Value::int(Type::Uint128, n).unwrap()
+}
+
+fn address(bytes: [u8; 20]) -> Value {
+    Value::ByStrX(bytes.to_vec())
+}
+
+fn string(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+fn entry<'v>(message: &'v Value, name: &str) -> &'v Value {
+    match message {
+        Value::Message(entries) => &entries.iter().find(|(n, _)| n == name).unwrap().1,
+        _ => panic!("{message} is not a message"),
+    }
+}
+
+fn zrc2(contract: &Contract) -> Simulator<'_> {
+    Simulator::new(
+        contract,
+        &[
+            ("contract_owner", address(OWNER)),
+            ("name", string("Token")),
+            ("symbol", string("TKN")),
+            ("decimals", Value::int(Type::Uint32, 12).unwrap()),
+            ("init_supply", uint128(1000)),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_token_transfers() {
+    let contract = contract("ZRC2");
+    let mut simulator = zrc2(&contract);
+    assert_eq!(simulator.field("total_supply"), Some(&uint128(1000)));
+
+    let transfer = [("to", address(BOB)), ("amount", uint128(100))];
+    let execution = simulator
+        .call("Transfer", &transfer, &CallContext::new(OWNER))
+        .unwrap();
+    assert_eq!(execution.events.len(), 1);
+    assert_eq!(
+        entry(&execution.events[0], "_eventname"),
+        &string("TransferSuccess")
+    );
+    let tags: Vec<&Value> = execution
+        .messages
+        .iter()
+        .map(|m| entry(m, "_tag"))
+        .collect();
+    assert_eq!(
+        tags,
+        vec![
+            &string("RecipientAcceptTransfer"),
+            &string("TransferSuccessCallBack")
+        ]
+    );
+    assert_eq!(execution.accepted, 0);
+    assert_eq!(
+        simulator.field("balances").unwrap().to_string(),
+        format!(
+            "[0x{} => Uint128 900, 0x{} => Uint128 100]",
+            "01".repeat(20),
+            "02".repeat(20)
+        )
+    );
+    assert_eq!(execution.state, simulator.state());
+
+    // Bob cannot transfer more than he has and the state is left unchanged.
+    let transfer = [("to", address(OWNER)), ("amount", uint128(200))];
+    let before = simulator.state();
+    match simulator.call("Transfer", &transfer, &CallContext::new(BOB)) {
+        Err(Error::Exception(Some(exception))) => {
+            assert_eq!(
+                entry(&exception, "code"),
+                &Value::int(Type::Int32, -2).unwrap()
+            )
+        }
+        other => panic!("expected an exception, got {other:?}"),
+    }
+    assert_eq!(simulator.state(), before);
+
+    // Nested maps are created on update.
+    let allowance = [("spender", address(BOB)), ("amount", uint128(50))];
+    simulator
+        .call("IncreaseAllowance", &allowance, &CallContext::new(OWNER))
+        .unwrap();
+    let transfer = [
+        ("from", address(OWNER)),
+        ("to", address(BOB)),
+        ("amount", uint128(30)),
+    ];
+    simulator
+        .call("TransferFrom", &transfer, &CallContext::new(BOB))
+        .unwrap();
+    assert_eq!(
+        simulator.field("allowances").unwrap().to_string(),
+        format!(
+            "[0x{} => [0x{} => Uint128 20]]",
+            "01".repeat(20),
+            "02".repeat(20)
+        )
+    );
+}
+
+#[test]
+fn test_state_updates() {
+    let contract = contract("HelloWorld");
+    let mut simulator = Simulator::new(&contract, &[("owner", address(OWNER))]).unwrap();
+    let set_hello = [("msg", string("Hi"))];
+
+    let execution = simulator
+        .call("setHello", &set_hello, &CallContext::new(BOB))
+        .unwrap();
+    assert_eq!(
+        entry(&execution.events[0], "code"),
+        &Value::int(Type::Int32, 1).unwrap()
+    );
+    assert_eq!(
+        simulator.field("welcome_msg"),
+        Some(&string("Hello world!"))
+    );
+
+    simulator
+        .call("setHello", &set_hello, &CallContext::new(OWNER))
+        .unwrap();
+    let execution = simulator
+        .call("getHello", &[], &CallContext::new(BOB))
+        .unwrap();
+    assert_eq!(entry(&execution.events[0], "msg"), &string("Hi"));
+
+    assert!(matches!(
+        simulator.call("setHello", &[], &CallContext::new(OWNER)),
+        Err(Error::InvalidValue(_))
+    ));
+    assert!(matches!(
+        simulator.call("SetHello", &set_hello, &CallContext::new(OWNER)),
+        Err(Error::InvalidValue(_))
+    ));
+}
+
+#[test]
+fn test_accepting_and_sending_funds() {
+    let contract = contract("SendZil");
+    let mut simulator = Simulator::new(&contract, &[]).unwrap();
+
+    let execution = simulator
+        .call("acceptZil", &[], &CallContext::new(BOB).with_amount(50))
+        .unwrap();
+    assert_eq!(execution.accepted, 50);
+    assert_eq!(entry(&execution.events[0], "value"), &uint128(50));
+
+    let execution = simulator
+        .call("dontAcceptZil", &[], &CallContext::new(BOB).with_amount(10))
+        .unwrap();
+    assert_eq!(execution.accepted, 0);
+    assert_eq!(simulator.field("_balance"), Some(&uint128(50)));
+
+    let fund = [("user", address(BOB)), ("amount", uint128(30))];
+    let execution = simulator
+        .call("fundUser", &fund, &CallContext::new(OWNER))
+        .unwrap();
+    assert_eq!(entry(&execution.messages[0], "_amount"), &uint128(30));
+    assert_eq!(simulator.field("_balance"), Some(&uint128(20)));
+
+    match simulator.call("fundUser", &fund, &CallContext::new(OWNER)) {
+        Err(Error::RuntimeError(message)) => {
+            assert_eq!(message, "cannot send 30 with a balance of 20")
+        }
+        other => panic!("expected a runtime error, got {other:?}"),
+    }
+    assert_eq!(simulator.field("_balance"), Some(&uint128(20)));
+}
+
+#[test]
+fn test_procedures_and_constraints() {
+    let contract = contract("ZRC6");
+    let params = |owner: [u8; 20]| {
+        [
+            ("initial_contract_owner", address(owner)),
+            ("initial_base_uri", string("https://example.com/")),
+            ("name", string("Creatures")),
+            ("symbol", string("CRT")),
+        ]
+    };
+    match Simulator::new(&contract, &params([0; 20])) {
+        Err(Error::RuntimeError(message)) => {
+            assert_eq!(message, "the contract constraint does not hold")
+        }
+        other => panic!("expected a runtime error, got {other:?}"),
+    }
+
+    let mut simulator = Simulator::new(&contract, &params(OWNER)).unwrap();
+    let pair = |to: [u8; 20], uri: &str| {
+        Value::pair(Type::ByStr(20), Type::String, address(to), string(uri))
+    };
+    let list = Value::list(
+        Type::Pair(Box::new(Type::ByStr(20)), Box::new(Type::String)),
+        vec![pair(BOB, "a"), pair(OWNER, "b")],
+    );
+    let execution = simulator
+        .call(
+            "BatchMint",
+            &[("to_token_uri_pair_list", list)],
+            &CallContext::new(OWNER),
+        )
+        .unwrap();
+    let uint256 = |n: u32| Value::int(Type::Uint256, n).unwrap();
+    assert_eq!(entry(&execution.events[0], "start_id"), &uint256(1));
+    assert_eq!(entry(&execution.events[0], "end_id"), &uint256(2));
+    assert_eq!(simulator.field("total_supply"), Some(&uint256(2)));
+
+    // Only minters can mint.
+    let mint = [("to", address(BOB)), ("token_uri", string("c"))];
+    assert!(matches!(
+        simulator.call("Mint", &mint, &CallContext::new(BOB)),
+        Err(Error::Exception(Some(_)))
+    ));
+}
+
+#[test]
+fn test_blockchain_context() {
+    let contract = contract("Timestamp");
+    let mut simulator = Simulator::new(&contract, &[]).unwrap();
+    let context = CallContext::new(BOB)
+        .with_block_number(10)
+        .with_timestamp(1_700_000_000);
+    let at = |block: u32| [("bnum", Value::BNum(block.into()))];
+    let execution = simulator.call("EventTimestamp", &at(10), &context).unwrap();
+    assert_eq!(
+        entry(&execution.events[0], "timestamp").to_string(),
+        "(Some Uint64 1700000000)"
+    );
+    let execution = simulator.call("EventTimestamp", &at(11), &context).unwrap();
+    assert_eq!(entry(&execution.events[0], "timestamp").to_string(), "None");
+
+    let contract = self::contract("chainid");
+    let mut simulator = Simulator::new(&contract, &[]).unwrap();
+    let execution = simulator
+        .call(
+            "EventChainID",
+            &[],
+            &CallContext::new(BOB).with_chain_id(333),
+        )
+        .unwrap();
+    assert_eq!(
+        entry(&execution.events[0], "chain_id"),
+        &Value::int(Type::Uint32, 333).unwrap()
+    );
+}
+
+#[test]
+fn test_remote_state() {
+    let contract = Contract::from_source(
+        "scilla_version 0
+        contract Reader ()
+        field seen : Uint128 = Uint128 0
+        transition Read (token : ByStr20)
+          maybe <- & token as ByStr20 with contract field total_supply : Uint128 end;
+          match maybe with
+          | Some t =>
+            supply <- & t.total_supply;
+            seen := supply
+          | None =>
+            e = { _exception : \"NotAToken\" };
+            throw e
+          end
+        end",
+    )
+    .unwrap();
+    let token = [9; 20];
+    let mut simulator = Simulator::new(&contract, &[]).unwrap().with_remote_field(
+        token,
+        "total_supply",
+        uint128(77),
+    );
+    simulator
+        .call("Read", &[("token", address(token))], &CallContext::new(BOB))
+        .unwrap();
+    assert_eq!(simulator.field("seen"), Some(&uint128(77)));
+    assert!(matches!(
+        simulator.call("Read", &[("token", address(BOB))], &CallContext::new(BOB)),
+        Err(Error::Exception(Some(_)))
+    ));
+}
@@ -0,0 +1,213 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Contract, Error};
+
+fn errors(src: &str) -> Vec<String> {
+    Contract::from_source(src)
+        .unwrap()
+        .type_check()
+        .unwrap()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn test_contracts_are_well_typed() {
+    for entry in std::fs::read_dir("tests/contracts").unwrap() {
+        let path = entry.unwrap().path();
+        let contract = Contract::from_path(&PathBuf::from(&path)).unwrap();
+        assert_eq!(
+            *contract.type_check().unwrap(),
+            vec![],
+            "{}",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn test_expressions() {
+    assert_eq!(
+        errors(
+            "scilla_version 0
+library Poly
+let id = tfun 'A => fun (a : 'A) => a
+let id_uint32 = @id Uint32
+let ok = let n = Uint32 1 in id_uint32 n
+let wrong_arg = let s = \"x\" in id_uint32 s
+let not_instantiated = let n = Uint32 1 in id n
+let not_poly = @ok Uint32
+let too_many = let n = Uint32 1 in id_uint32 n n
+let pair = let n = Uint32 1 in let s = \"x\" in Pair {Uint32 String} s n
+let unknown = Foo
+let sum = let n = Uint32 1 in let m = Uint64 1 in builtin add n m
+let annotated : String = Uint32 1
+contract Poly ()"
+        ),
+        vec![
+            "6:32: argument `s` of `id_uint32` has type String but Uint32 was expected",
+            "7:44: `id` is polymorphic and must be instantiated with `@id` before being applied",
+            "8:16: `ok` of type Uint32 is not polymorphic",
+            "9:36: `id_uint32` of type Uint32 -> Uint32 is applied to too many arguments",
+            "10:47: argument `s` of `Pair` has type String but Uint32 was expected",
+            "10:47: argument `n` of `Pair` has type Uint32 but String was expected",
+            "11:15: undefined constructor `Foo`",
+            "12:51: builtin add expects arguments of the same type, got Uint32 and Uint64",
+            "13:1: value of `annotated` has type Uint32 but String was expected",
+        ]
+    );
+}
+
+#[test]
+fn test_statements() {
+    assert_eq!(
+        errors(
+            "scilla_version 0
+contract Wallet (owner : ByStr20)
+field balances : Map ByStr20 Uint128 = Emp ByStr20 Uint128
+field total : Uint128 = Uint32 0
+transition Deposit (to : ByStr20)
+  zero = Uint128 0;
+  balances[to][to] := zero;
+  b <- balances[zero];
+  e = { _eventname : \"Deposit\" };
+  send e;
+  n = Uint32 1;
+  total := n;
+  Pay n;
+  Deposit to
+end
+procedure Pay (to : ByStr20)
+  missing <- count
+end"
+        ),
+        vec![
+            "4:25: initial value of `total` has type Uint32 but Uint128 was expected",
+            "7:3: `balances` of type Map ByStr20 Uint128 is accessed with 2 keys",
+            "8:3: key `zero` of `balances` has type Uint128 but ByStr20 was expected",
+            "10:3: sent messages `e` has type Event but List Message was expected",
+            "12:3: value `n` stored in `total` has type Uint32 but Uint128 was expected",
+            "13:3: argument `n` of `Pay` has type Uint32 but ByStr20 was expected",
+            "14:3: transition `Deposit` cannot be called",
+            "17:3: undefined field `count`",
+        ]
+    );
+}
+
+#[test]
+fn test_matches() {
+    assert_eq!(
+        errors(
+            "scilla_version 0
+library Colors
+type Color = | Red | Green | Blue of Uint32
+let name =
+  fun (c : Color) =>
+    match c with
+    | Red => \"red\"
+    | Blue n => \"blue\"
+    end
+let pairs =
+  fun (p : Pair Bool Bool) =>
+    match p with
+    | Pair True _ => Uint32 0
+    | Pair False True => Uint32 1
+    | Pair _ False => Uint32 2
+    end
+let wrong_arm =
+  fun (b : Bool) =>
+    match b with
+    | True => Uint32 0
+    | False => \"one\"
+    end
+contract Colors ()
+transition Paint (c : Option Color)
+  match c with
+  | Some Red =>
+  | None =>
+  | Some (Blue n) =>
+  end;
+  match c with
+  | Some Green =>
+  | Some _ =>
+  | _ =>
+  end;
+  match c with
+  | Green =>
+  | _ =>
+  end
+end"
+        ),
+        vec![
+            "6:5: match on `c` is not exhaustive, missing Green",
+            "21:16: the arm has type String but Uint32 was expected",
+            "25:3: match on `c` is not exhaustive, missing Some Green",
+            "35:3: pattern `Green` cannot match a value of type Option Color",
+        ]
+    );
+}
+
+#[test]
+fn test_imports() {
+    let library = |imports: &str| {
+        errors(&format!(
+            "scilla_version 0
+{imports}
+library Flags
+let both = fun (a : Bool) => fun (b : Bool) => andb a b
+let count = fun (l : List Uint32) => let f = @list_length Uint32 in f l
+let unknown = fun (a : Bool) => bool_to_json a
+contract Flags ()"
+        ))
+    };
+    // The standard library functions are only in scope when their library is imported.
+    assert_eq!(
+        library(""),
+        vec![
+            "4:48: undefined variable `andb`",
+            "5:46: undefined variable `list_length`",
+            "6:33: undefined variable `bool_to_json`",
+        ]
+    );
+    assert_eq!(
+        library("import BoolUtils ListUtils"),
+        vec!["6:33: undefined variable `bool_to_json`"]
+    );
+    // Names may come from a library that is not modelled.
+    assert_eq!(
+        library("import BoolUtils ListUtils Conversions"),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn test_serializable() {
+    assert_eq!(
+        errors(
+            "scilla_version 0
+contract Store (table : Map ByStr20 Uint128)
+field handler : Uint32 -> Uint32 = fun (x : Uint32) => x
+field nested : Map ByStr20 (Map Uint32 (List String)) = Emp ByStr20 (Map Uint32 (List String))
+field messages : List Message = Nil {Message}
+transition Set (amounts : Map ByStr20 Uint128, tags : List (Pair String Uint32))
+end
+procedure Internal (amounts : Map ByStr20 Uint128)
+end"
+        ),
+        vec![
+            "2:17: contract parameter `table` has type Map ByStr20 Uint128, which is not serializable",
+            "3:1: field `handler` has type Uint32 -> Uint32, which is not serializable",
+            "5:1: field `messages` has type List Message, which is not serializable",
+            "6:17: parameter `amounts` of `Set` has type Map ByStr20 Uint128, which is not serializable",
+        ]
+    );
+}
+
+#[test]
+fn test_type_check_unavailable_without_source() {
+    let json = std::fs::read_to_string("tests/contract_info/ZRC2.json").unwrap();
+    let contract = Contract::from_contract_info_json(&json).unwrap();
+    assert!(matches!(contract.type_check(), Err(Error::NoSource)));
+}
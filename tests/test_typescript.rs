@@ -0,0 +1,62 @@
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+
+use scilla_parser::{Contract, Error};
+
+#[test]
+fn test_typescript_matches_golden_files() {
+    for name in ["HelloWorld", "ZRC2"] {
+        let contract =
+            Contract::from_path(&PathBuf::from(format!("tests/contracts/{name}.scilla"))).unwrap();
+        let expected = std::fs::read_to_string(format!("tests/typescript/{name}.ts")).unwrap();
+        assert_eq!(contract.typescript().unwrap(), expected, "{name}");
+    }
+}
+
+#[test]
+fn test_typescript_types() {
+    let contract = Contract::from_source(
+        r#"scilla_version 0
+        library Registry
+        type Status = | Active | Retired of BNum
+        contract Registry (admin : ByStr20 with end)
+        field entries : Map Uint256 (Pair String (Option (List Int64))) = Emp Uint256 (Pair String (Option (List Int64)))
+        field statuses : Map ByStr20 Status = Emp ByStr20 Status
+        transition Retire (id : Uint256, owners : List ByStr20, proof : ByStr)
+        end"#,
+    )
+    .unwrap();
+    let ts = contract.typescript().unwrap();
+    assert!(ts.contains("  admin: ByStr;\n"));
+    assert!(ts.contains("  entries: Record<string, Pair<string, Option<Array<Int64>>>>;\n"));
+    assert!(ts.contains("  statuses: Record<string, Adt>;\n"));
+    assert!(ts.contains(
+        r#"| { _tag: "Retire"; params: { id: Uint256; owners: Array<ByStr>; proof: ByStr } };"#
+    ));
+    assert!(ts.contains(r#"Retire: { id: "Uint256", owners: "List (ByStr20)", proof: "ByStr" },"#));
+    assert!(ts.contains("export type RegistryEvent = never;\n"));
+}
+
+#[test]
+fn test_typescript_event_logs() {
+    let contract = Contract::from_source(
+        r#"scilla_version 0
+        contract Events ()
+        transition Emit (amount : Uint128, value : ByStr)
+          e = { _eventname : "Emitted"; amount : amount; value : value; unknown : unknown_value };
+          event e
+        end"#,
+    )
+    .unwrap();
+    let ts = contract.typescript().unwrap();
+    assert!(ts.contains(
+        r#"| { _eventname: "Emitted"; address: ByStr; params: [EventParam<"amount", Uint128>, EventParam<"value", ByStr>, EventParam<"unknown", unknown>] };"#
+    ));
+}
+
+#[test]
+fn test_typescript_unavailable_without_source() {
+    let json = std::fs::read_to_string("tests/contract_info/ZRC2.json").unwrap();
+    let contract = Contract::from_contract_info_json(&json).unwrap();
+    assert!(matches!(contract.typescript(), Err(Error::NoSource)));
+}
@@ -0,0 +1,52 @@
+// Generated from the `HelloWorld` contract. Do not edit.
+
+export type Int32 = string;
+export type Int64 = string;
+export type Int128 = string;
+export type Int256 = string;
+export type Uint32 = string;
+export type Uint64 = string;
+export type Uint128 = string;
+export type Uint256 = string;
+export type BNum = string;
+/** A hex string prefixed with `0x`. */
+export type ByStr = string;
+export type Bool = { constructor: "True" | "False"; argtypes: []; arguments: [] };
+export type Option<T> =
+  | { constructor: "Some"; argtypes: string[]; arguments: [T] }
+  | { constructor: "None"; argtypes: string[]; arguments: [] };
+export type Pair<A, B> = { constructor: "Pair"; argtypes: string[]; arguments: [A, B] };
+/** A value of a user-defined ADT. */
+export type Adt = { constructor: string; argtypes: string[]; arguments: unknown[] };
+/** A parameter in the format expected by `Contract.call` of zilliqa-js. */
+export type CallParam = { vname: string; type: string; value: unknown };
+/** A parameter of an event log in a transaction receipt. */
+export type EventParam<N extends string, T> = { vname: N; type: string; value: T };
+
+export interface HelloWorldInit {
+  owner: ByStr;
+}
+
+export interface HelloWorldState {
+  _balance: Uint128;
+  welcome_msg: string;
+}
+
+export type HelloWorldTransition =
+  | { _tag: "setHello"; params: { msg: string } }
+  | { _tag: "getHello"; params: {} };
+
+export type HelloWorldEvent =
+  | { _eventname: "setHello()"; address: ByStr; params: [EventParam<"code", Int32>] }
+  | { _eventname: "getHello()"; address: ByStr; params: [EventParam<"msg", string>] };
+
+const helloWorldParamTypes: Record<string, Record<string, string>> = {
+  setHello: { msg: "String" },
+  getHello: {  },
+};
+
+/** The parameters of a `HelloWorld` transition as expected by `Contract.call`. */
+export function helloWorldCallParams(call: HelloWorldTransition): CallParam[] {
+  const types = helloWorldParamTypes[call._tag];
+  return Object.entries(call.params).map(([vname, value]) => ({ vname, type: types[vname], value }));
+}
@@ -0,0 +1,70 @@
+// Generated from the `FungibleToken` contract. Do not edit.
+
+export type Int32 = string;
+export type Int64 = string;
+export type Int128 = string;
+export type Int256 = string;
+export type Uint32 = string;
+export type Uint64 = string;
+export type Uint128 = string;
+export type Uint256 = string;
+export type BNum = string;
+/** A hex string prefixed with `0x`. */
+export type ByStr = string;
+export type Bool = { constructor: "True" | "False"; argtypes: []; arguments: [] };
+export type Option<T> =
+  | { constructor: "Some"; argtypes: string[]; arguments: [T] }
+  | { constructor: "None"; argtypes: string[]; arguments: [] };
+export type Pair<A, B> = { constructor: "Pair"; argtypes: string[]; arguments: [A, B] };
+/** A value of a user-defined ADT. */
+export type Adt = { constructor: string; argtypes: string[]; arguments: unknown[] };
+/** A parameter in the format expected by `Contract.call` of zilliqa-js. */
+export type CallParam = { vname: string; type: string; value: unknown };
+/** A parameter of an event log in a transaction receipt. */
+export type EventParam<N extends string, T> = { vname: N; type: string; value: T };
+
+export interface FungibleTokenInit {
+  contract_owner: ByStr;
+  name: string;
+  symbol: string;
+  decimals: Uint32;
+  init_supply: Uint128;
+}
+
+export interface FungibleTokenState {
+  _balance: Uint128;
+  total_supply: Uint128;
+  balances: Record<string, Uint128>;
+  allowances: Record<string, Record<string, Uint128>>;
+}
+
+export type FungibleTokenTransition =
+  | { _tag: "Mint"; params: { recipient: ByStr; amount: Uint128 } }
+  | { _tag: "Burn"; params: { burn_account: ByStr; amount: Uint128 } }
+  | { _tag: "IncreaseAllowance"; params: { spender: ByStr; amount: Uint128 } }
+  | { _tag: "DecreaseAllowance"; params: { spender: ByStr; amount: Uint128 } }
+  | { _tag: "Transfer"; params: { to: ByStr; amount: Uint128 } }
+  | { _tag: "TransferFrom"; params: { from: ByStr; to: ByStr; amount: Uint128 } };
+
+export type FungibleTokenEvent =
+  | { _eventname: "Minted"; address: ByStr; params: [EventParam<"minter", ByStr>, EventParam<"recipient", ByStr>, EventParam<"amount", Uint128>] }
+  | { _eventname: "Burnt"; address: ByStr; params: [EventParam<"burner", ByStr>, EventParam<"burn_account", ByStr>, EventParam<"amount", Uint128>] }
+  | { _eventname: "IncreasedAllowance"; address: ByStr; params: [EventParam<"token_owner", ByStr>, EventParam<"spender", ByStr>, EventParam<"new_allowance", Uint128>] }
+  | { _eventname: "DecreasedAllowance"; address: ByStr; params: [EventParam<"token_owner", ByStr>, EventParam<"spender", ByStr>, EventParam<"new_allowance", Uint128>] }
+  | { _eventname: "TransferSuccess"; address: ByStr; params: [EventParam<"sender", ByStr>, EventParam<"recipient", ByStr>, EventParam<"amount", Uint128>] }
+  | { _eventname: "TransferFromSuccess"; address: ByStr; params: [EventParam<"initiator", ByStr>, EventParam<"sender", ByStr>, EventParam<"recipient", ByStr>, EventParam<"amount", Uint128>] };
+
+const fungibleTokenParamTypes: Record<string, Record<string, string>> = {
+  Mint: { recipient: "ByStr20", amount: "Uint128" },
+  Burn: { burn_account: "ByStr20", amount: "Uint128" },
+  IncreaseAllowance: { spender: "ByStr20", amount: "Uint128" },
+  DecreaseAllowance: { spender: "ByStr20", amount: "Uint128" },
+  Transfer: { to: "ByStr20", amount: "Uint128" },
+  TransferFrom: { from: "ByStr20", to: "ByStr20", amount: "Uint128" },
+};
+
+/** The parameters of a `FungibleToken` transition as expected by `Contract.call`. */
+export function fungibleTokenCallParams(call: FungibleTokenTransition): CallParam[] {
+  const types = fungibleTokenParamTypes[call._tag];
+  return Object.entries(call.params).map(([vname, value]) => ({ vname, type: types[vname], value }));
+}